@@ -25,3 +25,16 @@ pub struct SetLiquidityFeeEvent {
     pub resources: Vec<ResourceAddress>,
     pub fee: Decimal,
 }
+
+/// Event emitted when the default maker fee is set.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SetMakerFeeDefaultEvent {
+    pub fee: Decimal,
+}
+
+/// Event emitted when a maker fee is set.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SetMakerFeeEvent {
+    pub package_address: PackageAddress,
+    pub fee: Decimal,
+}
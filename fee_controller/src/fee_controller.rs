@@ -8,12 +8,15 @@ use crate::util::*;
     SetProtocolFeeDefaultEvent, 
     SetLiquidityFeeDefaultEvent, 
     SetProtocolFeeEvent, 
-    SetLiquidityFeeEvent
+    SetLiquidityFeeEvent,
+    SetMakerFeeDefaultEvent,
+    SetMakerFeeEvent
 )]
 #[types(
     ResourcesKey,
     PackageAddress,
     u16,
+    i32,
 )]
 mod fee_controller_mod {
     enable_method_auth! {
@@ -25,11 +28,16 @@ mod fee_controller_mod {
             set_liquidity_fee_default => restrict_to: [fee_manager];
             set_protocol_fee => restrict_to: [fee_manager];
             set_liquidity_fee => restrict_to: [fee_manager];
+            set_maker_fee_default => restrict_to: [fee_manager];
+            set_maker_fee => restrict_to: [fee_manager];
             get_protocol_fee_default => PUBLIC;
             get_liquidity_fee_default => PUBLIC;
             get_protocol_fee => PUBLIC;
             get_liquidity_fee => PUBLIC;
             get_fees => PUBLIC;
+            get_maker_fee_default => PUBLIC;
+            get_maker_fee => PUBLIC;
+            get_maker_taker_fees => PUBLIC;
         }
     }
 
@@ -37,6 +45,8 @@ mod fee_controller_mod {
     const MAX_PROTOCOL_FEE: u16 = 10000u16;
     // Maximum liquidity fee of 5% in basis points hundredths.
     const MAX_LIQUIDITY_FEE: u16 = 50000u16;
+    // Maximum maker fee or rebate of 1% in basis points hundredths.
+    const MAX_MAKER_FEE: i32 = 10000i32;
 
     struct FeeController {
         /// Default protocol fee.
@@ -47,6 +57,10 @@ mod fee_controller_mod {
         protocol_fees: KeyValueStore<PackageAddress, u16>,
         /// Liquidity fee for a combination of resources.
         liquidity_fees: KeyValueStore<ResourcesKey, u16>,
+        /// Default maker fee. A negative fee is a rebate.
+        maker_fee_default: i32,
+        /// Maker fee for a package. A negative fee is a rebate.
+        maker_fees: KeyValueStore<PackageAddress, i32>,
     }
 
     impl FeeController {
@@ -66,11 +80,16 @@ mod fee_controller_mod {
         /// * `set_liquidity_fee_default` - Fee manager required.
         /// * `set_protocol_fee` - Fee manager required.
         /// * `set_liquidity_fee` - Fee manager required.
+        /// * `set_maker_fee_default` - Fee manager required.
+        /// * `set_maker_fee` - Fee manager required.
         /// * `get_protocol_fee_default` - Public.
         /// * `get_liquidity_fee_default` - Public.
         /// * `get_protocol_fee` - Public.
         /// * `get_liquidity_fee` - Public.
         /// * `get_fees` - Public.
+        /// * `get_maker_fee_default` - Public.
+        /// * `get_maker_fee` - Public.
+        /// * `get_maker_taker_fees` - Public.
         ///
         pub fn new(admin_badge_address: ResourceAddress) -> Global<FeeController> {
             // Instantiate component
@@ -95,6 +114,8 @@ mod fee_controller_mod {
                 liquidity_fee_default: 3000u16,
                 protocol_fees: KeyValueStore::new_with_registered_type(),
                 liquidity_fees: KeyValueStore::new_with_registered_type(),
+                maker_fee_default: 0i32,
+                maker_fees: KeyValueStore::new_with_registered_type(),
             }
             .instantiate()
         }
@@ -215,6 +236,62 @@ mod fee_controller_mod {
             });
         }
 
+        /// FEE MANAGER: Set default maker fee. A negative fee is a rebate paid to makers.
+        ///
+        /// # Arguments
+        ///
+        /// * `fee` - Default maker fee.
+        ///
+        /// # Panics
+        ///
+        /// * If `fee` is less than -1% or greater than 1%.
+        ///
+        /// # Events
+        /// 
+        /// * `SetMakerFeeDefaultEvent` - Event emitted when default maker fee is set.
+        /// 
+        pub fn set_maker_fee_default(&mut self, fee: i32) {
+            assert!(
+                (-MAX_MAKER_FEE..=MAX_MAKER_FEE).contains(&fee),
+                "Maker fee must be between -1% and 1%"
+            );
+
+            self.maker_fee_default = fee;
+
+            Runtime::emit_event(SetMakerFeeDefaultEvent { 
+                fee: Decimal::from_signed_basis_point_hundredths(fee) 
+            });
+        }
+
+        /// FEE MANAGER: Set maker fee. A negative fee is a rebate paid to makers.
+        ///
+        /// # Arguments
+        ///
+        /// * `package_address` - Package address for protocol.
+        /// * `fee` - Maker fee.
+        ///
+        /// # Panics
+        ///
+        /// * If `fee` is less than -1% or greater than 1%.
+        /// 
+        /// # Events
+        /// 
+        /// * `SetMakerFeeEvent` - Event emitted when maker fee is set.
+        ///
+        pub fn set_maker_fee(&mut self, package_address: PackageAddress, fee: i32) {
+            assert!(
+                (-MAX_MAKER_FEE..=MAX_MAKER_FEE).contains(&fee),
+                "Maker fee must be between -1% and 1%"
+            );
+
+            self.maker_fees.insert(package_address, fee);
+
+            Runtime::emit_event(SetMakerFeeEvent {
+                package_address,
+                fee: Decimal::from_signed_basis_point_hundredths(fee) 
+            });
+        }
+
         /// Get protocol fee default.
         ///
         /// # Returns
@@ -292,5 +369,50 @@ mod fee_controller_mod {
                 self.get_liquidity_fee(resource_addresses),
             )
         }
+
+        /// Get maker fee default.
+        ///
+        /// # Returns
+        ///
+        /// * `Decimal` - Maker fee default as ratio. Negative for a rebate.
+        ///
+        pub fn get_maker_fee_default(&self) -> Decimal {
+            Decimal::from_signed_basis_point_hundredths(self.maker_fee_default)
+        }
+
+        /// Get maker fee.
+        ///
+        /// # Arguments
+        ///
+        /// * `package_address` - Package address for protocol.
+        ///
+        /// # Returns
+        ///
+        /// * `Decimal` - Maker fee as ratio. Negative for a rebate.
+        ///
+        pub fn get_maker_fee(&self, package_address: PackageAddress) -> Decimal {
+            match self.maker_fees.get(&package_address) {
+                Some(fee) => Decimal::from_signed_basis_point_hundredths(*fee),
+                None => Decimal::from_signed_basis_point_hundredths(self.maker_fee_default),
+            }
+        }
+
+        /// Get maker and taker fees. The taker fee is the protocol fee.
+        ///
+        /// # Arguments
+        ///
+        /// * `package_address` - Package address for protocol.
+        ///
+        /// # Returns
+        ///
+        /// * `Decimal` - Maker fee as ratio. Negative for a rebate.
+        /// * `Decimal` - Taker fee as ratio.
+        ///
+        pub fn get_maker_taker_fees(&self, package_address: PackageAddress) -> (Decimal, Decimal) {
+            (
+                self.get_maker_fee(package_address),
+                self.get_protocol_fee(package_address),
+            )
+        }
     }
 }
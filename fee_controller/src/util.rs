@@ -36,6 +36,7 @@ impl From<Vec<ResourceAddress>> for ResourcesKey {
 /// Basis points math
 pub trait BasisPoints {
     fn from_basis_point_hundredths(value: u16) -> Self;
+    fn from_signed_basis_point_hundredths(value: i32) -> Self;
 }
 
 /// Implement BasisPoints for Decimal
@@ -44,4 +45,9 @@ impl BasisPoints for Decimal {
         let multiplier: I192 = I192::from(10u8).pow(12);
         Decimal(I192::from(value) * multiplier)
     }
+
+    fn from_signed_basis_point_hundredths(value: i32) -> Self {
+        let magnitude = Self::from_basis_point_hundredths(value.unsigned_abs() as u16);
+        if value < 0 { -magnitude } else { magnitude }
+    }
 }
\ No newline at end of file
@@ -139,6 +139,35 @@ pub fn set_method_with_u16_input_receipt(
     receipt
 }
 
+// generic setter - signed input
+pub fn set_method_with_i32_input_receipt(
+    vars: &mut Vars,
+    fee_controller_component: ComponentAddress,
+    method_name: &str,
+    with_proof: bool,
+    input: i32,
+) -> TransactionReceipt {
+    let manifest = if with_proof {
+        ManifestBuilder::new()
+            .create_proof_from_account_of_amount(
+                vars.admin_account_component_address,
+                vars.admin_badge_resource_address,
+                dec!(1))
+            .call_method(fee_controller_component, method_name, manifest_args!(input))
+            .build()
+    } else {
+        ManifestBuilder::new()
+            .call_method(fee_controller_component, method_name, manifest_args!(input))
+            .build()
+    };
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
+    );
+    println!("{:?}", receipt);
+    receipt
+}
+
 // set protocol fee receipt
 pub fn set_protocol_fee_receipt_with_proof(
     vars: &mut Vars,
@@ -213,6 +242,43 @@ pub fn set_liquidity_fee_receipt_with_proof(
     receipt
 }
 
+// set maker fee receipt
+pub fn set_maker_fee_receipt_with_proof(
+    vars: &mut Vars,
+    fee_controller_component: ComponentAddress,
+    with_proof: bool,
+    package_address: PackageAddress,
+    fee: i32,
+) -> TransactionReceipt {
+    let manifest = if with_proof {
+        ManifestBuilder::new()
+            .create_proof_from_account_of_amount(
+                vars.admin_account_component_address,
+                vars.admin_badge_resource_address,
+                dec!(1))
+            .call_method(
+                fee_controller_component,
+                "set_maker_fee",
+                manifest_args!(package_address, fee),
+            )
+            .build()
+    } else {
+        ManifestBuilder::new()
+            .call_method(
+                fee_controller_component,
+                "set_maker_fee",
+                manifest_args!(package_address, fee),
+            )
+            .build()
+    };
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
+    );
+    println!("{:?}", receipt);
+    receipt
+}
+
 // SET methods
 pub fn set_protocol_fee_default(
     vars: &mut Vars,
@@ -276,6 +342,37 @@ pub fn set_liquidity_fee(
     receipt.expect_commit_success();
 }
 
+pub fn set_maker_fee_default(
+    vars: &mut Vars,
+    fee_controller_component: ComponentAddress,
+    input: i32,
+) {
+    let receipt = set_method_with_i32_input_receipt(
+        vars,
+        fee_controller_component,
+        "set_maker_fee_default",
+        true,
+        input,
+    );
+    receipt.expect_commit_success();
+}
+
+pub fn set_maker_fee(
+    vars: &mut Vars,
+    fee_controller_component: ComponentAddress,
+    package_address: PackageAddress,
+    fee: i32,
+) {
+    let receipt = set_maker_fee_receipt_with_proof(
+        vars,
+        fee_controller_component,
+        true,
+        package_address,
+        fee,
+    );
+    receipt.expect_commit_success();
+}
+
 // GET METHODS:
 pub fn get_protocol_fee_default(
     vars: &mut Vars,
@@ -346,3 +443,43 @@ pub fn get_fees(
         .expect_commit_success()
         .output::<(Decimal, Decimal)>(1)
 }
+
+pub fn get_maker_fee_default(
+    vars: &mut Vars,
+    fee_controller_component: ComponentAddress,
+) -> Decimal {
+    let receipt = get_method_with_no_input_receipt(
+        vars,
+        fee_controller_component,
+        "get_maker_fee_default",
+    );
+    receipt.expect_commit_success().output::<Decimal>(1)
+}
+
+pub fn get_maker_fee(
+    vars: &mut Vars,
+    fee_controller_component: ComponentAddress,
+    package_address: PackageAddress,
+) -> Decimal {
+    let receipt = get_method_with_package_address_input_receipt(
+        vars,
+        fee_controller_component,
+        "get_maker_fee",
+        package_address,
+    );
+    receipt.expect_commit_success().output::<Decimal>(1)
+}
+
+pub fn get_maker_taker_fees(
+    vars: &mut Vars,
+    fee_controller_component: ComponentAddress,
+    package_address: PackageAddress,
+) -> (Decimal, Decimal) {
+    let receipt = get_method_with_package_address_input_receipt(
+        vars,
+        fee_controller_component,
+        "get_maker_taker_fees",
+        package_address,
+    );
+    receipt.expect_commit_success().output::<(Decimal, Decimal)>(1)
+}
@@ -47,3 +47,25 @@ pub fn test_basic_04() {
         Decimal::from_str("0.065").unwrap()
     );
 }
+
+#[test]
+pub fn test_signed_01() {
+    // ARRANGE
+    // ACT
+    // ASSERT
+    assert_eq!(
+        Decimal::from_signed_basis_point_hundredths(-200),
+        Decimal::from_str("-0.0002").unwrap()
+    );
+}
+
+#[test]
+pub fn test_signed_02() {
+    // ARRANGE
+    // ACT
+    // ASSERT
+    assert_eq!(
+        Decimal::from_signed_basis_point_hundredths(200),
+        Decimal::from_basis_point_hundredths(200)
+    );
+}
@@ -128,3 +128,47 @@ fn test_get_fees_01() {
     assert_eq!(protocol_fee, protocol_fee_default);
     assert_eq!(liquidity_fee, liquidity_fee_default);
 }
+
+#[test]
+fn test_get_maker_fee_default_01() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+
+    // ACT
+    let maker_fee_default =
+        fee_controller::get_maker_fee_default(&mut vars, fee_controller_component);
+
+    // ASSERT
+    assert_eq!(maker_fee_default, Decimal::ZERO);
+}
+
+#[test]
+fn test_get_maker_taker_fees_01() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+    let some_random_package_address = vars.fee_controller_package_address;
+    fee_controller::set_maker_fee(
+        &mut vars,
+        fee_controller_component,
+        some_random_package_address,
+        -100i32,
+    );
+    let protocol_fee = fee_controller::get_protocol_fee(
+        &mut vars,
+        fee_controller_component,
+        some_random_package_address,
+    );
+
+    // ACT
+    let (maker_fee, taker_fee) = fee_controller::get_maker_taker_fees(
+        &mut vars,
+        fee_controller_component,
+        some_random_package_address,
+    );
+
+    // ASSERT
+    assert_eq!(maker_fee, Decimal::from_str("-0.0001").unwrap());
+    assert_eq!(taker_fee, protocol_fee);
+}
@@ -440,3 +440,109 @@ fn test_set_liquidity_fee_05() {
     // ASSERT
     assert_eq!(liquidity_fee, Decimal::from_str("0.049999").unwrap());
 }
+
+#[test]
+fn test_set_maker_fee_default_01() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+
+    // ACT
+    let receipt = fee_controller::set_method_with_i32_input_receipt(
+        &mut vars,
+        fee_controller_component,
+        "set_maker_fee_default",
+        false,
+        -5i32,
+    );
+
+    // ASSERT
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn test_set_maker_fee_default_02() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+
+    // ACT
+    fee_controller::set_maker_fee_default(&mut vars, fee_controller_component, -100i32);
+    let maker_fee_default =
+        fee_controller::get_maker_fee_default(&mut vars, fee_controller_component);
+
+    // ASSERT
+    assert_eq!(maker_fee_default, Decimal::from_str("-0.0001").unwrap());
+}
+
+#[test]
+#[should_panic]
+fn test_set_maker_fee_default_03() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+
+    // ACT - ASSERT
+    fee_controller::set_maker_fee_default(&mut vars, fee_controller_component, -10001i32);
+}
+
+#[test]
+fn test_set_maker_fee_01() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+    let some_random_package_address = vars.fee_controller_package_address;
+
+    // ACT
+    let receipt = fee_controller::set_maker_fee_receipt_with_proof(
+        &mut vars,
+        fee_controller_component,
+        false,
+        some_random_package_address,
+        -5i32,
+    );
+
+    // ASSERT
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn test_set_maker_fee_02() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+    let some_random_package_address = vars.fee_controller_package_address;
+
+    // ACT
+    fee_controller::set_maker_fee(
+        &mut vars,
+        fee_controller_component,
+        some_random_package_address,
+        250i32,
+    );
+    let maker_fee = fee_controller::get_maker_fee(
+        &mut vars,
+        fee_controller_component,
+        some_random_package_address,
+    );
+
+    // ASSERT
+    assert_eq!(maker_fee, Decimal::from_str("0.00025").unwrap());
+}
+
+#[test]
+#[should_panic]
+fn test_set_maker_fee_03() {
+    // ARRANGE
+    let mut vars = setup();
+    let fee_controller_component = fee_controller::new_fee_controller_manifest(&mut vars);
+    let some_random_package_address = vars.fee_controller_package_address;
+
+    // ACT - ASSERT
+    fee_controller::set_maker_fee(
+        &mut vars,
+        fee_controller_component,
+        some_random_package_address,
+        10001i32,
+    );
+}
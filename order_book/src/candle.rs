@@ -0,0 +1,71 @@
+use scrypto::prelude::*;
+
+/// Open, high, low, close, and volume of fills in the order book during an epoch.
+/// Prices are calculated as `tokens_y / tokens_x`.
+///
+/// # Responsible for
+///
+/// * Track prices and volumes of fills during an epoch.
+/// * Link to the previous epoch with fills.
+///
+/// # Not responsible for
+///
+/// * Storing candles or finding the candle for an epoch.
+///
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct Candle {
+    /// Price of the first fill in the epoch.
+    pub open: Decimal,
+    /// Highest price filled in the epoch.
+    pub high: Decimal,
+    /// Lowest price filled in the epoch.
+    pub low: Decimal,
+    /// Price of the last fill in the epoch.
+    pub close: Decimal,
+    /// Amount of tokens filled in the epoch calculated in tokens x.
+    pub volume_x: Decimal,
+    /// Amount of tokens filled in the epoch calculated in tokens y.
+    pub volume_y: Decimal,
+    /// Previous epoch with fills. None if this is the first epoch with fills.
+    pub prev_epoch: Option<u64>,
+}
+
+impl Candle {
+    /// Create a new candle.
+    ///
+    /// # Arguments
+    ///
+    /// * `open` - Price of the first fill in the epoch.
+    /// * `prev_epoch` - Previous epoch with fills.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The new candle.
+    ///
+    pub fn new(open: Decimal, prev_epoch: Option<u64>) -> Self {
+        Self {
+            open,
+            high: open,
+            low: open,
+            close: open,
+            volume_x: Decimal::zero(),
+            volume_y: Decimal::zero(),
+            prev_epoch,
+        }
+    }
+
+    /// Add a fill to the candle.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - Price of the fill.
+    /// * `amount_x` - Amount of the fill calculated in tokens x.
+    ///
+    pub fn add_fill(&mut self, price: Decimal, amount_x: Decimal) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume_x += amount_x;
+        self.volume_y += amount_x * price;
+    }
+}
@@ -0,0 +1,17 @@
+use scrypto::prelude::*;
+
+/// Limits of one side of the order book aggregated into a price bucket.
+/// Prices are calculated as `tokens_y / tokens_x`.
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq)]
+pub struct DepthLevel {
+    /// Price of the bucket. Ask prices are rounded up and bid prices rounded down to a multiple of the bucket size.
+    pub price: Decimal,
+    /// Amount of tokens in the bucket calculated in tokens x.
+    pub amount_x: Decimal,
+    /// Amount of tokens in the bucket calculated in tokens y at the price of each limit.
+    pub amount_y: Decimal,
+    /// Amount of tokens from the best price up to and including the bucket calculated in tokens x.
+    pub cumulative_x: Decimal,
+    /// Amount of tokens from the best price up to and including the bucket calculated in tokens y.
+    pub cumulative_y: Decimal,
+}
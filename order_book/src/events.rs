@@ -1,5 +1,9 @@
 use scrypto::prelude::*;
 
+use crate::market_order_mode::*;
+use crate::order_book_state::*;
+use crate::trigger_mode::*;
+
 /// Event emitted when a new order book is created.
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct NewOrderBookEvent {
@@ -11,6 +15,28 @@ pub struct NewOrderBookEvent {
     pub token_x_address: ResourceAddress,
     /// The address of the token x for the new order book.
     pub token_y_address: ResourceAddress,
+    /// The minimum value of an order valued in tokens y.
+    pub min_order_size: Decimal,
+    /// The optional size of which limit order amounts must be a multiple valued in tokens x.
+    pub lot_size: Option<Decimal>,
+    /// The number of significant figures to which prices are truncated.
+    pub price_precision: u32,
+}
+
+/// Event emitted when the state of the order book is set.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SetStateEvent {
+    /// The new state of the order book.
+    pub state: OrderBookState,
+}
+
+/// Event emitted when tokens kept to pay maker rebates are swept to the fee vaults.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SweepRebatesEvent {
+    /// The amount of tokens x swept.
+    pub amount_x: Decimal,
+    /// The amount of tokens y swept.
+    pub amount_y: Decimal,
 }
 
 /// Event emitted when a limit order is placed.
@@ -24,6 +50,33 @@ pub struct LimitOrderEvent {
     pub price: Decimal,
     /// The amount the limit order is for valued in tokens x.
     pub amount: Decimal,
+    /// Whether the limit order was placed as post only.
+    pub is_post_only: bool,
+    /// Whether the price of the post only limit order was moved so as not to cross the order book.
+    pub is_repriced: bool,
+    /// Optional epoch after which the limit order expires.
+    pub expiry_epoch: Option<u64>,
+    /// Optional amount shown in the order book at a time for an iceberg order valued in tokens x.
+    pub display_amount: Option<Decimal>,
+}
+
+/// Event emitted when a limit order is amended.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct AmendOrderEvent {
+    /// The id of the amended order receipt.
+    pub order_id: NonFungibleLocalId,
+    /// Whether the amended limit order is on the ask or bid side of the order book.
+    pub is_ask: bool,
+    /// The price of the limit order before being amended calculated as `tokens_y / token_x`.
+    pub old_price: Decimal,
+    /// The price of the limit order after being amended calculated as `tokens_y / token_x`.
+    pub new_price: Decimal,
+    /// The unfilled amount of the limit order after being amended valued in tokens x.
+    pub amount: Decimal,
+    /// The amount of tokens x returned for the amended limit order.
+    pub amount_x: Decimal,
+    /// The amount of tokens y returned for the amended limit order.
+    pub amount_y: Decimal,
 }
 
 /// Event emitted when a market order is placed.
@@ -33,6 +86,63 @@ pub struct MarketOrderEvent {
     pub is_buy: bool, 
     /// Vector of fills for the market order. Each fill is a tuple of `(price, amount)`, where `amount` is valued in tokens x.
     pub fills: Vec<(Decimal, Decimal)>,
+    /// Execution mode of the market order.
+    pub mode: MarketOrderMode,
+    /// Whether the market order is the crossing part of a limit order.
+    pub is_limit_order: bool,
+}
+
+/// Event emitted for each limit filled by a market order.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct FillEvent {
+    /// Whether the market order is buying into the ask or selling into bid side of the order book.
+    pub is_buy: bool,
+    /// The price of the limit calculated as `tokens_y / token_x`.
+    pub price: Decimal,
+    /// The amount filled valued in tokens x.
+    pub amount_x: Decimal,
+    /// The amount filled valued in tokens y.
+    pub amount_y: Decimal,
+    /// The id of the first order receipt in the limit reached by the fill.
+    pub first_order_id: NonFungibleLocalId,
+    /// The id of the last order receipt in the limit reached by the fill.
+    pub last_order_id: NonFungibleLocalId,
+}
+
+/// Event emitted when a trigger order is placed.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct TriggerOrderEvent {
+    /// The id of the created order receipt.
+    pub order_id: NonFungibleLocalId,
+    /// Whether the trigger order sells tokens x or buys tokens x.
+    pub is_ask: bool,
+    /// The price at which the trigger order is triggered calculated as `tokens_y / token_x`.
+    pub trigger_price: Decimal,
+    /// The trigger mode of the trigger order.
+    pub mode: TriggerMode,
+    /// The optional stop price of the market order executed when triggered.
+    pub stop_price: Option<Decimal>,
+    /// The amount of input tokens escrowed for the market order.
+    pub amount_in: Decimal,
+    /// The amount of input tokens escrowed as a bounty for activating the trigger order.
+    pub bounty: Decimal,
+}
+
+/// Event emitted when a trigger order is executed.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ExecuteTriggerOrderEvent {
+    /// The id of the executed order receipt.
+    pub order_id: NonFungibleLocalId,
+    /// Whether the trigger order sold tokens x or bought tokens x.
+    pub is_ask: bool,
+    /// The last price the trigger order was checked against calculated as `tokens_y / token_x`.
+    pub last_price: Decimal,
+    /// The amount of tokens bought by the trigger order.
+    pub amount_out: Decimal,
+    /// The amount of input tokens of the trigger order not executed.
+    pub amount_remaining: Decimal,
+    /// The amount of input tokens paid as a bounty to the caller.
+    pub bounty: Decimal,
 }
 
 /// Event emitted when an order is claimed.
@@ -46,19 +156,30 @@ pub struct ClaimOrderEvent {
     pub price: Decimal,
     /// The amount the claimed limit order was for valued in tokens x.
     pub amount_canceled: Decimal,
-    /// The amount the claimed limit order was filled valued in tokens x.
+    /// The amount the claimed limit order was filled since last claimed valued in tokens x.
     pub amount_filled: Decimal,
     /// The amount of tokens x received for the claimed limit order.
     pub amount_x: Decimal,
     /// The amount of tokens y received for the claimed limit order.
     pub amount_y: Decimal,
+    /// The maker fee taken from the filled tokens of the claimed limit order.
+    pub maker_fee: Decimal,
+    /// The maker rebate added to the filled tokens of the claimed limit order.
+    pub maker_rebate: Decimal,
 }
 
 /// Event emitted when protocol fee is collected.
+/// The amount collected is calculated as `taker_fee + maker_fee - maker_rebate`.
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct ProtocolFeeEvent {
     /// Fee token address.
     pub token_address: ResourceAddress,
-    /// Fee amount.
+    /// Fee amount collected.
     pub amount: Decimal,
+    /// Fee amount paid by the taker of a market order.
+    pub taker_fee: Decimal,
+    /// Fee amount paid by the maker of a claimed limit order.
+    pub maker_fee: Decimal,
+    /// Fee amount kept by the order book to pay maker rebates.
+    pub maker_rebate: Decimal,
 }
\ No newline at end of file
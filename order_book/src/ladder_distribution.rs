@@ -0,0 +1,14 @@
+use scrypto::prelude::*;
+
+/// Distribution of tokens across the levels of a limit order ladder.
+/// Levels are ordered from the start price to the end price of the ladder.
+///
+/// * `Flat` - Every level gets the same amount of tokens.
+/// * `Linear` - Each level gets an amount proportional to its position, starting at one.
+/// * `Geometric(Decimal)` - Each level gets the amount of the previous level multiplied by the contained ratio.
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LadderDistribution {
+    Flat,
+    Linear,
+    Geometric(Decimal),
+}
@@ -1,7 +1,18 @@
+pub mod candle;
+pub mod depth_level;
 pub mod events;
+pub mod ladder_distribution;
 pub mod limit;
+pub mod market_order_mode;
+pub mod market_order_quote;
+pub mod observation;
 pub mod order_book;
+pub mod order_book_state;
 pub mod order_receipt;
 pub mod order_status;
+pub mod post_only_mode;
 pub mod price;
-pub mod price_index;
\ No newline at end of file
+pub mod price_index;
+pub mod self_trade_mode;
+pub mod trigger;
+pub mod trigger_mode;
\ No newline at end of file
@@ -9,10 +9,12 @@ use crate::order_receipt::*;
 /// * Track token amounts.
 /// * Allocate tokens to order receipts.
 /// * Maintain head and tail of order receipt linked list used for FIFO ordering of limit orders.
+/// * Clear the skip flag of order receipts once they are filled.
+/// * Track maker rebates of unfilled orders at the maker fee of each order.
 /// 
 /// # Not responsible for
 /// 
-/// * Minting, burning, or updating order receipts.
+/// * Minting, burning, or otherwise updating order receipts.
 /// * Moving tokens.
 /// * Calculating between tokens x and tokens y.
 /// 
@@ -22,10 +24,20 @@ pub struct Limit {
     amount_x: Decimal,
     /// Sum of tokens calculated in tokens x that have been sold and not allocated to an order.
     amount_x_unallocated: Decimal,
+    /// Sum of tokens calculated in tokens x held in the hidden reserves of iceberg orders.
+    amount_x_reserve: Decimal,
+    /// Sum of maker rebates calculated in tokens x on the unfilled amounts of orders, at the maker fee of each order.
+    amount_x_rebate: Decimal,
     /// Head of order receipts linked list, the first order to be filled.
     head_id: u64,
     /// Tail of order receipts linked list, the last order to be filled.
     tail_id: u64,
+    /// Lower bound of the sequence of the head order. Orders in the limit with a lower sequence have been filled.
+    head_sequence: u64,
+    /// Lower bound of the expiry epochs of orders in the limit. None if no order in the limit expires.
+    min_expiry_epoch: Option<u64>,
+    /// Number of orders in the limit skipped by self trade prevention and put back at the front.
+    skipped_count: u64,
 }
 
 impl Limit {
@@ -40,8 +52,13 @@ impl Limit {
         Self { 
             amount_x: Decimal::zero(), 
             amount_x_unallocated: Decimal::zero(), 
+            amount_x_reserve: Decimal::zero(), 
+            amount_x_rebate: Decimal::zero(), 
             head_id: 0,
             tail_id: 0,
+            head_sequence: 0,
+            min_expiry_epoch: None,
+            skipped_count: 0,
         }
     }
 
@@ -75,6 +92,16 @@ impl Limit {
         self.amount_x_unallocated
     }
 
+    /// Get amount of tokens calculated in tokens x held in the hidden reserves of iceberg orders.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Decimal` - Amount of tokens calculated in tokens x.
+    /// 
+    pub fn get_amount_x_reserve(&self) -> Decimal {
+        self.amount_x_reserve
+    }
+
     /// Get id of the order receipt that is the first order in the linked list.
     /// 
     /// # Returns
@@ -95,12 +122,95 @@ impl Limit {
         self.tail_id
     }
 
+    /// Get id of the order receipt that was filled last.
+    /// This is the first order in the linked list if it is partly filled, else the order before it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order_receipt_manager` - Order receipt manager.
+    /// 
+    /// # Returns
+    /// 
+    /// * `u64` - Id of the order receipt. 0 if no order in the limit has been filled.
+    /// 
+    pub fn get_last_filled_id(&self, order_receipt_manager: &ResourceManager) -> u64 {
+        if self.head_id == 0 || self.amount_x_unallocated > Decimal::zero() {
+            return self.head_id;
+        }
+
+        order_receipt_manager
+            .get_non_fungible_data::<OrderReceipt>(&NonFungibleLocalId::integer(self.head_id))
+            .prev_id
+    }
+
+    /// Get lower bound of the sequence of the order receipt that is the first order in the linked list.
+    /// Orders in the limit with a lower sequence have been filled.
+    /// 
+    /// # Returns
+    /// 
+    /// * `u64` - Sequence of the order receipt.
+    /// 
+    pub fn get_head_sequence(&self) -> u64 {
+        self.head_sequence
+    }
+
+    /// Get if an order in the limit has been filled.
+    /// Orders with a lower sequence than the head order have been filled, unless they were skipped by 
+    /// self trade prevention and put back at the front of the limit.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order_data` - Order receipt data.
+    /// 
+    /// # Returns
+    /// 
+    /// * `bool` - True if the order has been filled.
+    /// 
+    /// # Requires
+    /// 
+    /// * `order_data` - Must be of an order in the limit that has not been removed.
+    /// 
+    pub fn is_filled(&self, order_data: &OrderReceipt) -> bool {
+        self.head_id == 0 || (!order_data.is_skipped && order_data.sequence < self.head_sequence)
+    }
+
+    /// Get if the limit may contain orders that have expired.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `epoch` - Current epoch number.
+    /// 
+    /// # Returns
+    /// 
+    /// * `bool` - True if an order in the limit may have expired.
+    /// 
+    pub fn has_expired_orders(&self, epoch: u64) -> bool {
+        match self.min_expiry_epoch {
+            Some(min_expiry_epoch) => epoch > min_expiry_epoch,
+            None => false,
+        }
+    }
+
+    /// Set the lower bound of the expiry epochs of orders in the limit.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `min_expiry_epoch` - Lowest expiry epoch of orders in the limit. None if no order in the limit expires.
+    /// 
+    pub fn set_min_expiry_epoch(&mut self, min_expiry_epoch: Option<u64>) {
+        self.min_expiry_epoch = min_expiry_epoch;
+    }
+
     /// Add a limit order to the limit.
     /// 
     /// # Arguments
     /// 
     /// * `order_id` - Id of the order receipt.
+    /// * `sequence` - Sequence of the order receipt.
     /// * `amount_x` - Amount of tokens calculated in tokens x.
+    /// * `amount_x_reserve` - Amount of tokens calculated in tokens x held in the hidden reserve of the order.
+    /// * `expiry_epoch` - Optional epoch after which the order expires.
+    /// * `maker_fee` - Maker fee of the order as ratio. Negative for a rebate.
     /// 
     /// # Returns
     /// 
@@ -109,21 +219,117 @@ impl Limit {
     /// # Requires
     /// 
     /// * `order_id` - Id of the order receipt must be greater than 0.
-    /// * `order_id` - Id of the order receipt must be greater than the previous order receipt.
     /// * `order_id` - Id must be for a valid order receipt.
+    /// * `sequence` - Sequence of the order receipt must be greater than the previous order receipt.
     /// * `amount_x` - Amount of tokens must match the amount in the order receipt.
-    /// 
-    pub fn add_order(&mut self, order_id: u64, amount_x: &Decimal) -> u64 {
+    /// * `amount_x_reserve` - Amount of tokens must match the reserve amount in the order receipt.
+    /// 
+    pub fn add_order(
+        &mut self, 
+        order_id: u64, 
+        sequence: u64, 
+        amount_x: &Decimal, 
+        amount_x_reserve: &Decimal, 
+        expiry_epoch: Option<u64>, 
+        maker_fee: Decimal,
+    ) -> u64 {
         let tail_id = self.tail_id;
         self.tail_id = order_id;
         if self.head_id == 0 {
             self.head_id = order_id;
+            self.head_sequence = sequence;
         }
         self.amount_x += *amount_x;
+        self.amount_x_reserve += *amount_x_reserve;
+        self.amount_x_rebate += *amount_x * Self::rebate_rate(maker_fee);
+        if let Some(expiry_epoch) = expiry_epoch {
+            self.min_expiry_epoch = match self.min_expiry_epoch {
+                Some(min_expiry_epoch) => Some(min_expiry_epoch.min(expiry_epoch)),
+                None => Some(expiry_epoch),
+            };
+        }
         
         tail_id
     }
     
+    /// Put an order skipped by self trade prevention back at the front of the linked list.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order_id` - Id of the order receipt.
+    /// * `amount_x` - Amount of tokens calculated in tokens x.
+    /// * `amount_x_reserve` - Amount of tokens calculated in tokens x in the hidden reserve of an iceberg order.
+    /// * `expiry_epoch` - Optional expiry epoch of the order.
+    /// * `next_sequence` - Sequence the next order placed will get.
+    /// * `maker_fee` - Maker fee of the order as ratio. Negative for a rebate.
+    /// 
+    /// # Returns
+    /// 
+    /// * `u64` - Id of the order receipt that was previously the first order in the linked list.
+    /// 
+    /// # Requires
+    /// 
+    /// * `order_id` - Order receipt must be flagged as skipped.
+    /// * There are no tokens sold and not allocated to an order.
+    /// 
+    pub fn add_skipped_order(
+        &mut self, 
+        order_id: u64, 
+        amount_x: &Decimal, 
+        amount_x_reserve: &Decimal, 
+        expiry_epoch: Option<u64>, 
+        next_sequence: u64, 
+        maker_fee: Decimal,
+    ) -> u64 {
+        let head_id = self.head_id;
+        self.head_id = order_id;
+        if head_id == 0 {
+            self.tail_id = order_id;
+            self.head_sequence = next_sequence;
+        }
+        self.amount_x += *amount_x;
+        self.amount_x_reserve += *amount_x_reserve;
+        self.amount_x_rebate += *amount_x * Self::rebate_rate(maker_fee);
+        self.skipped_count += 1;
+        if let Some(expiry_epoch) = expiry_epoch {
+            self.min_expiry_epoch = match self.min_expiry_epoch {
+                Some(min_expiry_epoch) => Some(min_expiry_epoch.min(expiry_epoch)),
+                None => Some(expiry_epoch),
+            };
+        }
+
+        head_id
+    }
+
+    /// Take the tokens sold and not allocated to the first order in the linked list, so they can be 
+    /// recorded on its order receipt as a filled slice.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Decimal` - Amount of tokens calculated in tokens x that were not allocated.
+    /// 
+    pub fn take_amount_x_unallocated(&mut self) -> Decimal {
+        let amount_x: Decimal = self.amount_x_unallocated;
+        self.amount_x_unallocated = Decimal::zero();
+        amount_x
+    }
+
+    /// Reduce the amount of an unfilled order without changing its position in the linked list.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `amount_x` - Amount of tokens calculated in tokens x to remove from the order.
+    /// * `maker_fee` - Maker fee of the order as ratio. Negative for a rebate.
+    /// 
+    /// # Requires
+    /// 
+    /// * `amount_x` - Must be less than the unfilled amount of the order.
+    /// 
+    pub fn reduce_order(&mut self, amount_x: &Decimal, maker_fee: Decimal) {
+        self.amount_x -= *amount_x;
+        self.remove_rebate(*amount_x * Self::rebate_rate(maker_fee));
+    }
+
     /// Fill the limit by some amount.
     /// 
     /// # Arguments
@@ -131,46 +337,174 @@ impl Limit {
     /// * `amount_x` - Amount of tokens calculated in tokens x.
     /// * `order_receipt_manager` - Order receipt manager.
     /// 
-    pub fn fill(&mut self, amount_x: &Decimal, order_receipt_manager: &ResourceManager) {
+    /// # Returns
+    /// 
+    /// * `Vec<(u64, Decimal)>` - Ids of filled iceberg orders to refill and the amounts calculated in tokens x 
+    /// taken from their hidden reserves.
+    /// * `Decimal` - Maker rebates calculated in tokens x on the amounts filled, at the maker fee of each order.
+    /// 
+    pub fn fill(&mut self, amount_x: &Decimal, order_receipt_manager: &ResourceManager) -> (Vec<(u64, Decimal)>, Decimal) {
+        let amount_x_filled_before: Decimal = self.amount_x_unallocated;
         self.amount_x -= *amount_x;
         self.amount_x_unallocated += *amount_x;
 
-        self.allocate(order_receipt_manager);
+        let (refills, amount_x_rebate) = self.allocate(amount_x_filled_before, order_receipt_manager);
+        self.remove_rebate(amount_x_rebate);
+
+        (refills, amount_x_rebate)
     }
 
-    /// Fully fill the limit.
-    pub fn fully_fill(&mut self) {
+    /// Fully fill the limit. The hidden reserves of iceberg orders are not filled.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order_receipt_manager` - Order receipt manager.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Vec<(u64, Decimal)>` - Ids of filled iceberg orders to refill and the amounts calculated in tokens x 
+    /// taken from their hidden reserves.
+    /// * `Decimal` - Maker rebates calculated in tokens x on the amounts filled, at the maker fee of each order.
+    /// 
+    pub fn fully_fill(&mut self, order_receipt_manager: &ResourceManager) -> (Vec<(u64, Decimal)>, Decimal) {
+        // Find iceberg orders and skipped orders if any remain
+        let mut refills: Vec<(u64, Decimal)> = Vec::new();
+        if self.amount_x_reserve > Decimal::zero() || self.skipped_count > 0 {
+            let mut next_id: u64 = self.head_id;
+            while next_id != 0 {
+                let order_data = order_receipt_manager
+                    .get_non_fungible_data::<OrderReceipt>(&NonFungibleLocalId::integer(next_id));
+                self.clear_skipped(next_id, &order_data, order_receipt_manager);
+                if let Some(amount_x) = self.take_refill(&order_data) {
+                    refills.push((next_id, amount_x));
+                }
+                next_id = order_data.next_id;
+            }
+        }
+
+        let amount_x_rebate: Decimal = self.amount_x_rebate;
         self.amount_x = Decimal::zero();
         self.amount_x_unallocated = Decimal::zero();
+        self.amount_x_rebate = Decimal::zero();
         self.head_id = 0;
         self.tail_id = 0;
+        self.head_sequence = 0;
+        self.min_expiry_epoch = None;
+
+        (refills, amount_x_rebate)
     }
 
     /// Helper method to allocate tokens to orders receipts.
     /// 
     /// # Arguments
     /// 
+    /// * `amount_x_filled_before` - Amount of tokens calculated in tokens x of the first order that was filled before.
     /// * `order_receipt_manager` - Order receipt manager.
     /// 
-    fn allocate(&mut self, order_receipt_manager: &ResourceManager) {
+    /// # Returns
+    /// 
+    /// * `Vec<(u64, Decimal)>` - Ids of filled iceberg orders to refill and the amounts calculated in tokens x 
+    /// taken from their hidden reserves.
+    /// * `Decimal` - Maker rebates calculated in tokens x on the amounts newly filled, at the maker fee of each order.
+    /// 
+    fn allocate(&mut self, amount_x_filled_before: Decimal, order_receipt_manager: &ResourceManager) -> (Vec<(u64, Decimal)>, Decimal) {
         // Get next order
+        let mut refills: Vec<(u64, Decimal)> = Vec::new();
+        let mut amount_x_rebate: Decimal = Decimal::zero();
+        let mut amount_x_filled_before: Decimal = amount_x_filled_before;
         loop {
             // Get order data
             let order_data = order_receipt_manager
                 .get_non_fungible_data::<OrderReceipt>(&NonFungibleLocalId::integer(self.head_id));
+            let rebate_rate: Decimal = Self::rebate_rate(order_data.maker_fee);
 
             // If sold tokens_a left to allocate
             if self.amount_x_unallocated >= order_data.amount {
                 // Allocate sold tokens_a to fill order
+                amount_x_rebate += (order_data.amount - amount_x_filled_before) * rebate_rate;
+                amount_x_filled_before = Decimal::zero();
                 self.amount_x_unallocated -= order_data.amount;
+                self.clear_skipped(self.head_id, &order_data, order_receipt_manager);
+                if let Some(amount_x) = self.take_refill(&order_data) {
+                    refills.push((self.head_id, amount_x));
+                }
                 self.head_id = order_data.next_id;
             } else {
-                return;
+                // Add rebate on the part of the order filled
+                amount_x_rebate += (self.amount_x_unallocated - amount_x_filled_before) * rebate_rate;
+
+                // Skipped orders have a lower sequence than the orders filled while they were skipped
+                self.head_sequence = self.head_sequence.max(order_data.sequence);
+                return (refills, amount_x_rebate);
             }
         }
     }
 
+    /// Helper method to get the maker rebate rate of an order.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `maker_fee` - Maker fee of the order as ratio. Negative for a rebate.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Decimal` - Maker rebate as ratio. Zero if the order pays a maker fee.
+    /// 
+    fn rebate_rate(maker_fee: Decimal) -> Decimal {
+        (-maker_fee).max(Decimal::zero())
+    }
+
+    /// Helper method to remove maker rebates from the sum of the limit.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `amount_x_rebate` - Maker rebates calculated in tokens x to remove.
+    /// 
+    fn remove_rebate(&mut self, amount_x_rebate: Decimal) {
+        self.amount_x_rebate = (self.amount_x_rebate - amount_x_rebate).max(Decimal::zero());
+    }
+
+    /// Helper method to clear the skip flag of a filled order skipped by self trade prevention.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order_id` - Id of the order receipt.
+    /// * `order_data` - Order receipt data.
+    /// * `order_receipt_manager` - Order receipt manager.
+    /// 
+    fn clear_skipped(&mut self, order_id: u64, order_data: &OrderReceipt, order_receipt_manager: &ResourceManager) {
+        if !order_data.is_skipped {
+            return;
+        }
+
+        order_receipt_manager.update_non_fungible_data(&NonFungibleLocalId::integer(order_id), "is_skipped", false);
+        self.skipped_count -= 1;
+    }
+
+    /// Helper method to take the next slice of a filled iceberg order from the hidden reserves.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order_data` - Order receipt data.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Option<Decimal>` - Amount of tokens calculated in tokens x taken from the hidden reserves. 
+    /// None if the order has no hidden reserve.
+    /// 
+    fn take_refill(&mut self, order_data: &OrderReceipt) -> Option<Decimal> {
+        if order_data.amount_reserve == Decimal::zero() {
+            return None;
+        }
+
+        let amount_x: Decimal = order_data.display_amount.unwrap().min(order_data.amount_reserve);
+        self.amount_x_reserve -= amount_x;
+
+        Some(amount_x)
+    }
+
     /// Claim tokens owned by a order receipt.
+    /// Includes the hidden reserve and filled previous slices of an iceberg order.
     /// 
     /// # Arguments
     /// 
@@ -191,7 +525,7 @@ impl Limit {
         // Get amounts due
         let amount_order = order_data.amount;
         let (amount_canceled, amount_filled): (Decimal, Decimal) = 
-            if self.head_id == 0 || order_id < self.head_id {
+            if self.is_filled(order_data) {
                 self.claim_filled_order(amount_order)
             } else if order_id == self.head_id {
                 self.cancel_active_order(amount_order)
@@ -199,9 +533,21 @@ impl Limit {
                 self.cancel_order(amount_order)
             };
 
+        // Remove maker rebate of the canceled part of the order
+        self.remove_rebate(amount_canceled * Self::rebate_rate(order_data.maker_fee));
+
+        // Add iceberg order amounts
+        self.amount_x_reserve -= order_data.amount_reserve;
+        if order_data.is_skipped {
+            self.skipped_count -= 1;
+        }
+        let amount_canceled = amount_canceled + order_data.amount_reserve;
+        let amount_filled = amount_filled + order_data.amount_slices_filled;
+
         // Update pointers
         if order_id == self.head_id {
             self.head_id = order_data.next_id;
+            self.head_sequence = self.head_sequence.max(order_data.sequence + 1);
         }
         if order_id == self.tail_id {
             self.tail_id = order_data.prev_id;
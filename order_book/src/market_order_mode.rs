@@ -0,0 +1,12 @@
+use scrypto::prelude::*;
+
+/// Execution mode for a market order.
+///
+/// * `ImmediateOrCancel(Decimal)` - Execute as much as possible and return any tokens remaining. 
+/// Panics if the amount of tokens bought is less than the contained minimum amount.
+/// * `FillOrKill` - Panic unless all tokens are executed.
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarketOrderMode {
+    ImmediateOrCancel(Decimal),
+    FillOrKill,
+}
@@ -0,0 +1,17 @@
+use scrypto::prelude::*;
+
+/// Expected outcome of a market order if executed against the current state of the order book.
+/// Amounts are not rounded to the divisibility of the tokens.
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct MarketOrderQuote {
+    /// Amount of tokens bought.
+    pub amount_out: Decimal,
+    /// Average price of fills calculated as `tokens_y / tokens_x`. None if nothing would be filled.
+    pub average_price: Option<Decimal>,
+    /// Amount of input tokens taken as protocol fee.
+    pub protocol_fee: Decimal,
+    /// Last limit price reached calculated as `tokens_y / tokens_x`. None if nothing would be filled.
+    pub worst_price: Option<Decimal>,
+    /// Amount of input tokens returned unused, including any unused protocol fee.
+    pub amount_remaining: Decimal,
+}
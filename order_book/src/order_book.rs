@@ -1,17 +1,34 @@
 use scrypto::prelude::*;
 
+use crate::candle::*;
+use crate::depth_level::*;
 use crate::events::*;
+use crate::ladder_distribution::*;
 use crate::limit::*;
+use crate::market_order_mode::*;
+use crate::market_order_quote::*;
+use crate::observation::*;
+use crate::order_book_state::*;
 use crate::order_receipt::*;
 use crate::price::*;
 use crate::price_index::*;
 use crate::order_status::*;
+use crate::post_only_mode::*;
+use crate::self_trade_mode::*;
+use crate::trigger::*;
+use crate::trigger_mode::*;
 
 #[blueprint]
 #[events(
     NewOrderBookEvent,
+    SetStateEvent,
+    SweepRebatesEvent,
     LimitOrderEvent, 
+    AmendOrderEvent,
     MarketOrderEvent, 
+    FillEvent,
+    TriggerOrderEvent,
+    ExecuteTriggerOrderEvent,
     ClaimOrderEvent,
     ProtocolFeeEvent,
 )]
@@ -21,6 +38,9 @@ use crate::order_status::*;
     OrderReceipt,
     u32,
     IndexNode,
+    u64,
+    Candle,
+    Observation,
 )]
 mod order_book {
     // Import FeeController to get fee percentage.
@@ -28,6 +48,7 @@ mod order_book {
         "package_sim1pkyls09c258rasrvaee89dnapp2male6v6lmh7en5ynmtnavqdsvk9",
         FeeController {
             fn get_protocol_fee(&self, package_address: PackageAddress) -> Decimal;
+            fn get_maker_taker_fees(&self, package_address: PackageAddress) -> (Decimal, Decimal);
         }
     );
 
@@ -55,26 +76,43 @@ mod order_book {
     enable_method_auth! { 
         roles {
             user => updatable_by: [OWNER];
+            state_manager => updatable_by: [OWNER];
         },
         methods { 
+            set_state => restrict_to: [OWNER, state_manager];
+            sweep_rebates => restrict_to: [OWNER];
             limit_order => restrict_to: [user];
             limit_order_batch => restrict_to: [user];
+            limit_order_ladder => restrict_to: [user];
             market_order => restrict_to: [user];
+            trigger_order => restrict_to: [user];
+            execute_trigger_orders => PUBLIC;
+            amend_order => restrict_to: [user];
             claim_orders => PUBLIC;
+            claim_filled => PUBLIC;
             get_fee_controller_address => PUBLIC;
             get_fee_vaults_address => PUBLIC;
             get_token_x_address => PUBLIC;
             get_token_y_address => PUBLIC;
             get_order_receipt_address => PUBLIC;
+            get_min_order_size => PUBLIC;
+            get_lot_size => PUBLIC;
+            get_price_precision => PUBLIC;
             get_amount_x => PUBLIC;
             get_amount_y => PUBLIC;
+            get_rebates => PUBLIC;
             get_last_price => PUBLIC;
             get_current_ask_price => PUBLIC;
             get_current_bid_price => PUBLIC;
             get_ask_limits => PUBLIC;
             get_bid_limits => PUBLIC;
+            get_depth => PUBLIC;
             get_order_status => PUBLIC;
             get_order_statuses => PUBLIC;
+            quote_market_order => PUBLIC;
+            get_candle => PUBLIC;
+            get_twap => PUBLIC;
+            get_state => PUBLIC;
         }
     }
 
@@ -82,6 +120,9 @@ mod order_book {
     const INCOMING: WithdrawStrategy = WithdrawStrategy::Rounded(RoundingMode::ToPositiveInfinity);
     const OUTGOING: WithdrawStrategy = WithdrawStrategy::Rounded(RoundingMode::ToZero);
 
+    // Set maximum number of price observations kept
+    const MAX_OBSERVATIONS: u32 = 1440;
+
     /// Order book component that creates a market for two fungible tokens. 
     /// Limit orders are placed at a specific price and executed in a FIFO ordering at that price. 
     /// There can never be overlap between ask and bid limits. Market orders execute 
@@ -92,7 +133,9 @@ mod order_book {
     /// to keep track of available limit prices. The price index is a efficient data
     /// structure that allows for quick lookup of the best available limit prices and has 
     /// a low cost to maintain. Protocol fees are set by the external fee controller component 
-    /// and collected by the external fee vaults component.
+    /// and collected by the external fee vaults component. Takers pay the protocol fee. Makers 
+    /// pay a maker fee or receive a maker rebate when claiming filled orders. Maker rebates are 
+    /// paid from taker fees kept by the order book.
     /// 
     /// Price is calculated as `tokens_y / tokens_x`.
     /// Amounts are tracked in tokens x.
@@ -100,8 +143,16 @@ mod order_book {
     struct OrderBook {
         /// Number used to create order ids. The value 0 is reserved as a special value and is used as a null id.
         nonce: u64,
+        /// Number used to create order sequences that set the FIFO ordering of orders within a limit.
+        sequence: u64,
         /// Last price a limit was filled at.
         last_price: Price,
+        /// Whether any limit of the order book has been filled.
+        has_traded: bool,
+        /// Hash of the latest transaction that filled a limit.
+        last_fill_transaction: Option<Hash>,
+        /// Last price before the latest transaction that filled a limit. None if nothing was filled before that transaction.
+        prev_transaction_price: Option<Price>,
         /// Index of active prices for the order book.
         price_index: PriceIndex,
         /// Map of prices to ask limits. Ask limits sell tokens x for tokens y.
@@ -114,6 +165,32 @@ mod order_book {
         tokens_x: Vault,
         /// Vault of tokens y. Includes both tokens from unfilled bid limit orders and filled ask limit orders.
         tokens_y: Vault,
+        /// Vault of tokens x escrowed by ask trigger orders, including bounties and the outcome of executed orders.
+        trigger_tokens_x: Vault,
+        /// Vault of tokens y escrowed by bid trigger orders, including bounties and the outcome of executed orders.
+        trigger_tokens_y: Vault,
+        /// Vault of tokens x kept from taker fees to pay maker rebates to bid limit orders.
+        rebates_x: Vault,
+        /// Vault of tokens y kept from taker fees to pay maker rebates to ask limit orders.
+        rebates_y: Vault,
+        /// Minimum value of an order calculated in tokens y.
+        min_order_size: Decimal,
+        /// Optional size calculated in tokens x of which limit order amounts must be a multiple.
+        lot_size: Option<Decimal>,
+        /// Number of significant figures to which prices are truncated.
+        price_precision: u32,
+        /// Map of epochs to open, high, low, close, and volume of fills during the epoch.
+        candles: KeyValueStore<u64, Candle>,
+        /// Last epoch with fills. None if the order book has never been filled.
+        last_candle_epoch: Option<u64>,
+        /// Ring buffer of price observations used to calculate time weighted average prices.
+        observations: KeyValueStore<u32, Observation>,
+        /// Index of the latest price observation.
+        observation_index: u32,
+        /// Number of price observations recorded, up to the maximum kept.
+        observation_count: u32,
+        /// State of the order book. Orders can only be placed, amended, or matched while active.
+        state: OrderBookState,
     }
 
     impl OrderBook {
@@ -123,34 +200,60 @@ mod order_book {
         /// 
         /// * `owner_rule` - Owner access rule.
         /// * `user_rule` - User role access rule.
+        /// * `state_manager_rule` - State manager role access rule.
         /// * `token_x_address` - Address of token x.
         /// * `token_y_address` - Address of token y.
+        /// * `min_order_size` - Minimum value of an order calculated in tokens y.
+        /// * `lot_size` - Optional size calculated in tokens x of which limit order amounts must be a multiple. If none, any amount is allowed.
+        /// * `price_precision` - Number of significant figures to which prices are truncated.
         /// * `reservation` - Optional address reservation for component.
         /// 
         /// # Returns
         /// 
         /// * `Global<OrderBook>` - Order book component.
         /// 
+        /// # Panics
+        /// 
+        /// * If `min_order_size` is negative.
+        /// * If `lot_size` is not greater than zero.
+        /// * If `price_precision` is not in the range of [1, 5].
+        /// 
         /// # Access Rules
         /// 
+        /// * `set_state` - Owner or state manager required.
+        /// * `sweep_rebates` - Owner required.
         /// * `limit_order` - User required.
         /// * `limit_order_batch` - User required
+        /// * `limit_order_ladder` - User required
         /// * `market_order` - User required.
+        /// * `trigger_order` - User required.
+        /// * `execute_trigger_orders` - Public.
+        /// * `amend_order` - User required.
         /// * `claim_order` - Public.
+        /// * `claim_filled` - Public.
         /// * `get_fee_controller_address` - Public.
         /// * `get_fee_vaults_address` - Public.
         /// * `get_token_x_address` - Public.
         /// * `get_token_y_address` - Public.
         /// * `get_order_receipt_address` - Public.
+        /// * `get_min_order_size` - Public.
+        /// * `get_lot_size` - Public.
+        /// * `get_price_precision` - Public.
         /// * `get_amount_x` - Public.
         /// * `get_amount_y` - Public.
+        /// * `get_rebates` - Public.
         /// * `get_last_price` - Public.
         /// * `get_current_ask_price` - Public.
         /// * `get_current_bid_price` - Public.
         /// * `get_ask_limits` - Public.
         /// * `get_bid_limits` - Public.
+        /// * `get_depth` - Public.
         /// * `get_order_status` - Public.
         /// * `get_order_statuses` - Public.
+        /// * `quote_market_order` - Public.
+        /// * `get_candle` - Public.
+        /// * `get_twap` - Public.
+        /// * `get_state` - Public.
         /// 
         /// # Events
         /// 
@@ -159,10 +262,22 @@ mod order_book {
         pub fn new(
             owner_rule: AccessRule,
             user_rule: AccessRule,
+            state_manager_rule: AccessRule,
             token_x_address: ResourceAddress,
             token_y_address: ResourceAddress,
+            min_order_size: Decimal,
+            lot_size: Option<Decimal>,
+            price_precision: u32,
             reservation: Option<GlobalAddressReservation>
         ) -> Global<OrderBook> {
+            // Check parameters
+            assert!(min_order_size >= Decimal::zero(), "Minimum order size must not be negative.");
+            assert!(lot_size.is_none() || lot_size.unwrap() > Decimal::zero(), "Lot size must be greater than zero.");
+            assert!(
+                price_precision >= 1 && price_precision <= Price::PRECISION, 
+                "Price precision must be between 1 and {}.", Price::PRECISION
+            );
+
             // Get component address
             let (address_reservation, component_address) = match reservation {
                 Some(reservation) => {
@@ -216,18 +331,38 @@ mod order_book {
                 order_receipt_address: order_receipt_manager.address(),
                 token_x_address,
                 token_y_address,
+                min_order_size,
+                lot_size,
+                price_precision,
             });
 
             // Instantiate and globalize order book
             Self {
                 nonce: 1,
+                sequence: 1,
                 last_price: Price::MIN,
+                has_traded: false,
+                last_fill_transaction: None,
+                prev_transaction_price: None,
                 price_index: PriceIndex::new(),
                 ask_limit_map: KeyValueStore::new_with_registered_type(),
                 bid_limit_map: KeyValueStore::new_with_registered_type(),
                 order_receipt_manager,
                 tokens_x: Vault::new(token_x_address),
                 tokens_y: Vault::new(token_y_address),
+                trigger_tokens_x: Vault::new(token_x_address),
+                trigger_tokens_y: Vault::new(token_y_address),
+                rebates_x: Vault::new(token_x_address),
+                rebates_y: Vault::new(token_y_address),
+                min_order_size,
+                lot_size,
+                price_precision,
+                candles: KeyValueStore::new_with_registered_type(),
+                last_candle_epoch: None,
+                observations: KeyValueStore::new_with_registered_type(),
+                observation_index: 0,
+                observation_count: 0,
+                state: OrderBookState::Active,
             }.instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(owner_rule))
             .metadata(metadata!(
@@ -243,10 +378,81 @@ mod order_book {
             .with_address(address_reservation)
             .roles(roles!(
                 user => user_rule;
+                state_manager => state_manager_rule;
             ))
             .globalize()
         }
 
+        /// OWNER or STATE MANAGER: Set the state of the order book.
+        /// While cancel only, order receipts can be claimed but no orders can be placed, amended, or matched.
+        /// While halted, order receipts can not be claimed either.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `state` - New state of the order book.
+        /// 
+        /// # Events
+        /// 
+        /// * `SetStateEvent` - Event emitted when the state of the order book is set.
+        /// 
+        pub fn set_state(&mut self, state: OrderBookState) {
+            // Set state
+            self.state = state;
+
+            // Emit set state event
+            Runtime::emit_event(SetStateEvent {
+                state,
+            });
+        }
+
+        /// OWNER: Sweep tokens kept to pay maker rebates to the fee vaults.
+        /// Rebates are kept and paid at the maker fee recorded when each limit order was placed, 
+        /// so only rounding leaves tokens not needed for rebates.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_x` - Amount of tokens x to sweep.
+        /// * `amount_y` - Amount of tokens y to sweep.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `amount_x` or `amount_y` is negative.
+        /// * If `amount_x` or `amount_y` is greater than the tokens kept for rebates.
+        /// 
+        /// # Events
+        /// 
+        /// * `SweepRebatesEvent` - Event emitted when rebates are swept.
+        /// 
+        pub fn sweep_rebates(&mut self, amount_x: Decimal, amount_y: Decimal) {
+            // Check amounts
+            assert!(
+                amount_x >= Decimal::zero() && amount_y >= Decimal::zero(), 
+                "Sweep amounts must not be negative."
+            );
+            assert!(
+                amount_x <= self.rebates_x.amount() && amount_y <= self.rebates_y.amount(), 
+                "Not enough rebates to sweep."
+            );
+
+            // Deposit swept tokens into fee vaults
+            let tokens_x: Bucket = self.rebates_x.take_advanced(amount_x, OUTGOING);
+            let tokens_y: Bucket = self.rebates_y.take_advanced(amount_y, OUTGOING);
+            let (amount_x, amount_y): (Decimal, Decimal) = (tokens_x.amount(), tokens_y.amount());
+            for tokens in [tokens_x, tokens_y] {
+                if tokens.is_empty() {
+                    tokens.drop_empty();
+                } else {
+                    FEE_VAULTS.deposit(tokens);
+                }
+            }
+
+            // Emit sweep rebates event
+            Runtime::emit_event(SweepRebatesEvent {
+                amount_x,
+                amount_y,
+            });
+        }
+
         /// Get component address of fee controller.
         /// 
         /// # Returns
@@ -297,6 +503,36 @@ mod order_book {
             self.order_receipt_manager.address()
         }
 
+        /// Get the minimum order size.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Minimum value of an order calculated in tokens y.
+        /// 
+        pub fn get_min_order_size(&self) -> Decimal {
+            self.min_order_size
+        }
+
+        /// Get the lot size.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Option<Decimal>` - Size calculated in tokens x of which limit order amounts must be a multiple. None if any amount is allowed.
+        /// 
+        pub fn get_lot_size(&self) -> Option<Decimal> {
+            self.lot_size
+        }
+
+        /// Get the price precision.
+        /// 
+        /// # Returns
+        /// 
+        /// * `u32` - Number of significant figures to which prices are truncated.
+        /// 
+        pub fn get_price_precision(&self) -> u32 {
+            self.price_precision
+        }
+
         /// Get amount of tokens x.
         /// 
         /// # Returns
@@ -317,6 +553,17 @@ mod order_book {
             self.tokens_y.amount()
         }
 
+        /// Get amounts of tokens kept to pay maker rebates.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Amount of tokens x kept to pay maker rebates.
+        /// * `Decimal` - Amount of tokens y kept to pay maker rebates.
+        /// 
+        pub fn get_rebates(&self) -> (Decimal, Decimal) {
+            (self.rebates_x.amount(), self.rebates_y.amount())
+        }
+
         /// Get last price a limit order was filled at.
         /// Price is calculated as `tokens_y / tokens_x`.
         /// 
@@ -513,6 +760,38 @@ mod order_book {
             }
         }
 
+        /// Get the depth of both sides of the order book aggregated into price buckets.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Ask limits are added to the bucket at or above their price and bid limits to the bucket at or below their price.
+        /// Amounts exclude expired orders and include hidden reserves of iceberg orders, matching market order quotes.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `levels` - Maximum number of buckets to return for each side.
+        /// * `bucket_size` - Optional price width of each bucket. If none, each limit price is its own bucket.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Vec<DepthLevel>` - Ask buckets from the current ask price upwards.
+        /// * `Vec<DepthLevel>` - Bid buckets from the current bid price downwards.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `bucket_size` is not greater than zero.
+        /// 
+        pub fn get_depth(&self, levels: u32, bucket_size: Option<Decimal>) -> (Vec<DepthLevel>, Vec<DepthLevel>) {
+            // Check parameters
+            if let Some(bucket_size) = bucket_size {
+                assert!(bucket_size > Decimal::zero(), "Bucket size must be greater than zero.");
+            }
+
+            // Get depth of each side
+            (
+                self.get_depth_levels(true, levels, bucket_size),
+                self.get_depth_levels(false, levels, bucket_size),
+            )
+        }
+
         /// Get the status of a limit order.
         /// Amount is calculated in tokens x.
         /// 
@@ -527,7 +806,10 @@ mod order_book {
         /// # Order Status
         /// 
         /// * `Open(OrderData)` - Order is open and has the contained order data.
+        /// * `Pending(OrderData)` - Trigger order is waiting to be triggered and has the contained order data.
         /// * `Filled(OrderData)` - Order has been filled and has the contained order data.
+        /// * `Expired(OrderData)` - Order has expired before being filled and has the contained order data.
+        /// * `Canceled(OrderData)` - Order has been canceled by self trade prevention and has the contained order data.
         /// * `Claimed` - Order has been claimed and no longer exists.
         /// * `Invalid` - Order id is invalid.
         /// 
@@ -536,7 +818,11 @@ mod order_book {
         /// * `is_ask` - Is ask or bid order.
         /// * `price` - Price of order.
         /// * `amount_filled` - Filled amount of tokens for order calculated in tokens x.
+        /// * `amount_claimed` - Filled amount of tokens for order already claimed calculated in tokens x.
+        /// * `amount_unclaimed` - Filled amount of tokens for order not yet claimed calculated in tokens x.
         /// * `amount_total` - Total amount of tokens for order calculated in tokens x.
+        /// * `amount_visible` - Unfilled amount of tokens for order shown in the limit calculated in tokens x.
+        /// * `amount_remaining` - Unfilled amount of tokens for order including any hidden reserve calculated in tokens x.
         /// 
         pub fn get_order_status(&self, order_receipt_id: NonFungibleLocalId) -> OrderStatus {
             let order_id: u64 = match order_receipt_id.clone() {
@@ -557,6 +843,50 @@ mod order_book {
             // Get order receipt
             let order_data: OrderReceipt = self.order_receipt_manager.get_non_fungible_data(&order_receipt_id);
 
+            // If trigger order
+            if order_data.trigger.is_some() {
+                let order_status_data = OrderData {
+                    is_ask: order_data.is_ask,
+                    price: order_data.price,
+                    amount_filled: Decimal::zero(),
+                    amount_claimed: Decimal::zero(),
+                    amount_unclaimed: Decimal::zero(),
+                    amount_total: order_data.amount,
+                    amount_visible: Decimal::zero(),
+                    amount_remaining: order_data.amount,
+                };
+                return match order_data.trigger_amounts {
+                    Some(_) => OrderStatus::Filled(OrderData {
+                        amount_filled: order_data.amount,
+                        amount_unclaimed: order_data.amount,
+                        amount_remaining: Decimal::zero(),
+                        ..order_status_data
+                    }),
+                    None => OrderStatus::Pending(order_status_data),
+                };
+            }
+
+            let amount_total: Decimal = order_data.amount_slices_filled + order_data.amount + order_data.amount_reserve;
+
+            // If order has been removed from limit for expiring or by self trade prevention
+            if let Some(amount_filled) = order_data.expired_amount_filled {
+                let order_status_data = OrderData {
+                    is_ask: order_data.is_ask,
+                    price: order_data.price,
+                    amount_filled,
+                    amount_claimed: order_data.amount_claimed,
+                    amount_unclaimed: amount_filled - order_data.amount_claimed,
+                    amount_total,
+                    amount_visible: Decimal::zero(),
+                    amount_remaining: Decimal::zero(),
+                };
+                return if order_data.is_self_trade_canceled {
+                    OrderStatus::Canceled(order_status_data)
+                } else {
+                    OrderStatus::Expired(order_status_data)
+                };
+            }
+
             // Get limit
             let price: Price = order_data.price.into();
             let limit = if order_data.is_ask {
@@ -567,29 +897,47 @@ mod order_book {
             
             // Check position in relation to active order
             let head_id: u64 = limit.get_head_id();
-            if head_id == 0 || order_id < head_id {
-                OrderStatus::Filled(
+            if limit.is_filled(&order_data) {
+                return OrderStatus::Filled(
                     OrderData {
                         is_ask: order_data.is_ask,
                         price: order_data.price,
-                        amount_filled: order_data.amount,
-                        amount_total: order_data.amount,
+                        amount_filled: amount_total,
+                        amount_claimed: order_data.amount_claimed,
+                        amount_unclaimed: amount_total - order_data.amount_claimed,
+                        amount_total,
+                        amount_visible: Decimal::zero(),
+                        amount_remaining: Decimal::zero(),
                     }
-                )
-            } else if order_id == head_id {
-                OrderStatus::Open(OrderData {
-                    is_ask: order_data.is_ask,
-                    price: order_data.price,
-                    amount_filled: limit.get_amount_x_unallocated(),
-                    amount_total: order_data.amount,
-                })
+                );
+            }
+            let amount_slice_filled: Decimal = if order_id == head_id {
+                limit.get_amount_x_unallocated()
             } else {
-                OrderStatus::Open(OrderData {
-                    is_ask: order_data.is_ask,
-                    price: order_data.price,
-                    amount_filled: Decimal::zero(),
-                    amount_total: order_data.amount,
-                })
+                Decimal::zero()
+            };
+            let amount_filled: Decimal = order_data.amount_slices_filled + amount_slice_filled;
+            let order_status_data = OrderData {
+                is_ask: order_data.is_ask,
+                price: order_data.price,
+                amount_filled,
+                amount_claimed: order_data.amount_claimed,
+                amount_unclaimed: amount_filled - order_data.amount_claimed,
+                amount_total,
+                amount_visible: order_data.amount - amount_slice_filled,
+                amount_remaining: amount_total - amount_filled,
+            };
+
+            // Check if order has expired
+            match order_data.expiry_epoch {
+                Some(expiry_epoch) if Runtime::current_epoch().number() > expiry_epoch => {
+                    OrderStatus::Expired(OrderData {
+                        amount_visible: Decimal::zero(),
+                        amount_remaining: Decimal::zero(),
+                        ..order_status_data
+                    })
+                },
+                _ => OrderStatus::Open(order_status_data),
             }
         }
 
@@ -606,7 +954,10 @@ mod order_book {
         /// # Order Status
         /// 
         /// * `Open(amount)` - Order is open and has had amount of tokens filled.
+        /// * `Pending` - Trigger order is waiting to be triggered.
         /// * `Filled` - Order has been filled.
+        /// * `Expired` - Order has expired before being filled.
+        /// * `Canceled` - Order has been canceled by self trade prevention.
         /// * `Claimed` - Order has been claimed and no longer exists.
         /// * `Invalid` - Order id is invalid.
         /// 
@@ -614,14 +965,138 @@ mod order_book {
             order_receipt_ids.into_iter().map(|id| self.get_order_status(id)).collect()
         }
 
+        /// Get a quote for a market order without executing it.
+        /// A fee is taken from the input tokens.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Input tokens x are rounded down to the lot size, any tokens left over are included in the amount remaining.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - Address of tokens used to execute the market order.
+        /// * `amount` - Amount of tokens used to execute the market order.
+        /// * `stop_price` - Optional stop price. If none, market order will not stop until filled.
+        /// 
+        /// # Returns
+        /// 
+        /// * `MarketOrderQuote` - Expected outcome of the market order.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `amount` is not greater than zero.
+        /// * If `resource` is not tokens x or tokens y.
+        /// 
+        /// # Market Order Quote
+        /// 
+        /// * `amount_out` - Amount of tokens bought.
+        /// * `average_price` - Average price of fills. None if nothing would be filled.
+        /// * `protocol_fee` - Amount of input tokens taken as protocol fee.
+        /// * `worst_price` - Last limit price reached. None if nothing would be filled.
+        /// * `amount_remaining` - Amount of input tokens returned unused.
+        /// 
+        pub fn quote_market_order(
+            &self, 
+            resource: ResourceAddress, 
+            amount: Decimal, 
+            stop_price: Option<Decimal>,
+        ) -> MarketOrderQuote {
+            // Check parameters
+            assert!(amount > Decimal::zero(), "Order size must be greater than zero.");
+
+            // Check if tokens_x or tokens_y
+            if resource == self.tokens_x.resource_address() {
+                let stop_price: Price = match stop_price {
+                    Some(price_dec) => price_dec.round_to_price_range().into(),
+                    None => Price::MIN,
+                };
+
+                self.quote_market_order_x_to_y(amount, stop_price)
+            } else if resource == self.tokens_y.resource_address() {
+                let stop_price: Price = match stop_price {
+                    Some(price_dec) => price_dec.round_to_price_range().into(),
+                    None => Price::MAX,
+                };
+
+                self.quote_market_order_y_to_x(amount, stop_price)
+            } else {
+                panic!("Invalid token address.");
+            }
+        }
+
+        /// Get the state of the order book.
+        /// 
+        /// # Returns
+        /// 
+        /// * `OrderBookState` - State of the order book.
+        /// 
+        pub fn get_state(&self) -> OrderBookState {
+            self.state
+        }
+
+        /// Get the open, high, low, close, and volume of fills during an epoch.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `epoch` - Epoch number.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Option<Candle>` - Candle for the epoch. None if there were no fills during the epoch.
+        /// 
+        pub fn get_candle(&self, epoch: u64) -> Option<Candle> {
+            self.candles.get(&epoch).map(|candle| *candle)
+        }
+
+        /// Get the time weighted average price between two times.
+        /// The last price is accumulated over time before every fill, so each price is weighted by how long it was held.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `start_time` - Start of the range in seconds since the unix epoch.
+        /// * `end_time` - End of the range in seconds since the unix epoch.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Time weighted average price.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `start_time` is not before `end_time`.
+        /// * If `end_time` is after the current time.
+        /// * If the order book has never been filled.
+        /// * If `start_time` is before the oldest price observation.
+        /// 
+        pub fn get_twap(&self, start_time: i64, end_time: i64) -> Decimal {
+            // Check parameters
+            assert!(start_time < end_time, "Start time must be before end time.");
+            let now: i64 = Clock::current_time_rounded_to_minutes().seconds_since_unix_epoch;
+            assert!(end_time <= now, "End time must not be after the current time.");
+            assert!(self.observation_count > 0, "No price observations.");
+
+            // Calculate average from price accumulator
+            let price_cumulative_start: Decimal = self.price_cumulative_at(start_time);
+            let price_cumulative_end: Decimal = self.price_cumulative_at(end_time);
+            (price_cumulative_end - price_cumulative_start) / Decimal::from(end_time - start_time)
+        }
+
         /// Place a limit order into the order book.
         /// Price is calculated as `tokens_y / tokens_x`.
-        /// Price is truncated to 5 significant figures.
+        /// Price is truncated to the price precision of the order book.
+        /// The amount of the limit order is rounded down to the lot size, any tokens left over are returned.
+        /// If the limit order still crosses the order book after self trade prevention, the remaining tokens are returned.
         /// 
         /// # Arguments
         /// 
         /// * `tokens` - Tokens used to place the limit order.
         /// * `price` - Price at which to place limit order.
+        /// * `post_only` - Optional post only mode. If none, any overlap with the order book is executed as a market order first.
+        /// * `expiry_epoch` - Optional epoch after which the limit order expires and is no longer filled. If none, the order does not expire.
+        /// * `display_amount` - Optional amount calculated in tokens x shown in the limit at a time, placing an iceberg order. 
+        /// When the shown amount is filled the next slice is added to the end of the limit from the hidden reserve. If none, the full amount is shown.
+        /// * `owner_proof` - Optional proof of a badge identifying the owner of the order. Stored on the order receipt for self trade prevention.
+        /// * `self_trade_mode` - Optional self trade prevention mode applied when any overlap is executed against limit orders of the same owner. 
+        /// If none, orders of the same owner are matched.
         /// 
         /// # Returns
         /// 
@@ -631,69 +1106,58 @@ mod order_book {
         /// 
         /// # Panics
         /// 
+        /// * If the order book is not active.
         /// * If `tokens` are not tokens x or tokens y.
         /// * If order size is not greater than zero.
+        /// * If order size is less than the lot size.
+        /// * If order value is less than the minimum order size.
         /// * If `price` is not in the valid range of [0.00000000001, 100000000000].
+        /// * If `post_only` is `Reject` and the order would cross the order book.
+        /// * If `post_only` is `Reprice` and there is no price that does not cross the order book.
+        /// * If `expiry_epoch` is before the current epoch.
+        /// * If `display_amount` is not greater than zero.
+        /// * If `self_trade_mode` is provided without an `owner_proof`.
+        /// * If `owner_proof` is not a proof of a single non fungible badge.
         /// 
         /// # Events
         /// 
         /// * `LimitOrderEvent` - Event emitted when limit order is placed.
         /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
         /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
         /// 
-        pub fn limit_order(&mut self, tokens: Bucket, price: Decimal) -> (Bucket, Bucket, Bucket) { 
-            // Check if tokens_x or tokens_y
-            if tokens.resource_address() == self.tokens_x.resource_address() {
-                // Market order to clear overlap
-                let price: Price = price.into();
-                let current_bid: Option<Price> = self.price_index.current_bid();
-                let (tokens_y, mut tokens_x) = if current_bid.is_some() && price <= current_bid.unwrap() {
-                    self.market_order_x_to_y(tokens, price)
-                } else {
-                    (Bucket::new(self.tokens_y.resource_address()), tokens)
-                };
-
-                // If remaining tokens place limit order
-                let order_receipt = if tokens_x.amount() > Decimal::ZERO {
-                    self.limit_order_x_to_y(tokens_x.take(tokens_x.amount()), price)
-                } else {
-                    Bucket::new(self.order_receipt_manager.address())
-                };
+        pub fn limit_order(
+            &mut self, 
+            tokens: Bucket, 
+            price: Decimal, 
+            post_only: Option<PostOnlyMode>, 
+            expiry_epoch: Option<u64>,
+            display_amount: Option<Decimal>,
+            owner_proof: Option<Proof>,
+            self_trade_mode: Option<SelfTradeMode>,
+        ) -> (Bucket, Bucket, Bucket) { 
+            // Check order book is active
+            self.check_active();
 
-                // Return buckets
-                (order_receipt, tokens_y, tokens_x)
-            } else if tokens.resource_address() == self.tokens_y.resource_address() {
-                // Market order to clear overlap
-                let price: Price = price.into();
-                let current_ask: Option<Price> = self.price_index.current_ask();
-                let (tokens_x, mut tokens_y) = if current_ask.is_some() && price >= current_ask.unwrap() {
-                    self.market_order_y_to_x(tokens, price)
-                } else {
-                    (Bucket::new(self.tokens_x.resource_address()), tokens)
-                };
-
-                // If remaining tokens place limit order
-                let price_dec: Decimal = price.into();
-                let order_receipt = if tokens_y.amount() / price_dec > Decimal::ZERO {
-                    self.limit_order_y_to_x(tokens_y.take(tokens_y.amount()), price)
-                } else {
-                    Bucket::new(self.order_receipt_manager.address())
-                };
+            // Get owner for self trade prevention
+            let (owner, self_trade): (Option<NonFungibleGlobalId>, Option<(NonFungibleGlobalId, SelfTradeMode)>) = 
+                Self::get_owner(owner_proof, self_trade_mode);
 
-                // Return buckets
-                (order_receipt, tokens_x, tokens_y)
-            } else {
-                panic!("Invalid token address.");
-            }
+            self.place_limit_order(tokens, price, post_only, expiry_epoch, display_amount, owner, self_trade)
         }
 
         /// Place a batch of limit orders into the order book.
         /// Price is calculated as `tokens_y / tokens_x`.
-        /// Price is truncated to 5 significant figures.
+        /// Price is truncated to the price precision of the order book.
+        /// The amount of each limit order is rounded down to the lot size, any tokens left over are returned.
         /// 
         /// # Arguments
         /// 
         /// * `limits` - Vector of limits to place in format (tokens, price).
+        /// * `post_only` - Optional post only mode applied to every limit order in the batch.
+        /// * `expiry_epoch` - Optional expiry epoch applied to every limit order in the batch.
+        /// * `owner_proof` - Optional proof of a badge identifying the owner of every limit order in the batch.
+        /// * `self_trade_mode` - Optional self trade prevention mode applied to every limit order in the batch.
         /// 
         /// # Returns
         /// 
@@ -703,17 +1167,40 @@ mod order_book {
         /// 
         /// # Panics
         /// 
+        /// * If the order book is not active.
         /// * If `tokens` are not tokens x or tokens y.
         /// * If order size is not greater than zero.
+        /// * If any order size is less than the lot size.
+        /// * If any order value is less than the minimum order size.
         /// * If `price` is not in the valid range of [0.00000000001, 100000000000].
+        /// * If `post_only` is `Reject` and any order would cross the order book.
+        /// * If `post_only` is `Reprice` and there is no price that does not cross the order book.
+        /// * If `expiry_epoch` is before the current epoch.
+        /// * If `self_trade_mode` is provided without an `owner_proof`.
+        /// * If `owner_proof` is not a proof of a single non fungible badge.
         /// 
         /// # Events
         /// 
         /// * `LimitOrderEvent` - Event emitted when limit order is placed.
         /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
         /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
         /// 
-        pub fn limit_order_batch(&mut self, limits: Vec<(Bucket, Decimal)>) -> (Bucket, Bucket, Bucket) {
+        pub fn limit_order_batch(
+            &mut self, 
+            limits: Vec<(Bucket, Decimal)>, 
+            post_only: Option<PostOnlyMode>, 
+            expiry_epoch: Option<u64>,
+            owner_proof: Option<Proof>,
+            self_trade_mode: Option<SelfTradeMode>,
+        ) -> (Bucket, Bucket, Bucket) {
+            // Check order book is active
+            self.check_active();
+
+            // Get owner for self trade prevention
+            let (owner, self_trade): (Option<NonFungibleGlobalId>, Option<(NonFungibleGlobalId, SelfTradeMode)>) = 
+                Self::get_owner(owner_proof, self_trade_mode);
+
             // Create buckets
             let mut order_receipts: Bucket = Bucket::new(self.order_receipt_manager.address());
             let mut tokens_x: Bucket = Bucket::new(self.tokens_x.resource_address());
@@ -722,7 +1209,7 @@ mod order_book {
             // Loop through limits
             for (tokens, price) in limits {
                 // Place limit order
-                let (order_receipt, tokens_a, tokens_b) = self.limit_order(tokens, price);
+                let (order_receipt, tokens_a, tokens_b) = self.place_limit_order(tokens, price, post_only, expiry_epoch, None, owner.clone(), self_trade.clone());
 
                 // Deposit order receipt
                 order_receipts.put(order_receipt);
@@ -741,508 +1228,2623 @@ mod order_book {
             (order_receipts, tokens_x, tokens_y)
         }
 
-        /// Execute a market order on the order book.
-        /// A fee is taken from the input tokens.
+        /// Place a ladder of limit orders into the order book from a single bucket of tokens.
+        /// Prices of the levels are spaced evenly from the start price to the end price, both included.
+        /// Tokens are split across the levels by the distribution, ordered from the start price.
         /// Price is calculated as `tokens_y / tokens_x`.
+        /// Price is truncated to the price precision of the order book.
+        /// The amount of each limit order is rounded down to the lot size, any tokens left over are returned.
+        /// Limit orders of the ladder are placed as post only and no level may cross the order book.
         /// 
         /// # Arguments
         /// 
-        /// * `tokens` - Tokens used to execute the market order.
-        /// * `stop_price` - Optional stop price. If none, market order will not stop until filled.
+        /// * `tokens` - Tokens used to place the limit orders.
+        /// * `start_price` - Price of the first level of the ladder.
+        /// * `end_price` - Price of the last level of the ladder.
+        /// * `levels` - Number of limit orders in the ladder.
+        /// * `distribution` - Distribution of tokens across the levels.
+        /// * `expiry_epoch` - Optional expiry epoch applied to every limit order in the ladder.
+        /// * `owner_proof` - Optional proof of a badge identifying the owner of every limit order in the ladder.
         /// 
         /// # Returns
         /// 
-        /// * `Bucket` - Contains any tokens bought.
-        /// * `Bucket` - Contains any tokens remaining.
+        /// * `Bucket` - Contains the order receipts.
+        /// * `Bucket` - Contains any tokens left over.
         /// 
         /// # Panics
         /// 
-        /// * If amount of tokens is not greater than zero.
-        /// * If tokens are not tokens x or tokens y.
+        /// * If the order book is not active.
+        /// * If `tokens` are not tokens x or tokens y.
+        /// * If `levels` is not greater than zero.
+        /// * If `distribution` is `Geometric` and the ratio is not greater than zero.
+        /// * If any order size is not greater than zero.
+        /// * If any order size is less than the lot size.
+        /// * If any order value is less than the minimum order size.
+        /// * If any price is not in the valid range of [0.00000000001, 100000000000].
+        /// * If any level would cross the order book.
+        /// * If `expiry_epoch` is before the current epoch.
+        /// * If `owner_proof` is not a proof of a single non fungible badge.
         /// 
         /// # Events
         /// 
-        /// * `MarketOrderEvent` - Event emitted when market order is placed.
-        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// * `LimitOrderEvent` - Event emitted when limit order is placed.
         /// 
-        pub fn market_order(&mut self, tokens: Bucket, stop_price: Option<Decimal>) -> (Bucket, Bucket) {
+        pub fn limit_order_ladder(
+            &mut self, 
+            mut tokens: Bucket, 
+            start_price: Decimal, 
+            end_price: Decimal, 
+            levels: u32, 
+            distribution: LadderDistribution, 
+            expiry_epoch: Option<u64>,
+            owner_proof: Option<Proof>,
+        ) -> (Bucket, Bucket) {
+            // Check order book is active
+            self.check_active();
+
             // Check parameters
-            assert!(tokens.amount() > Decimal::zero(), "Order size must be greater than zero.");
+            assert!(levels > 0, "Number of levels must be greater than zero.");
+            assert!(
+                expiry_epoch.is_none() || expiry_epoch.unwrap() >= Runtime::current_epoch().number(), 
+                "Expiry epoch must not be before the current epoch."
+            );
 
-            // Check if tokens_x or tokens_y
-            if tokens.resource_address() == self.tokens_x.resource_address() {
-                let stop_price: Price = match stop_price {
-                    Some(price_dec) => price_dec.round_to_price_range().into(),
-                    None => Price::MIN,
-                };
+            // Get owner of limit orders
+            let (owner, _): (Option<NonFungibleGlobalId>, Option<(NonFungibleGlobalId, SelfTradeMode)>) = 
+                Self::get_owner(owner_proof, None);
 
-                self.market_order_x_to_y(tokens, stop_price)
+            // Check if tokens_x or tokens_y
+            let is_ask: bool = if tokens.resource_address() == self.tokens_x.resource_address() {
+                true
             } else if tokens.resource_address() == self.tokens_y.resource_address() {
-                let stop_price: Price = match stop_price {
-                    Some(price_dec) => price_dec.round_to_price_range().into(),
-                    None => Price::MAX,
-                };
-
-                self.market_order_y_to_x(tokens, stop_price)
+                false
             } else {
                 panic!("Invalid token address.");
-            }
-        }
+            };
 
-        /// Claim tokens owned by order receipts.
-        /// 
-        /// # Arguments
-        /// 
-        /// * `order_receipts` - Order receipts.
-        /// 
-        /// # Returns
-        /// 
-        /// * `Bucket` - Contains any tokens x from claimed orders.
-        /// * `Bucket` - Contains any tokens y from claimed orders.
-        /// 
-        /// # Panics
-        /// 
-        /// * If an order receipt is invalid.
-        /// * If an order receipt is for a different order book.
-        /// 
-        /// # Events
-        /// 
-        /// * `ClaimOrderEvent` - Event emitted when order is claimed.
-        /// 
-        pub fn claim_orders(&mut self, order_receipts: Bucket) -> (Bucket, Bucket) {
-            // Check is valid order receipt
-            assert!(order_receipts.resource_address() == self.order_receipt_manager.address(), "Invalid order receipt.");
+            // Get weight of each level
+            let weights: Vec<Decimal> = Self::get_ladder_weights(levels, distribution);
+            let weight_total: Decimal = weights.iter().fold(Decimal::zero(), |total, weight| total + *weight);
+            let amount_total: Decimal = tokens.amount();
 
-            // As non fungible bucket
-            let order_receipts = order_receipts.as_non_fungible();
+            // Loop through levels
+            let mut order_receipts: Bucket = Bucket::new(self.order_receipt_manager.address());
+            for (level, weight) in weights.iter().enumerate() {
+                // Get price of level
+                let price_dec: Decimal = if levels == 1 {
+                    start_price
+                } else {
+                    start_price + (end_price - start_price) * Decimal::from(level as u64) / Decimal::from(levels - 1)
+                };
+                let price: Price = Price::from(price_dec).truncate(self.price_precision);
+                let price_dec: Decimal = price.into();
 
-            // Create buckets
-            let mut tokens_x: Bucket = Bucket::new(self.tokens_x.resource_address());
-            let mut tokens_y: Bucket = Bucket::new(self.tokens_y.resource_address());
+                // Get share of tokens for level
+                let amount: Decimal = amount_total * *weight / weight_total;
 
-            // Loop through order receipts
-            let order_id_list: Vec<NonFungibleLocalId> = order_receipts.non_fungible_local_ids().into_iter().collect();
-            let order_data_list: Vec<NonFungible<OrderReceipt>> = order_receipts.non_fungibles();
-            for i in 0..order_id_list.len() {
-                // Get order
-                let order_id: u64 = match &order_id_list[i] {
-                    NonFungibleLocalId::Integer(id) => id.value(),
-                    _ => unreachable!(),
-                };
-                let order_data: OrderReceipt = order_data_list[i].data();
+                if is_ask {
+                    // Check level does not cross the order book
+                    assert!(
+                        self.price_index.current_bid().map_or(true, |current_bid| price > current_bid), 
+                        "Ladder must not cross the order book."
+                    );
 
-                // Remove order from chain
-                self.remove_from_order_chain(&order_data);
+                    // Check order size
+                    let amount_x: Decimal = self.round_to_lot_size(amount);
+                    assert!(amount_x > Decimal::zero(), "Order size must be greater than zero.");
+                    self.check_order_size(amount_x, price_dec);
 
-                // Claim and return tokens
-                let (tokens_a, tokens_b) = if order_data.is_ask {
-                    self.claim_order_ask(order_id, &order_data)
+                    // Place limit order
+                    let order_receipt: Bucket = self.limit_order_x_to_y(tokens.take_advanced(amount_x, OUTGOING), price, true, false, expiry_epoch, None, owner.clone());
+                    order_receipts.put(order_receipt);
                 } else {
-                    self.claim_order_bid(order_id, &order_data)
-                };
-                tokens_x.put(tokens_a);
-                tokens_y.put(tokens_b);
-            }
+                    // Check level does not cross the order book
+                    assert!(
+                        self.price_index.current_ask().map_or(true, |current_ask| price < current_ask), 
+                        "Ladder must not cross the order book."
+                    );
 
-            // Burn order receipts
-            order_receipts.burn();
+                    // Check order size
+                    let amount_x: Decimal = self.round_to_lot_size(amount / price_dec);
+                    assert!(amount_x > Decimal::zero(), "Order size must be greater than zero.");
+                    self.check_order_size(amount_x, price_dec);
 
-            // Return tokens
-            (tokens_x, tokens_y)
+                    // Place limit order
+                    let amount_y: Decimal = match self.lot_size {
+                        Some(_) => amount_x * price_dec,
+                        None => amount,
+                    };
+                    let order_receipt: Bucket = self.limit_order_y_to_x(tokens.take_advanced(amount_y, OUTGOING), price, true, false, expiry_epoch, None, owner.clone());
+                    order_receipts.put(order_receipt);
+                }
+            }
+
+            // Return order receipts and tokens left over
+            (order_receipts, tokens)
         }
 
-        /// Helper method to place an ask limit order into the order book.
+        /// Execute a market order on the order book.
+        /// A fee is taken from the input tokens.
         /// Price is calculated as `tokens_y / tokens_x`.
-        /// Price is truncated to 5 significant figures.
+        /// Input tokens x left after the fee are rounded down to the lot size, any tokens left over are returned.
+        /// The value of input tokens x is calculated at the current bid price, which is the first price filled.
         /// 
         /// # Arguments
         /// 
-        /// * `tokens_x` - Tokens x used to place the limit order.
-        /// * `price` - Price at which to place limit order.
+        /// * `tokens` - Tokens used to execute the market order.
+        /// * `stop_price` - Optional stop price. If none, market order will not stop until filled.
+        /// * `mode` - Optional execution mode. If none, `ImmediateOrCancel` with no minimum amount bought.
+        /// * `max_amount_in` - Optional maximum amount of tokens to use including the fee. If none, use all tokens.
+        /// * `owner_proof` - Optional proof of a badge identifying the owner of the market order for self trade prevention.
+        /// * `self_trade_mode` - Optional self trade prevention mode applied to limit orders of the same owner. 
+        /// If none, orders of the same owner are matched.
         /// 
         /// # Returns
         /// 
-        /// * `Bucket` - Contains an order receipt.
+        /// * `Bucket` - Contains any tokens bought.
+        /// * `Bucket` - Contains any tokens remaining.
         /// 
-        /// # Requires
+        /// # Panics
         /// 
-        /// * `tokens_x` - Must be tokens x.
-        /// * `tokens_x` - Must not be empty.
-        /// * `price` - Must be greater than current bid price.
+        /// * If the order book is not active.
+        /// * If amount of tokens is not greater than zero.
+        /// * If `max_amount_in` is not greater than zero.
+        /// * If tokens are not tokens x or tokens y.
+        /// * If amount of tokens x is less than the lot size.
+        /// * If value of tokens is less than the minimum order size.
+        /// * If tokens are tokens x, there is a minimum order size and there are no bid limits to value them.
+        /// * If `mode` is `ImmediateOrCancel` and the amount of tokens bought is less than the minimum.
+        /// * If `mode` is `FillOrKill` and the market order is not fully filled.
+        /// * If `self_trade_mode` is provided without an `owner_proof`.
+        /// * If `owner_proof` is not a proof of a single non fungible badge.
         /// 
         /// # Events
         /// 
-        /// * `LimitOrderEvent` - Event emitted when limit order is placed.
+        /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
         /// 
-        fn limit_order_x_to_y(&mut self, tokens_x: Bucket, price: Price) -> Bucket {
-            // Deposit tokens
-            let amount_x: Decimal = tokens_x.amount();
-            self.tokens_x.put(tokens_x);
+        pub fn market_order(
+            &mut self, 
+            mut tokens: Bucket, 
+            stop_price: Option<Decimal>, 
+            mode: Option<MarketOrderMode>, 
+            max_amount_in: Option<Decimal>,
+            owner_proof: Option<Proof>,
+            self_trade_mode: Option<SelfTradeMode>,
+        ) -> (Bucket, Bucket) {
+            // Check order book is active
+            self.check_active();
 
-            // Get order nonce to make order receipt id
-            let order_id: u64 = self.nonce;
-            self.nonce += 1;
+            // Check parameters
+            assert!(tokens.amount() > Decimal::zero(), "Order size must be greater than zero.");
+            assert!(max_amount_in.is_none() || max_amount_in.unwrap() > Decimal::zero(), "Maximum input must be greater than zero.");
+            let mode: MarketOrderMode = mode.unwrap_or(MarketOrderMode::ImmediateOrCancel(Decimal::zero()));
+            let (_, self_trade): (Option<NonFungibleGlobalId>, Option<(NonFungibleGlobalId, SelfTradeMode)>) = 
+                Self::get_owner(owner_proof, self_trade_mode);
 
-            // Get limit
-            let mut limit = match self.ask_limit_map.get(&price) {
-                Some(limit) => {
-                    // If empty limit add price to price tree
-                    if limit.is_empty() {
-                        self.price_index.insert(price, true);
-                    }
+            // Get amount of tokens to use
+            let is_x: bool = tokens.resource_address() == self.tokens_x.resource_address();
+            let amount_in: Decimal = match max_amount_in {
+                Some(max_amount_in) => tokens.amount().min(max_amount_in),
+                None => tokens.amount(),
+            };
 
-                    // Return limit
-                    *limit
-                },
-                None => {
-                    // Add price to price tree
-                    self.price_index.insert(price, true);
+            // Check order size, valuing tokens x at the first price filled
+            assert!(
+                !is_x || self.round_to_lot_size(amount_in) > Decimal::zero(), 
+                "Order size must be at least the lot size."
+            );
+            let value_in: Decimal = if is_x {
+                self.price_index.current_bid().map_or(Decimal::zero(), |price| amount_in * Decimal::from(price))
+            } else {
+                amount_in
+            };
+            assert!(value_in >= self.min_order_size, "Order size must be at least the minimum order size.");
 
-                    // Return new limit
-                    Limit::new()
-                }
+            // Set aside tokens over the maximum input
+            let (tokens, tokens_excess): (Bucket, Option<Bucket>) = if tokens.amount() > amount_in {
+                (tokens.take_advanced(amount_in, OUTGOING), Some(tokens))
+            } else {
+                (tokens, None)
             };
 
-            // Add order to limit
-            let tail_id: u64 = limit.add_order(order_id, &amount_x);
-            self.ask_limit_map.insert(price, limit);
+            // Check if tokens_x or tokens_y
+            let (tokens_bought, mut tokens_remaining) = if tokens.resource_address() == self.tokens_x.resource_address() {
+                let stop_price: Price = match stop_price {
+                    Some(price_dec) => price_dec.round_to_price_range().into(),
+                    None => Price::MIN,
+                };
 
-            // Update tail order receipt
-            if tail_id != 0 {
-                self.order_receipt_manager.update_non_fungible_data(&NonFungibleLocalId::integer(tail_id), "next_id", order_id);
-            }
+                self.market_order_x_to_y(tokens, stop_price, mode, false, self_trade)
+            } else if tokens.resource_address() == self.tokens_y.resource_address() {
+                let stop_price: Price = match stop_price {
+                    Some(price_dec) => price_dec.round_to_price_range().into(),
+                    None => Price::MAX,
+                };
 
-            // Mint new order receipt
-            let price = Decimal::from(price);
-            let order_receipt: Bucket = self.order_receipt_manager.mint_non_fungible(
-                &NonFungibleLocalId::integer(order_id),
-                OrderReceipt {
-                    is_ask: true,
-                    price,
-                    amount: amount_x,
-                    next_id: 0,
-                    prev_id: tail_id,
-                },
-            );
+                self.market_order_y_to_x(tokens, stop_price, mode, false, self_trade)
+            } else {
+                panic!("Invalid token address.");
+            };
 
-            // Emit limit order event
-            Runtime::emit_event(LimitOrderEvent {
-                order_id: NonFungibleLocalId::integer(order_id),
-                is_ask: true,
-                price,
-                amount: amount_x,
-            });
+            // Return excess tokens with remaining tokens
+            if let Some(tokens_excess) = tokens_excess {
+                tokens_remaining.put(tokens_excess);
+            }
 
-            // Return order receipt
-            order_receipt
+            // Return tokens
+            (tokens_bought, tokens_remaining)
         }
 
-        /// Helper method to place a bid limit order into the order book.
+        /// Place a trigger order into the order book.
+        /// Tokens are escrowed in the order book and executed as a market order once the last price 
+        /// crosses the trigger price. Triggered orders are executed by calling `execute_trigger_orders`, 
+        /// which pays the bounty of each executed order to the caller.
         /// Price is calculated as `tokens_y / tokens_x`.
-        /// Price is truncated to 5 significant figures.
+        /// Trigger price is truncated to the price precision of the order book.
+        /// Input tokens x are rounded down to the lot size, any tokens left over are returned.
         /// 
         /// # Arguments
         /// 
-        /// * `tokens_y` - Tokens y used to place the limit order.
-        /// * `price` - Price at which to place limit order.
+        /// * `tokens` - Tokens used to place the trigger order, including the bounty.
+        /// * `trigger_price` - Price at which the order is triggered.
+        /// * `mode` - Trigger mode of the order.
+        /// * `stop_price` - Optional stop price of the market order executed when triggered. If none, the market order will not stop until filled.
+        /// * `bounty` - Amount of tokens paid to the caller that executes the trigger order.
         /// 
         /// # Returns
         /// 
         /// * `Bucket` - Contains an order receipt.
+        /// * `Bucket` - Contains any tokens leftover.
         /// 
-        /// # Requires
+        /// # Panics
         /// 
-        /// * `tokens_y` - Must be tokens y.
-        /// * `tokens_y` - Must not be empty.
-        /// * `price` - Must be less than current ask price.
+        /// * If the order book is not active.
+        /// * If `tokens` are not tokens x or tokens y.
+        /// * If `bounty` is negative.
+        /// * If order size after the bounty is not greater than zero.
+        /// * If order size is less than the lot size.
+        /// * If order value at the trigger price is less than the minimum order size.
+        /// * If `trigger_price` is not in the valid range of [0.00000000001, 100000000000].
         /// 
         /// # Events
         /// 
-        /// * `LimitOrderEvent` - Event emitted when limit order is placed.
-        /// 
-        fn limit_order_y_to_x(&mut self, tokens_y: Bucket, price: Price) -> Bucket {
-            // Deposit tokens
-            let amount_y: Decimal = tokens_y.amount();
-            let price_dec: Decimal = price.into();
-            let amount_x: Decimal = amount_y / price_dec;
-            self.tokens_y.put(tokens_y);
+        /// * `TriggerOrderEvent` - Event emitted when trigger order is placed.
+        /// 
+        pub fn trigger_order(
+            &mut self, 
+            mut tokens: Bucket, 
+            trigger_price: Decimal, 
+            mode: TriggerMode, 
+            stop_price: Option<Decimal>, 
+            bounty: Decimal,
+        ) -> (Bucket, Bucket) {
+            // Check order book is active
+            self.check_active();
 
-            // Get order nonce to make order receipt id
-            let order_id: u64 = self.nonce;
-            self.nonce += 1;
+            // Check parameters
+            assert!(bounty >= Decimal::zero(), "Bounty must not be negative.");
+            assert!(tokens.amount() > bounty, "Order size must be greater than zero.");
+            let trigger_price: Decimal = Price::from(trigger_price).truncate(self.price_precision).into();
 
-            // Get limit
-            let mut limit = match self.bid_limit_map.get(&price) {
-                Some(limit) => {
-                    // If empty limit add price to price tree
-                    if limit.is_empty() {
-                        self.price_index.insert(price, false);
-                    }
+            // Check if tokens_x or tokens_y
+            let is_ask: bool = if tokens.resource_address() == self.tokens_x.resource_address() {
+                true
+            } else if tokens.resource_address() == self.tokens_y.resource_address() {
+                false
+            } else {
+                panic!("Invalid token address.");
+            };
 
-                    // Return limit
-                    *limit
-                },
-                None => {
-                    // Add price to price tree
-                    self.price_index.insert(price, false);
+            // Take bounty
+            let tokens_bounty: Bucket = tokens.take_advanced(bounty, INCOMING);
 
-                    // Return new limit
-                    Limit::new()
-                }
+            // Check order size, rounding tokens x down to the lot size
+            let (amount_in, amount_x): (Decimal, Decimal) = if is_ask {
+                let amount_x: Decimal = self.round_to_lot_size(tokens.amount());
+                (amount_x, amount_x)
+            } else {
+                (tokens.amount(), tokens.amount() / trigger_price)
             };
+            self.check_order_size(amount_x, trigger_price);
 
-            // Add order to limit
-            let tail_id: u64 = limit.add_order(order_id, &amount_x);
-            self.bid_limit_map.insert(price, limit);
-    
-            // Update tail order receipt
-            if tail_id != 0 {
-                self.order_receipt_manager.update_non_fungible_data(&NonFungibleLocalId::integer(tail_id), "next_id", order_id);
+            // Deposit tokens
+            let tokens_in: Bucket = tokens.take_advanced(amount_in, OUTGOING);
+            let trigger = Trigger {
+                mode,
+                trigger_price,
+                stop_price,
+                amount_in: tokens_in.amount(),
+                bounty: tokens_bounty.amount(),
+            };
+            if is_ask {
+                self.trigger_tokens_x.put(tokens_in);
+                self.trigger_tokens_x.put(tokens_bounty);
+            } else {
+                self.trigger_tokens_y.put(tokens_in);
+                self.trigger_tokens_y.put(tokens_bounty);
             }
 
+            // Get order nonce to make order receipt id
+            let order_id: u64 = self.nonce;
+            self.nonce += 1;
+
             // Mint new order receipt
-            let price = Decimal::from(price);
             let order_receipt: Bucket = self.order_receipt_manager.mint_non_fungible(
                 &NonFungibleLocalId::integer(order_id),
                 OrderReceipt {
-                    is_ask: false,
-                    price,
+                    is_ask,
+                    price: trigger_price,
                     amount: amount_x,
+                    display_amount: None,
+                    amount_reserve: Decimal::zero(),
+                    amount_slices_filled: Decimal::zero(),
+                    expiry_epoch: None,
+                    maker_fee: Decimal::zero(),
+                    sequence: 0,
                     next_id: 0,
-                    prev_id: tail_id,
+                    prev_id: 0,
+                    amount_claimed: Decimal::zero(),
+                    expired_amount_filled: None,
+                    owner: None,
+                    is_self_trade_canceled: false,
+                    is_skipped: false,
+                    trigger: Some(trigger),
+                    trigger_amounts: None,
                 },
             );
 
-            // Emit limit order event
-            Runtime::emit_event(LimitOrderEvent {
+            // Emit trigger order event
+            Runtime::emit_event(TriggerOrderEvent {
                 order_id: NonFungibleLocalId::integer(order_id),
-                is_ask: false,
-                price,
-                amount: amount_x,
+                is_ask,
+                trigger_price,
+                mode,
+                stop_price,
+                amount_in: trigger.amount_in,
+                bounty: trigger.bounty,
             });
 
-            // Return order receipt
-            order_receipt
+            // Return order receipt and leftover tokens
+            (order_receipt, tokens)
         }
 
-        /// Helper method to execute a market order on ask limits in the order book.
-        /// A fee is taken from the input tokens.
-        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Execute trigger orders whose trigger price has been crossed by the last price.
+        /// Each trigger order is executed as a market order and its bounty is paid to the caller.
+        /// Trigger orders are not executed before the order book has been filled for the first time.
+        /// If limits have already been filled in the same transaction, the last price before that transaction is used, 
+        /// so a caller can not move the price to trigger orders and collect their bounties.
+        /// Orders that are not triggered, already executed, or claimed are skipped.
         /// 
         /// # Arguments
         /// 
-        /// * `tokens_y` - Tokens y used to execute the market order.
-        /// * `stop_price` - Stop price at which to stop executing market order.
+        /// * `order_receipt_ids` - Ids of order receipts of trigger orders to execute.
         /// 
         /// # Returns
         /// 
-        /// * `Bucket` - Contains any tokens x bought.
-        /// * `Bucket` - Contains any tokens y remaining.
+        /// * `Bucket` - Contains bounties paid in tokens x.
+        /// * `Bucket` - Contains bounties paid in tokens y.
         /// 
-        /// # Requires
+        /// # Panics
         /// 
-        /// * `tokens_y` - Must be tokens y.
-        /// * `tokens_y` - Must not be empty.
+        /// * If the order book is not active.
         /// 
         /// # Events
         /// 
+        /// * `ExecuteTriggerOrderEvent` - Event emitted when trigger order is executed.
         /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
         /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
         /// 
-        fn market_order_y_to_x(&mut self, mut tokens_y: Bucket, stop_price: Price) -> (Bucket, Bucket) {
-            // Take fee
-            let protocol_fee: Decimal =  FEE_CONTROLLER.get_protocol_fee(Runtime::package_address());
-            let mut tokens_fee: Bucket = tokens_y.take_advanced(tokens_y.amount() * protocol_fee, INCOMING);
-            let amount_y_input: Decimal = tokens_y.amount();
-
-            // Initialize amounts
-            let mut amount_x_bought: Decimal = Decimal::zero();
-            let mut amount_y_order: Decimal = tokens_y.amount();
-            let mut fills: Vec<(Decimal, Decimal)> = Vec::new();
+        pub fn execute_trigger_orders(&mut self, order_receipt_ids: Vec<NonFungibleLocalId>) -> (Bucket, Bucket) {
+            // Check order book is active
+            self.check_active();
 
-            // Loop through limits
-            while let Some(price) = self.price_index.current_ask() {
-                // Decompress price
-                let price_dec: Decimal = price.into();
+            // Create buckets
+            let mut bounties_x: Bucket = Bucket::new(self.tokens_x.resource_address());
+            let mut bounties_y: Bucket = Bucket::new(self.tokens_y.resource_address());
 
-                // Check if price is past stop price
-                if price > stop_price {
-                    break;
+            // Loop through order receipts
+            for order_receipt_id in order_receipt_ids {
+                // Skip if order no longer exists
+                if !self.order_receipt_manager.non_fungible_exists(&order_receipt_id) {
+                    continue;
                 }
 
-                // Update last traded price
-                self.last_price = price;
-
-                // Get limit
-                let limit: &mut Limit = &mut self.ask_limit_map.get_mut(&price).unwrap();
-
-                // Calculate amounts
-                let amount_x_limit: Decimal = limit.get_amount_x();
-                let amount_y_limit: Decimal = amount_x_limit * price_dec;
+                // Skip if not a trigger order waiting to be triggered
+                let order_data: OrderReceipt = self.order_receipt_manager.get_non_fungible_data(&order_receipt_id);
+                let trigger: Trigger = match order_data.trigger {
+                    Some(trigger) if order_data.trigger_amounts.is_none() => trigger,
+                    _ => continue,
+                };
 
-                // If order amount is greater or equal to the limit amount fill limit and continue,
-                // else partly fill limit and break
-                if amount_y_order >= amount_y_limit {
-                    limit.fully_fill();
-                    fills.push((price_dec, amount_x_limit));
-                    amount_x_bought += amount_x_limit;
-                    amount_y_order -= amount_y_limit;
+                // Skip if last price has not crossed trigger price
+                let trigger_price: Price = match self.get_trigger_reference_price() {
+                    Some(price) => price,
+                    None => continue,
+                };
+                if !trigger.is_triggered(order_data.is_ask, trigger_price.into()) {
+                    continue;
+                }
 
-                    self.price_index.remove(price);
+                // Execute trigger order and collect bounty
+                let tokens_bounty: Bucket = self.execute_trigger_order(&order_receipt_id, &order_data, &trigger);
+                if order_data.is_ask {
+                    bounties_x.put(tokens_bounty);
                 } else {
-                    let amount_x_order: Decimal = amount_y_order / price_dec;
-                    limit.fill(&amount_x_order, &self.order_receipt_manager);
-                    fills.push((price_dec, amount_x_order));
-                    amount_x_bought += amount_x_order;
-                    amount_y_order = Decimal::zero();
-
-                    break;
+                    bounties_y.put(tokens_bounty);
                 }
             }
 
-            // Deposit and withdraw tokens
-            let tokens_x: Bucket = self.tokens_x.take_advanced(amount_x_bought, OUTGOING);
-            self.tokens_y.put(tokens_y.take_advanced(tokens_y.amount() - amount_y_order, INCOMING));
-            
-            // If fee amount is not zero
-            if !tokens_fee.is_empty() {
-                // Return fee if tokens not used
-                if tokens_y.amount() != Decimal::zero() {
-                    let amount_unused: Decimal = tokens_y.amount() / amount_y_input * tokens_fee.amount();
-                    tokens_y.put(tokens_fee.take_advanced(amount_unused, OUTGOING));
-                }
+            // Return bounties
+            (bounties_x, bounties_y)
+        }
+
+        /// Amend the amount and price of an open limit order without burning its order receipt.
+        /// Reducing the amount keeps the position of the order in its limit. Changing the price 
+        /// moves the order to the end of the limit at the new price and returns any tokens 
+        /// from the filled part of the order.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Price is truncated to the price precision of the order book.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_proof` - Proof of the order receipt.
+        /// * `amount` - New unfilled amount of the order calculated in tokens x.
+        /// * `price` - Optional new price of the order. If none, the price is not changed.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains any tokens x returned.
+        /// * `Bucket` - Contains any tokens y returned.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the order book is not active.
+        /// * If `order_proof` is not a proof of an order receipt for this order book.
+        /// * If the order is not open.
+        /// * If the order is an iceberg order.
+        /// * If the order is a trigger order.
+        /// * If `amount` is not greater than zero.
+        /// * If `amount` is not a multiple of the lot size.
+        /// * If the amended order value is less than the minimum order size.
+        /// * If the amended order requires more tokens than the unfilled part of the order.
+        /// * If `price` is not in the valid range of [0.00000000001, 100000000000].
+        /// * If the amended order would cross the order book.
+        /// 
+        /// # Events
+        /// 
+        /// * `AmendOrderEvent` - Event emitted when limit order is amended.
+        /// * `ProtocolFeeEvent` - Event emitted when maker fee is collected.
+        /// 
+        pub fn amend_order(&mut self, order_proof: Proof, amount: Decimal, price: Option<Decimal>) -> (Bucket, Bucket) {
+            // Check order book is active
+            self.check_active();
+
+            // Check proof
+            let order_receipt: NonFungible<OrderReceipt> = order_proof
+                .check(self.order_receipt_manager.address())
+                .as_non_fungible()
+                .non_fungible();
+            let order_receipt_id: NonFungibleLocalId = order_receipt.local_id().clone();
+            let order_id: u64 = match &order_receipt_id {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => unreachable!(),
+            };
+            let order_data: OrderReceipt = order_receipt.data();
+
+            // Check parameters
+            assert!(amount > Decimal::zero(), "Order size must be greater than zero.");
+            assert!(order_data.display_amount.is_none(), "Iceberg orders can not be amended.");
+            assert!(order_data.trigger.is_none(), "Trigger orders can not be amended.");
+            let old_price: Price = order_data.price.into();
+            let new_price: Price = match price {
+                Some(price) => Price::from(price).truncate(self.price_precision),
+                None => old_price,
+            };
+            assert!(amount == self.round_to_lot_size(amount), "Order size must be a multiple of the lot size.");
+            assert!(amount * Decimal::from(new_price) >= self.min_order_size, "Order size must be at least the minimum order size.");
+
+            // Get limit
+            let mut limit: Limit = if order_data.is_ask {
+                *self.ask_limit_map.get(&old_price).unwrap()
+            } else {
+                *self.bid_limit_map.get(&old_price).unwrap()
+            };
+
+            // Check order is open
+            let is_expired: bool = order_data.expired_amount_filled.is_some() || match order_data.expiry_epoch {
+                Some(expiry_epoch) => Runtime::current_epoch().number() > expiry_epoch,
+                None => false,
+            };
+            let is_filled: bool = limit.is_filled(&order_data);
+            assert!(!is_expired && !is_filled, "Order is not open.");
+
+            // Calculate unfilled amount
+            let amount_filled: Decimal = if order_id == limit.get_head_id() {
+                limit.get_amount_x_unallocated()
+            } else {
+                Decimal::zero()
+            };
+            let amount_unfilled: Decimal = order_data.amount - amount_filled;
+
+            // Calculate tokens released from the unfilled part of the order
+            let (mut amount_x, mut amount_y): (Decimal, Decimal) = if order_data.is_ask {
+                (amount_unfilled - amount, Decimal::zero())
+            } else {
+                (Decimal::zero(), amount_unfilled * order_data.price - amount * Decimal::from(new_price))
+            };
+            assert!(amount_x >= Decimal::zero() && amount_y >= Decimal::zero(), "Amended order must not require more tokens.");
+
+            if new_price == old_price {
+                // Reduce order without changing its position
+                limit.reduce_order(&(amount_unfilled - amount), order_data.maker_fee);
+                if order_data.is_ask {
+                    self.ask_limit_map.insert(old_price, limit);
+                } else {
+                    self.bid_limit_map.insert(old_price, limit);
+                }
+
+                // Update order receipt
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount", amount_filled + amount);
+            } else {
+                // Check new price does not cross the order book
+                let is_crossing: bool = if order_data.is_ask {
+                    self.price_index.current_bid().map_or(false, |current_bid| new_price <= current_bid)
+                } else {
+                    self.price_index.current_ask().map_or(false, |current_ask| new_price >= current_ask)
+                };
+                assert!(!is_crossing, "Amended order would cross the order book.");
+
+                // Remove order from limit at old price
+                self.remove_from_order_chain(&order_data);
+                limit.claim_order(order_id, &order_data);
+                let is_empty: bool = limit.is_empty();
+                if order_data.is_ask {
+                    self.ask_limit_map.insert(old_price, limit);
+                } else {
+                    self.bid_limit_map.insert(old_price, limit);
+                }
+
+                // If limit is empty remove from price tree
+                if is_empty {
+                    self.price_index.remove(old_price);
+                }
+
+                // Return tokens from unclaimed filled part of the order
+                let amount_unclaimed: Decimal = amount_filled - order_data.amount_claimed;
+                if order_data.is_ask {
+                    amount_y += amount_unclaimed * order_data.price;
+                } else {
+                    amount_x += amount_unclaimed;
+                }
+
+                // Add order to limit at new price
+                let (sequence, tail_id): (u64, u64) = self.add_to_limit(order_id, new_price, order_data.is_ask, amount, Decimal::zero(), order_data.expiry_epoch, order_data.maker_fee);
+
+                // Update order receipt
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "price", Decimal::from(new_price));
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount", amount);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount_claimed", Decimal::zero());
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "sequence", sequence);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "next_id", 0u64);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "prev_id", tail_id);
+                if order_data.is_skipped {
+                    self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "is_skipped", false);
+                }
+            }
+
+            // Withdraw tokens
+            let mut tokens_x: Bucket = self.tokens_x.take_advanced(amount_x, OUTGOING);
+            let mut tokens_y: Bucket = self.tokens_y.take_advanced(amount_y, OUTGOING);
+
+            // Apply maker fee to tokens from the filled part of the order
+            if new_price != old_price {
+                if order_data.is_ask {
+                    self.apply_maker_fee(&mut tokens_y, order_data.maker_fee);
+                } else {
+                    self.apply_maker_fee(&mut tokens_x, order_data.maker_fee);
+                }
+            }
+
+            // Emit amend order event
+            Runtime::emit_event(AmendOrderEvent {
+                order_id: order_receipt_id,
+                is_ask: order_data.is_ask,
+                old_price: order_data.price,
+                new_price: new_price.into(),
+                amount,
+                amount_x: tokens_x.amount(),
+                amount_y: tokens_y.amount(),
+            });
+
+            // Return tokens
+            (tokens_x, tokens_y)
+        }
+
+        /// Claim tokens owned by order receipts.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_receipts` - Order receipts.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains any tokens x from claimed orders.
+        /// * `Bucket` - Contains any tokens y from claimed orders.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the order book is halted.
+        /// * If an order receipt is invalid.
+        /// * If an order receipt is for a different order book.
+        /// 
+        /// # Events
+        /// 
+        /// * `ClaimOrderEvent` - Event emitted when order is claimed.
+        /// * `ProtocolFeeEvent` - Event emitted when maker fee is collected.
+        /// 
+        pub fn claim_orders(&mut self, order_receipts: Bucket) -> (Bucket, Bucket) {
+            // Check order book is not halted
+            self.check_not_halted();
+
+            // Check is valid order receipt
+            assert!(order_receipts.resource_address() == self.order_receipt_manager.address(), "Invalid order receipt.");
+
+            // As non fungible bucket
+            let order_receipts = order_receipts.as_non_fungible();
+
+            // Create buckets
+            let mut tokens_x: Bucket = Bucket::new(self.tokens_x.resource_address());
+            let mut tokens_y: Bucket = Bucket::new(self.tokens_y.resource_address());
+
+            // Loop through order receipts
+            let order_id_list: Vec<NonFungibleLocalId> = order_receipts.non_fungible_local_ids().into_iter().collect();
+            let order_data_list: Vec<NonFungible<OrderReceipt>> = order_receipts.non_fungibles();
+            for i in 0..order_id_list.len() {
+                // Get order
+                let order_id: u64 = match &order_id_list[i] {
+                    NonFungibleLocalId::Integer(id) => id.value(),
+                    _ => unreachable!(),
+                };
+                let order_data: OrderReceipt = order_data_list[i].data();
+
+                // Claim and return tokens
+                let (tokens_a, tokens_b) = if order_data.trigger.is_some() {
+                    // Trigger order was never added to a limit
+                    self.claim_order_trigger(order_id, &order_data)
+                } else if order_data.expired_amount_filled.is_some() {
+                    // Expired order already removed from chain
+                    self.claim_order_expired(order_id, &order_data)
+                } else {
+                    // Remove order from chain
+                    self.remove_from_order_chain(&order_data);
+
+                    if order_data.is_ask {
+                        self.claim_order_ask(order_id, &order_data)
+                    } else {
+                        self.claim_order_bid(order_id, &order_data)
+                    }
+                };
+                tokens_x.put(tokens_a);
+                tokens_y.put(tokens_b);
+            }
+
+            // Burn order receipts
+            order_receipts.burn();
+
+            // Return tokens
+            (tokens_x, tokens_y)
+        }
+
+        /// Claim tokens from the filled part of a limit order without burning its order receipt.
+        /// The unfilled part of the limit order stays in the order book.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_proof` - Proof of the order receipt.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains tokens y for an ask limit order, or tokens x for a bid limit order.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the order book is halted.
+        /// * If `order_proof` is not a proof of an order receipt for this order book.
+        /// * If the order is a trigger order.
+        /// 
+        /// # Events
+        /// 
+        /// * `ClaimOrderEvent` - Event emitted when order is claimed.
+        /// * `ProtocolFeeEvent` - Event emitted when maker fee is collected.
+        /// 
+        pub fn claim_filled(&mut self, order_proof: Proof) -> Bucket {
+            // Check order book is not halted
+            self.check_not_halted();
+
+            // Check proof
+            let order_receipt: NonFungible<OrderReceipt> = order_proof
+                .check(self.order_receipt_manager.address())
+                .as_non_fungible()
+                .non_fungible();
+            let order_receipt_id: NonFungibleLocalId = order_receipt.local_id().clone();
+            let order_id: u64 = match &order_receipt_id {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => unreachable!(),
+            };
+            let order_data: OrderReceipt = order_receipt.data();
+            assert!(order_data.trigger.is_none(), "Trigger orders can not be partly claimed.");
+
+            // Get filled amount
+            let amount_filled: Decimal = match order_data.expired_amount_filled {
+                Some(amount_filled) => amount_filled,
+                None => {
+                    let price: Price = order_data.price.into();
+                    let limit: Limit = if order_data.is_ask {
+                        *self.ask_limit_map.get(&price).unwrap()
+                    } else {
+                        *self.bid_limit_map.get(&price).unwrap()
+                    };
+
+                    if limit.is_filled(&order_data) {
+                        order_data.amount_slices_filled + order_data.amount
+                    } else if order_id == limit.get_head_id() {
+                        order_data.amount_slices_filled + limit.get_amount_x_unallocated()
+                    } else {
+                        order_data.amount_slices_filled
+                    }
+                },
+            };
+
+            // Record claimed amount
+            let amount_unclaimed: Decimal = amount_filled - order_data.amount_claimed;
+            self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount_claimed", amount_filled);
+
+            // Withdraw tokens
+            let mut tokens: Bucket = if order_data.is_ask {
+                self.tokens_y.take_advanced(amount_unclaimed * order_data.price, OUTGOING)
+            } else {
+                self.tokens_x.take_advanced(amount_unclaimed, OUTGOING)
+            };
+
+            // Apply maker fee
+            let (maker_fee, maker_rebate) = self.apply_maker_fee(&mut tokens, order_data.maker_fee);
+            let (amount_x, amount_y) = if order_data.is_ask {
+                (Decimal::zero(), tokens.amount())
+            } else {
+                (tokens.amount(), Decimal::zero())
+            };
+
+            // Emit claim order event
+            Runtime::emit_event(ClaimOrderEvent {
+                order_id: order_receipt_id,
+                is_ask: order_data.is_ask,
+                price: order_data.price,
+                amount_canceled: Decimal::zero(),
+                amount_filled: amount_unclaimed,
+                amount_x,
+                amount_y,
+                maker_fee,
+                maker_rebate,
+            });
+
+            // Return tokens
+            tokens
+        }
+
+        /// Helper method to place a limit order into the order book.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Price is truncated to the price precision of the order book.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens` - Tokens used to place the limit order.
+        /// * `price` - Price at which to place limit order.
+        /// * `post_only` - Optional post only mode.
+        /// * `expiry_epoch` - Optional epoch after which the limit order expires.
+        /// * `display_amount` - Optional amount calculated in tokens x shown in the limit at a time for an iceberg order.
+        /// * `owner` - Optional global id of the badge identifying the owner of the order.
+        /// * `self_trade` - Optional owner and self trade prevention mode applied when executing any overlap.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Possibly contains an order_receipt.
+        /// * `Bucket` - Contains any tokens immediately bought.
+        /// * `Bucket` - Contains any tokens leftover.
+        /// 
+        /// # Panics
+        /// 
+        /// * Same as `limit_order`.
+        /// 
+        /// # Events
+        /// 
+        /// * `LimitOrderEvent` - Event emitted when limit order is placed.
+        /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// 
+        fn place_limit_order(
+            &mut self, 
+            tokens: Bucket, 
+            price: Decimal, 
+            post_only: Option<PostOnlyMode>, 
+            expiry_epoch: Option<u64>,
+            display_amount: Option<Decimal>,
+            owner: Option<NonFungibleGlobalId>,
+            self_trade: Option<(NonFungibleGlobalId, SelfTradeMode)>,
+        ) -> (Bucket, Bucket, Bucket) { 
+            // Check parameters
+            assert!(
+                expiry_epoch.is_none() || expiry_epoch.unwrap() >= Runtime::current_epoch().number(), 
+                "Expiry epoch must not be before the current epoch."
+            );
+            assert!(
+                display_amount.is_none() || display_amount.unwrap() > Decimal::zero(), 
+                "Display amount must be greater than zero."
+            );
+
+            // Truncate price to price precision
+            let price: Price = Price::from(price).truncate(self.price_precision);
+
+            // Check if tokens_x or tokens_y
+            if tokens.resource_address() == self.tokens_x.resource_address() {
+                // Apply post only mode
+                let (price, is_repriced): (Price, bool) = self.apply_post_only(price, true, post_only);
+
+                // Check order size
+                let price_dec: Decimal = price.into();
+                self.check_order_size(tokens.amount(), price_dec);
+
+                // Market order to clear overlap
+                let current_bid: Option<Price> = self.price_index.current_bid();
+                let (tokens_y, mut tokens_x) = if current_bid.is_some() && price <= current_bid.unwrap() {
+                    self.market_order_x_to_y(tokens, price, MarketOrderMode::ImmediateOrCancel(Decimal::zero()), true, self_trade)
+                } else {
+                    (Bucket::new(self.tokens_y.resource_address()), tokens)
+                };
+
+                // If remaining tokens meet lot size and minimum order size and no longer cross the order book 
+                // after self trade prevention place limit order
+                let amount_x: Decimal = self.get_amount_x_placeable(tokens_x.amount(), price_dec);
+                let is_crossing: bool = self.price_index.current_bid().map_or(false, |current_bid| price <= current_bid);
+                let order_receipt = if amount_x > Decimal::ZERO && !is_crossing {
+                    self.limit_order_x_to_y(tokens_x.take_advanced(amount_x, OUTGOING), price, post_only.is_some(), is_repriced, expiry_epoch, display_amount, owner)
+                } else {
+                    Bucket::new(self.order_receipt_manager.address())
+                };
+
+                // Return buckets
+                (order_receipt, tokens_y, tokens_x)
+            } else if tokens.resource_address() == self.tokens_y.resource_address() {
+                // Apply post only mode
+                let (price, is_repriced): (Price, bool) = self.apply_post_only(price, false, post_only);
+
+                // Check order size
+                let price_dec: Decimal = price.into();
+                self.check_order_size(tokens.amount() / price_dec, price_dec);
+
+                // Market order to clear overlap
+                let current_ask: Option<Price> = self.price_index.current_ask();
+                let (tokens_x, mut tokens_y) = if current_ask.is_some() && price >= current_ask.unwrap() {
+                    self.market_order_y_to_x(tokens, price, MarketOrderMode::ImmediateOrCancel(Decimal::zero()), true, self_trade)
+                } else {
+                    (Bucket::new(self.tokens_x.resource_address()), tokens)
+                };
+
+                // If remaining tokens meet lot size and minimum order size and no longer cross the order book 
+                // after self trade prevention place limit order
+                let amount_x: Decimal = self.get_amount_x_placeable(tokens_y.amount() / price_dec, price_dec);
+                let is_crossing: bool = self.price_index.current_ask().map_or(false, |current_ask| price >= current_ask);
+                let order_receipt = if amount_x > Decimal::ZERO && !is_crossing {
+                    let amount_y: Decimal = match self.lot_size {
+                        Some(_) => amount_x * price_dec,
+                        None => tokens_y.amount(),
+                    };
+                    self.limit_order_y_to_x(tokens_y.take_advanced(amount_y, OUTGOING), price, post_only.is_some(), is_repriced, expiry_epoch, display_amount, owner)
+                } else {
+                    Bucket::new(self.order_receipt_manager.address())
+                };
+
+                // Return buckets
+                (order_receipt, tokens_x, tokens_y)
+            } else {
+                panic!("Invalid token address.");
+            }
+        }
+
+        /// Helper method to place an ask limit order into the order book.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Price is truncated to 5 significant figures.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_x` - Tokens x used to place the limit order.
+        /// * `price` - Price at which to place limit order.
+        /// * `is_post_only` - Whether the limit order was placed as post only.
+        /// * `is_repriced` - Whether the limit order price was moved to not cross the order book.
+        /// * `expiry_epoch` - Optional epoch after which the limit order expires.
+        /// * `display_amount` - Optional amount calculated in tokens x shown in the limit at a time for an iceberg order.
+        /// * `owner` - Optional global id of the badge identifying the owner of the order.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains an order receipt.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tokens_x` - Must be tokens x.
+        /// * `tokens_x` - Must not be empty.
+        /// * `price` - Must be greater than current bid price.
+        /// 
+        /// # Events
+        /// 
+        /// * `LimitOrderEvent` - Event emitted when limit order is placed.
+        /// 
+        fn limit_order_x_to_y(
+            &mut self, 
+            tokens_x: Bucket, 
+            price: Price, 
+            is_post_only: bool, 
+            is_repriced: bool, 
+            expiry_epoch: Option<u64>,
+            display_amount: Option<Decimal>,
+            owner: Option<NonFungibleGlobalId>,
+        ) -> Bucket {
+            // Deposit tokens
+            let amount_x: Decimal = tokens_x.amount();
+            self.tokens_x.put(tokens_x);
+
+            // Get order nonce to make order receipt id
+            let order_id: u64 = self.nonce;
+            self.nonce += 1;
+
+            // Split iceberg order into shown slice and hidden reserve
+            let amount_slice: Decimal = display_amount.map_or(amount_x, |display_amount| display_amount.min(amount_x));
+            let amount_reserve: Decimal = amount_x - amount_slice;
+
+            // Add order to limit
+            let maker_fee: Decimal = Self::get_maker_fee();
+            let (sequence, tail_id): (u64, u64) = self.add_to_limit(order_id, price, true, amount_slice, amount_reserve, expiry_epoch, maker_fee);
+
+            // Mint new order receipt
+            let price = Decimal::from(price);
+            let order_receipt: Bucket = self.order_receipt_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(order_id),
+                OrderReceipt {
+                    is_ask: true,
+                    price,
+                    amount: amount_slice,
+                    display_amount,
+                    amount_reserve,
+                    amount_slices_filled: Decimal::zero(),
+                    expiry_epoch,
+                    maker_fee,
+                    sequence,
+                    next_id: 0,
+                    prev_id: tail_id,
+                    amount_claimed: Decimal::zero(),
+                    expired_amount_filled: None,
+                    owner,
+                    is_self_trade_canceled: false,
+                    is_skipped: false,
+                    trigger: None,
+                    trigger_amounts: None,
+                },
+            );
+
+            // Emit limit order event
+            Runtime::emit_event(LimitOrderEvent {
+                order_id: NonFungibleLocalId::integer(order_id),
+                is_ask: true,
+                price,
+                amount: amount_x,
+                is_post_only,
+                is_repriced,
+                expiry_epoch,
+                display_amount,
+            });
+
+            // Return order receipt
+            order_receipt
+        }
+
+        /// Helper method to place a bid limit order into the order book.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Price is truncated to 5 significant figures.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_y` - Tokens y used to place the limit order.
+        /// * `price` - Price at which to place limit order.
+        /// * `is_post_only` - Whether the limit order was placed as post only.
+        /// * `is_repriced` - Whether the limit order price was moved to not cross the order book.
+        /// * `expiry_epoch` - Optional epoch after which the limit order expires.
+        /// * `display_amount` - Optional amount calculated in tokens x shown in the limit at a time for an iceberg order.
+        /// * `owner` - Optional global id of the badge identifying the owner of the order.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains an order receipt.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tokens_y` - Must be tokens y.
+        /// * `tokens_y` - Must not be empty.
+        /// * `price` - Must be less than current ask price.
+        /// 
+        /// # Events
+        /// 
+        /// * `LimitOrderEvent` - Event emitted when limit order is placed.
+        /// 
+        fn limit_order_y_to_x(
+            &mut self, 
+            tokens_y: Bucket, 
+            price: Price, 
+            is_post_only: bool, 
+            is_repriced: bool, 
+            expiry_epoch: Option<u64>,
+            display_amount: Option<Decimal>,
+            owner: Option<NonFungibleGlobalId>,
+        ) -> Bucket {
+            // Deposit tokens
+            let amount_y: Decimal = tokens_y.amount();
+            let price_dec: Decimal = price.into();
+            let amount_x: Decimal = amount_y / price_dec;
+            self.tokens_y.put(tokens_y);
+
+            // Get order nonce to make order receipt id
+            let order_id: u64 = self.nonce;
+            self.nonce += 1;
+
+            // Split iceberg order into shown slice and hidden reserve
+            let amount_slice: Decimal = display_amount.map_or(amount_x, |display_amount| display_amount.min(amount_x));
+            let amount_reserve: Decimal = amount_x - amount_slice;
+
+            // Add order to limit
+            let maker_fee: Decimal = Self::get_maker_fee();
+            let (sequence, tail_id): (u64, u64) = self.add_to_limit(order_id, price, false, amount_slice, amount_reserve, expiry_epoch, maker_fee);
+
+            // Mint new order receipt
+            let price = Decimal::from(price);
+            let order_receipt: Bucket = self.order_receipt_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(order_id),
+                OrderReceipt {
+                    is_ask: false,
+                    price,
+                    amount: amount_slice,
+                    display_amount,
+                    amount_reserve,
+                    amount_slices_filled: Decimal::zero(),
+                    expiry_epoch,
+                    maker_fee,
+                    sequence,
+                    next_id: 0,
+                    prev_id: tail_id,
+                    amount_claimed: Decimal::zero(),
+                    expired_amount_filled: None,
+                    owner,
+                    is_self_trade_canceled: false,
+                    is_skipped: false,
+                    trigger: None,
+                    trigger_amounts: None,
+                },
+            );
+
+            // Emit limit order event
+            Runtime::emit_event(LimitOrderEvent {
+                order_id: NonFungibleLocalId::integer(order_id),
+                is_ask: false,
+                price,
+                amount: amount_x,
+                is_post_only,
+                is_repriced,
+                expiry_epoch,
+                display_amount,
+            });
+
+            // Return order receipt
+            order_receipt
+        }
+
+        /// Helper method to add an order to the end of the limit at a price.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_id` - Id of order.
+        /// * `price` - Price of the limit.
+        /// * `is_ask` - Whether the order is an ask or bid.
+        /// * `amount_x` - Amount of the order calculated in tokens x.
+        /// * `amount_x_reserve` - Amount of the order calculated in tokens x held in a hidden reserve.
+        /// * `expiry_epoch` - Optional epoch after which the order expires.
+        /// * `maker_fee` - Maker fee of the order as ratio. Negative for a rebate.
+        /// 
+        /// # Returns
+        /// 
+        /// * `u64` - Sequence of the order.
+        /// * `u64` - Id of the order that was previously the last order in the limit.
+        /// 
+        /// # Requires
+        /// 
+        /// * `order_id` - Must not be in any limit.
+        /// 
+        fn add_to_limit(
+            &mut self, 
+            order_id: u64, 
+            price: Price, 
+            is_ask: bool, 
+            amount_x: Decimal, 
+            amount_x_reserve: Decimal, 
+            expiry_epoch: Option<u64>,
+            maker_fee: Decimal,
+        ) -> (u64, u64) {
+            // Get order sequence
+            let sequence: u64 = self.sequence;
+            self.sequence += 1;
+
+            // Get limit
+            let limit: Option<Limit> = if is_ask {
+                self.ask_limit_map.get(&price).map(|limit| *limit)
+            } else {
+                self.bid_limit_map.get(&price).map(|limit| *limit)
+            };
+            let mut limit: Limit = match limit {
+                Some(limit) => {
+                    // If empty limit add price to price tree
+                    if limit.is_empty() {
+                        self.price_index.insert(price, is_ask);
+                    }
+
+                    // Return limit
+                    limit
+                },
+                None => {
+                    // Add price to price tree
+                    self.price_index.insert(price, is_ask);
+
+                    // Return new limit
+                    Limit::new()
+                }
+            };
+
+            // Add order to limit
+            let tail_id: u64 = limit.add_order(order_id, sequence, &amount_x, &amount_x_reserve, expiry_epoch, maker_fee);
+            if is_ask {
+                self.ask_limit_map.insert(price, limit);
+            } else {
+                self.bid_limit_map.insert(price, limit);
+            }
+
+            // Update tail order receipt
+            if tail_id != 0 {
+                self.order_receipt_manager.update_non_fungible_data(&NonFungibleLocalId::integer(tail_id), "next_id", order_id);
+            }
+
+            (sequence, tail_id)
+        }
+
+        /// Helper method to add the next slices of filled iceberg orders to the end of their limit 
+        /// from their hidden reserves.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `price` - Price of the limit.
+        /// * `is_ask` - Whether the orders are asks or bids.
+        /// * `refills` - Ids of filled iceberg orders and the amounts calculated in tokens x taken from their hidden reserves.
+        /// 
+        /// # Requires
+        /// 
+        /// * `refills` - Amounts must have been taken from the hidden reserves of the limit.
+        /// 
+        fn refill_orders(&mut self, price: Price, is_ask: bool, refills: Vec<(u64, Decimal)>) {
+            for (order_id, amount_x) in refills {
+                // Get order
+                let order_receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(order_id);
+                let order_data: OrderReceipt = self.order_receipt_manager.get_non_fungible_data(&order_receipt_id);
+
+                // Remove filled slice from order chain
+                self.remove_from_order_chain(&order_data);
+
+                // Add next slice to end of limit
+                let (sequence, tail_id): (u64, u64) = self.add_to_limit(order_id, price, is_ask, amount_x, Decimal::zero(), order_data.expiry_epoch, order_data.maker_fee);
+
+                // Update order receipt
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount_slices_filled", order_data.amount_slices_filled + order_data.amount);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount", amount_x);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount_reserve", order_data.amount_reserve - amount_x);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "sequence", sequence);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "next_id", 0u64);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "prev_id", tail_id);
+            }
+        }
+
+        /// Helper method to apply a post only mode to the price of a limit order.
+        /// An ask crosses the order book if its price is at or below the current bid price.
+        /// A bid crosses the order book if its price is at or above the current ask price.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `price` - Price of the limit order.
+        /// * `is_ask` - Whether the limit order is an ask or bid.
+        /// * `post_only` - Optional post only mode.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Price` - Price at which to place the limit order.
+        /// * `bool` - Whether the price was moved to not cross the order book.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `post_only` is `Reject` and the order would cross the order book.
+        /// * If `post_only` is `Reprice` and there is no price that does not cross the order book.
+        /// 
+        fn apply_post_only(&self, price: Price, is_ask: bool, post_only: Option<PostOnlyMode>) -> (Price, bool) {
+            // Return if not post only
+            let post_only: PostOnlyMode = match post_only {
+                Some(post_only) => post_only,
+                None => return (price, false),
+            };
+
+            // Get closest price that does not cross the order book
+            let closest_price: Option<Price> = if is_ask {
+                match self.price_index.current_bid() {
+                    Some(current_bid) if price <= current_bid => current_bid.increment_with_precision(self.price_precision),
+                    _ => return (price, false),
+                }
+            } else {
+                match self.price_index.current_ask() {
+                    Some(current_ask) if price >= current_ask => current_ask.decrement_with_precision(self.price_precision),
+                    _ => return (price, false),
+                }
+            };
+
+            // Reject or reprice order
+            match post_only {
+                PostOnlyMode::Reject => panic!("Post only order would cross the order book."),
+                PostOnlyMode::Reprice => (closest_price.expect("No price available for post only order."), true),
+            }
+        }
+
+        /// Helper method to emit an event for a limit filled by a market order.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `is_buy` - Whether the market order is buying into the ask or selling into bid side of the order book.
+        /// * `price` - Price of the limit.
+        /// * `amount_x` - Amount filled calculated in tokens x.
+        /// * `first_order_id` - Id of the first order in the limit reached by the fill.
+        /// * `last_order_id` - Id of the last order in the limit reached by the fill.
+        /// 
+        /// # Events
+        /// 
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
+        /// 
+        fn emit_fill_event(is_buy: bool, price: Decimal, amount_x: Decimal, first_order_id: u64, last_order_id: u64) {
+            Runtime::emit_event(FillEvent {
+                is_buy,
+                price,
+                amount_x,
+                amount_y: amount_x * price,
+                first_order_id: NonFungibleLocalId::integer(first_order_id),
+                last_order_id: NonFungibleLocalId::integer(last_order_id),
+            });
+        }
+
+        /// Helper method to add fills to the candle of the current epoch. 
+        /// The first fill of the order book also records the first price observation.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `fills` - Vector of fills in format (price, amount_x).
+        /// 
+        fn update_candle(&mut self, fills: &[(Decimal, Decimal)]) {
+            // Return if nothing was filled
+            if fills.is_empty() {
+                return;
+            }
+
+            // Get candle for current epoch or create a new one
+            let epoch: u64 = Runtime::current_epoch().number();
+            let candle: Option<Candle> = self.candles.get(&epoch).map(|candle| *candle);
+            let mut candle: Candle = match candle {
+                Some(candle) => candle,
+                None => Candle::new(fills[0].0, self.last_candle_epoch),
+            };
+
+            // Add fills to candle
+            for (price, amount_x) in fills {
+                candle.add_fill(*price, *amount_x);
+            }
+            self.candles.insert(epoch, candle);
+            self.last_candle_epoch = Some(epoch);
+
+            // Record transaction with fills
+            self.has_traded = true;
+            self.last_fill_transaction = Some(Runtime::transaction_hash());
+
+            // Record first price observation
+            if self.observation_count == 0 {
+                self.observations.insert(0, Observation {
+                    timestamp: Clock::current_time_rounded_to_minutes().seconds_since_unix_epoch,
+                    price_cumulative: Decimal::zero(),
+                });
+                self.observation_count = 1;
+            }
+        }
+
+        /// Helper method to accumulate the last price held since the latest price observation.
+        /// Called before every fill, so the price accumulator is recorded on the first fill of each minute.
+        /// 
+        fn update_observations(&mut self) {
+            // Skip if the order book has never been filled
+            if self.observation_count == 0 {
+                return;
+            }
+
+            // Skip if there is already an observation for this minute
+            let now: i64 = Clock::current_time_rounded_to_minutes().seconds_since_unix_epoch;
+            let latest: Observation = *self.observations.get(&self.observation_index).unwrap();
+            if latest.timestamp >= now {
+                return;
+            }
+
+            // Record observation, overwriting the oldest once the ring buffer is full
+            let last_price: Decimal = self.last_price.into();
+            self.observation_index = (self.observation_index + 1) % MAX_OBSERVATIONS;
+            self.observations.insert(self.observation_index, Observation {
+                timestamp: now,
+                price_cumulative: latest.price_cumulative + last_price * Decimal::from(now - latest.timestamp),
+            });
+            self.observation_count = (self.observation_count + 1).min(MAX_OBSERVATIONS);
+        }
+
+        /// Helper method to keep the last price from before the current transaction.
+        /// Called before every fill, so the price is kept before the first fill of each transaction.
+        /// 
+        fn update_prev_transaction_price(&mut self) {
+            if self.last_fill_transaction != Some(Runtime::transaction_hash()) {
+                self.prev_transaction_price = if self.has_traded { Some(self.last_price) } else { None };
+            }
+        }
+
+        /// Helper method to get the price trigger orders are checked against.
+        /// Uses the last price before the current transaction if limits have been filled in the current transaction.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Option<Price>` - Price to check trigger orders against. None if the order book has not been filled before.
+        /// 
+        fn get_trigger_reference_price(&self) -> Option<Price> {
+            if self.last_fill_transaction == Some(Runtime::transaction_hash()) {
+                self.prev_transaction_price
+            } else if self.has_traded {
+                Some(self.last_price)
+            } else {
+                None
+            }
+        }
+
+        /// Helper method to get the weight of each level of a limit order ladder.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `levels` - Number of levels in the ladder.
+        /// * `distribution` - Distribution of tokens across the levels.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Vec<Decimal>` - Weight of each level ordered from the start price.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `distribution` is `Geometric` and the ratio is not greater than zero.
+        /// 
+        fn get_ladder_weights(levels: u32, distribution: LadderDistribution) -> Vec<Decimal> {
+            match distribution {
+                LadderDistribution::Flat => (0..levels).map(|_| Decimal::one()).collect(),
+                LadderDistribution::Linear => (1..=levels).map(|level| Decimal::from(level)).collect(),
+                LadderDistribution::Geometric(ratio) => {
+                    assert!(ratio > Decimal::zero(), "Geometric ratio must be greater than zero.");
+                    let mut weight: Decimal = Decimal::one();
+                    let mut weights: Vec<Decimal> = Vec::new();
+                    for _ in 0..levels {
+                        weights.push(weight);
+                        weight = weight * ratio;
+                    }
+                    weights
+                },
+            }
+        }
+
+        /// Helper method to aggregate the unexpired amounts of the limits of one side of the order book into price buckets.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `is_ask` - Whether to aggregate ask or bid limits.
+        /// * `levels` - Maximum number of buckets to return.
+        /// * `bucket_size` - Optional price width of each bucket. If none, each limit price is its own bucket.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Vec<DepthLevel>` - Buckets starting from the best price.
+        /// 
+        fn get_depth_levels(&self, is_ask: bool, levels: u32, bucket_size: Option<Decimal>) -> Vec<DepthLevel> {
+            let epoch: u64 = Runtime::current_epoch().number();
+            let mut depth: Vec<DepthLevel> = Vec::new();
+            let mut cumulative_x: Decimal = Decimal::zero();
+            let mut cumulative_y: Decimal = Decimal::zero();
+
+            // Loop through limits starting from the best price
+            let mut next: Option<Price> = if is_ask {
+                self.price_index.current_ask()
+            } else {
+                self.price_index.current_bid()
+            };
+            while let Some(price) = next {
+                // Get amount of limit not expired and skip limit if none remains
+                let amount_x: Decimal = self.get_amount_x_unexpired(price, is_ask, epoch);
+                next = if is_ask {
+                    self.price_index.next_up(price)
+                } else {
+                    self.price_index.next_down(price)
+                };
+                if amount_x == Decimal::zero() {
+                    continue;
+                }
+
+                // Get bucket price, rounding away from the other side of the order book
+                let price_dec: Decimal = Decimal::from(price);
+                let bucket_price: Decimal = match bucket_size {
+                    Some(bucket_size) => {
+                        let buckets: Decimal = price_dec / bucket_size;
+                        let buckets: Decimal = if is_ask {
+                            buckets.checked_ceiling().unwrap()
+                        } else {
+                            buckets.checked_floor().unwrap()
+                        };
+                        buckets * bucket_size
+                    },
+                    None => price_dec,
+                };
+
+                // Stop if a new bucket would exceed the number of levels
+                let is_new_level: bool = depth.last().map_or(true, |level| level.price != bucket_price);
+                if is_new_level && depth.len() as u32 >= levels {
+                    break;
+                }
+
+                // Get limit amounts
+                let amount_y: Decimal = amount_x * price_dec;
+                cumulative_x += amount_x;
+                cumulative_y += amount_y;
+
+                // Add limit to bucket
+                if is_new_level {
+                    depth.push(DepthLevel {
+                        price: bucket_price,
+                        amount_x,
+                        amount_y,
+                        cumulative_x,
+                        cumulative_y,
+                    });
+                } else {
+                    let level: &mut DepthLevel = depth.last_mut().unwrap();
+                    level.amount_x += amount_x;
+                    level.amount_y += amount_y;
+                    level.cumulative_x = cumulative_x;
+                    level.cumulative_y = cumulative_y;
+                }
+            }
+
+            depth
+        }
+
+        /// Helper method to get the price accumulator at a time.
+        /// Times after the latest observation use the last price, and times between observations are interpolated.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `timestamp` - Time in seconds since the unix epoch.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Sum of the last price multiplied by the seconds it was held for, up to the time.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the time is before the oldest price observation.
+        /// 
+        /// # Requires
+        /// 
+        /// * There is at least one price observation.
+        /// 
+        fn price_cumulative_at(&self, timestamp: i64) -> Decimal {
+            // Extend latest observation with the last price
+            let latest: Observation = *self.observations.get(&self.observation_index).unwrap();
+            if timestamp >= latest.timestamp {
+                let last_price: Decimal = self.last_price.into();
+                return latest.price_cumulative + last_price * Decimal::from(timestamp - latest.timestamp);
+            }
+
+            // Check time is not before the oldest observation
+            let oldest_index: u32 = if self.observation_count < MAX_OBSERVATIONS {
+                0
+            } else {
+                (self.observation_index + 1) % MAX_OBSERVATIONS
+            };
+            let get_observation = |position: u32| *self.observations.get(&((oldest_index + position) % MAX_OBSERVATIONS)).unwrap();
+            let oldest: Observation = get_observation(0);
+            assert!(timestamp >= oldest.timestamp, "No price history for time {}.", timestamp);
+
+            // Binary search for the observations around the time
+            let mut low: u32 = 0;
+            let mut high: u32 = self.observation_count - 1;
+            while high - low > 1 {
+                let mid: u32 = (low + high) / 2;
+                if get_observation(mid).timestamp <= timestamp {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            let before: Observation = get_observation(low);
+            let after: Observation = get_observation(high);
+
+            // Interpolate between observations, the last price is held between them
+            before.price_cumulative + (after.price_cumulative - before.price_cumulative) 
+                * Decimal::from(timestamp - before.timestamp) 
+                / Decimal::from(after.timestamp - before.timestamp)
+        }
+
+        /// Helper method to check the order book is active.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the order book is not active.
+        /// 
+        fn check_active(&self) {
+            assert!(self.state == OrderBookState::Active, "Order book is not active.");
+        }
+
+        /// Helper method to check the order book is not halted.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the order book is halted.
+        /// 
+        fn check_not_halted(&self) {
+            assert!(self.state != OrderBookState::Halted, "Order book is halted.");
+        }
+
+        /// Helper method to check the size of a limit order against the lot size and minimum order size.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_x` - Amount of the order calculated in tokens x.
+        /// * `price` - Price of the order.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `amount_x` is less than the lot size.
+        /// * If the order value is less than the minimum order size.
+        /// 
+        fn check_order_size(&self, amount_x: Decimal, price: Decimal) {
+            assert!(
+                self.lot_size.is_none() || self.round_to_lot_size(amount_x) > Decimal::zero(), 
+                "Order size must be at least the lot size."
+            );
+            assert!(amount_x * price >= self.min_order_size, "Order size must be at least the minimum order size.");
+        }
+
+        /// Helper method to get the amount of a limit order that can be placed into the order book.
+        /// The amount is rounded down to the lot size and is zero if the order value is less than the minimum order size.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_x` - Amount of the order calculated in tokens x.
+        /// * `price` - Price of the order.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Amount of the order that can be placed calculated in tokens x.
+        /// 
+        fn get_amount_x_placeable(&self, amount_x: Decimal, price: Decimal) -> Decimal {
+            let amount_x: Decimal = self.round_to_lot_size(amount_x);
+            if amount_x * price < self.min_order_size {
+                Decimal::zero()
+            } else {
+                amount_x
+            }
+        }
+
+        /// Helper method to round an amount down to a multiple of the lot size.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_x` - Amount calculated in tokens x.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Amount rounded down to the lot size calculated in tokens x. Unchanged if there is no lot size.
+        /// 
+        fn round_to_lot_size(&self, amount_x: Decimal) -> Decimal {
+            match self.lot_size {
+                Some(lot_size) => (amount_x / lot_size).checked_floor().unwrap() * lot_size,
+                None => amount_x,
+            }
+        }
+
+        /// Helper method to execute a market order on ask limits in the order book.
+        /// A fee is taken from the input tokens.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_y` - Tokens y used to execute the market order.
+        /// * `stop_price` - Stop price at which to stop executing market order.
+        /// * `mode` - Execution mode of the market order.
+        /// * `is_limit_order` - Whether the market order is the crossing part of a limit order.
+        /// * `self_trade` - Optional owner and self trade prevention mode applied to limit orders of the same owner.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains any tokens x bought.
+        /// * `Bucket` - Contains any tokens y remaining.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tokens_y` - Must be tokens y.
+        /// * `tokens_y` - Must not be empty.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `mode` is `ImmediateOrCancel` and the amount of tokens bought is less than the minimum.
+        /// * If `mode` is `FillOrKill` and the market order is not fully filled.
+        /// 
+        /// # Events
+        /// 
+        /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// 
+        fn market_order_y_to_x(
+            &mut self, 
+            mut tokens_y: Bucket, 
+            stop_price: Price, 
+            mode: MarketOrderMode, 
+            is_limit_order: bool,
+            self_trade: Option<(NonFungibleGlobalId, SelfTradeMode)>,
+        ) -> (Bucket, Bucket) {
+            // Accumulate last price before it is moved by any fills
+            self.update_observations();
+            self.update_prev_transaction_price();
+
+            // Take fee
+            let (_, protocol_fee): (Decimal, Decimal) = FEE_CONTROLLER.get_maker_taker_fees(Runtime::package_address());
+            let mut tokens_fee: Bucket = tokens_y.take_advanced(tokens_y.amount() * protocol_fee, INCOMING);
+            let amount_y_input: Decimal = tokens_y.amount();
+
+            // Initialize amounts
+            let mut amount_x_bought: Decimal = Decimal::zero();
+            let mut amount_y_order: Decimal = tokens_y.amount();
+            let mut amount_y_rebate: Decimal = Decimal::zero();
+            let mut fills: Vec<(Decimal, Decimal)> = Vec::new();
+            let mut skipped: Vec<(u64, Price)> = Vec::new();
+
+            // Loop through limits
+            while let Some(price) = self.price_index.current_ask() {
+                // Decompress price
+                let price_dec: Decimal = price.into();
+
+                // Check if price is past stop price
+                if price > stop_price {
+                    break;
+                }
+
+                // Remove expired orders and skip limit if none remain
+                if self.remove_expired_orders(price, true) {
+                    continue;
+                }
+
+                // Apply self trade prevention if the market order reaches an order of the same owner
+                if let Some((owner, self_trade_mode)) = &self_trade {
+                    if let Some((order_id, amount_x_before)) = self.find_self_trade(price, true, owner, amount_y_order / price_dec) {
+                        // Fill orders ahead of the order of the same owner
+                        if amount_x_before > Decimal::zero() {
+                            self.last_price = price;
+                            let mut limit: Limit = *self.ask_limit_map.get(&price).unwrap();
+                            let first_order_id: u64 = limit.get_head_id();
+                            let (refills, rebate_x): (Vec<(u64, Decimal)>, Decimal) = limit.fill(&amount_x_before, &self.order_receipt_manager);
+                            amount_y_rebate += rebate_x * price_dec;
+                            self.ask_limit_map.insert(price, limit);
+                            fills.push((price_dec, amount_x_before));
+                            let last_order_id: u64 = limit.get_last_filled_id(&self.order_receipt_manager);
+                            Self::emit_fill_event(true, price_dec, amount_x_before, first_order_id, last_order_id);
+                            amount_x_bought += amount_x_before;
+                            amount_y_order = (amount_y_order - amount_x_before * price_dec).max(Decimal::zero());
+
+                            self.refill_orders(price, true, refills);
+                        }
+
+                        // Apply self trade prevention mode
+                        match self_trade_mode {
+                            SelfTradeMode::CancelResting => self.cancel_self_trade_order(order_id, price, true),
+                            SelfTradeMode::CancelIncoming => break,
+                            SelfTradeMode::SkipMaker => {
+                                self.skip_self_trade_order(order_id, price, true);
+                                skipped.push((order_id, price));
+                            },
+                        }
+                        continue;
+                    }
+                }
+
+                // Update last traded price
+                self.last_price = price;
+
+                // Get limit
+                let mut limit: Limit = *self.ask_limit_map.get(&price).unwrap();
+
+                // Calculate amounts
+                let amount_x_limit: Decimal = limit.get_amount_x();
+                let amount_y_limit: Decimal = amount_x_limit * price_dec;
+
+                // If order amount is greater or equal to the limit amount fill limit and continue,
+                // else partly fill limit and break
+                if amount_y_order >= amount_y_limit {
+                    let (first_order_id, last_order_id): (u64, u64) = (limit.get_head_id(), limit.get_tail_id());
+                    let (refills, rebate_x): (Vec<(u64, Decimal)>, Decimal) = limit.fully_fill(&self.order_receipt_manager);
+                    amount_y_rebate += rebate_x * price_dec;
+                    self.ask_limit_map.insert(price, limit);
+                    fills.push((price_dec, amount_x_limit));
+                    Self::emit_fill_event(true, price_dec, amount_x_limit, first_order_id, last_order_id);
+                    amount_x_bought += amount_x_limit;
+                    amount_y_order -= amount_y_limit;
+
+                    self.price_index.remove(price);
+                    self.refill_orders(price, true, refills);
+                } else {
+                    let amount_x_order: Decimal = amount_y_order / price_dec;
+                    let first_order_id: u64 = limit.get_head_id();
+                    let (refills, rebate_x): (Vec<(u64, Decimal)>, Decimal) = limit.fill(&amount_x_order, &self.order_receipt_manager);
+                    amount_y_rebate += rebate_x * price_dec;
+                    self.ask_limit_map.insert(price, limit);
+                    fills.push((price_dec, amount_x_order));
+                    let last_order_id: u64 = limit.get_last_filled_id(&self.order_receipt_manager);
+                    Self::emit_fill_event(true, price_dec, amount_x_order, first_order_id, last_order_id);
+                    amount_x_bought += amount_x_order;
+                    amount_y_order = Decimal::zero();
+
+                    self.refill_orders(price, true, refills);
+                    break;
+                }
+            }
+
+            // Put skipped orders back at the front of their limits
+            self.restore_skipped_orders(skipped, true);
+
+            // Deposit and withdraw tokens
+            let tokens_x: Bucket = self.tokens_x.take_advanced(amount_x_bought, OUTGOING);
+            self.tokens_y.put(tokens_y.take_advanced(tokens_y.amount() - amount_y_order, INCOMING));
+
+            // Check execution mode
+            Self::check_market_order_mode(mode, amount_y_order, tokens_x.amount());
+            
+            // If fee amount is not zero
+            if !tokens_fee.is_empty() {
+                // Return fee if tokens not used
+                if tokens_y.amount() != Decimal::zero() {
+                    let amount_unused: Decimal = tokens_y.amount() / amount_y_input * tokens_fee.amount();
+                    tokens_y.put(tokens_fee.take_advanced(amount_unused, OUTGOING));
+                }
+
+                // Keep maker rebates of the orders filled
+                let amount_taker_fee: Decimal = tokens_fee.amount();
+                let tokens_rebate: Bucket = tokens_fee.take_advanced(amount_y_rebate.min(amount_taker_fee), OUTGOING);
+                let amount_maker_rebate: Decimal = tokens_rebate.amount();
+                self.rebates_y.put(tokens_rebate);
+
+                // Emit fee event
+                Runtime::emit_event(ProtocolFeeEvent {
+                    token_address: self.tokens_y.resource_address(),
+                    amount: tokens_fee.amount(),
+                    taker_fee: amount_taker_fee,
+                    maker_fee: Decimal::zero(),
+                    maker_rebate: amount_maker_rebate,
+                });
+
+                // Deposit fee
+                if !tokens_fee.is_empty() {
+                    FEE_VAULTS.deposit(tokens_fee);
+                } else {
+                    tokens_fee.drop_empty();
+                }
+            } else {
+                tokens_fee.drop_empty();
+            }
+
+            // Update candle
+            self.update_candle(&fills);
+
+            // Emit market order event
+            Runtime::emit_event(MarketOrderEvent {
+                is_buy: true,
+                fills,
+                mode,
+                is_limit_order,
+            });
+
+            // Return tokens
+            (tokens_x, tokens_y)
+        }
+
+        /// Helper method to execute a market order on bid limits in the order book.
+        /// A fee is taken from the input tokens and the tokens left are rounded down to the lot size.
+        /// Price is calculated as `tokens_y / tokens_x`.
+        /// Buys tokens_y for tokens_x from limit orders until order is filled or stop_price is reached.
+        /// Returns (tokens_y, tokens_x).
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_x` - Tokens x used to execute the market order.
+        /// * `stop_price` - Stop price at which to stop executing market order.
+        /// * `mode` - Execution mode of the market order.
+        /// * `is_limit_order` - Whether the market order is the crossing part of a limit order.
+        /// * `self_trade` - Optional owner and self trade prevention mode applied to limit orders of the same owner.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains any tokens y bought.
+        /// * `Bucket` - Contains any tokens x remaining.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tokens_x` - Must be tokens x.
+        /// * `tokens_x` - Must not be empty.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `mode` is `ImmediateOrCancel` and the amount of tokens bought is less than the minimum.
+        /// * If `mode` is `FillOrKill` and the market order is not fully filled.
+        /// 
+        /// # Events
+        /// 
+        /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// 
+        fn market_order_x_to_y(
+            &mut self, 
+            mut tokens_x: Bucket, 
+            stop_price: Price, 
+            mode: MarketOrderMode, 
+            is_limit_order: bool,
+            self_trade: Option<(NonFungibleGlobalId, SelfTradeMode)>,
+        ) -> (Bucket, Bucket) {
+            // Accumulate last price before it is moved by any fills
+            self.update_observations();
+            self.update_prev_transaction_price();
+
+            // Take fee
+            let (_, protocol_fee): (Decimal, Decimal) = FEE_CONTROLLER.get_maker_taker_fees(Runtime::package_address());
+            let mut tokens_fee: Bucket = tokens_x.take_advanced(tokens_x.amount() * protocol_fee, INCOMING);
+            let amount_x_input: Decimal = tokens_x.amount();
+
+            // Initialize amounts, rounding tokens x left after the fee down to the lot size
+            let mut amount_y_bought: Decimal = Decimal::zero();
+            let amount_x_lots: Decimal = self.round_to_lot_size(tokens_x.amount());
+            let mut amount_x_order: Decimal = amount_x_lots;
+            let mut amount_x_rebate: Decimal = Decimal::zero();
+            let mut fills: Vec<(Decimal, Decimal)> = Vec::new();
+            let mut skipped: Vec<(u64, Price)> = Vec::new();
+
+            // Loop through limits
+            while let Some(price) = self.price_index.current_bid() {
+                // Decompress price
+                let price_dec: Decimal = price.into();
+
+                // Check stop price and if any whole lots are left
+                if price < stop_price || amount_x_order == Decimal::zero() {
+                    break;
+                }
+
+                // Remove expired orders and skip limit if none remain
+                if self.remove_expired_orders(price, false) {
+                    continue;
+                }
+
+                // Apply self trade prevention if the market order reaches an order of the same owner
+                if let Some((owner, self_trade_mode)) = &self_trade {
+                    if let Some((order_id, amount_x_before)) = self.find_self_trade(price, false, owner, amount_x_order) {
+                        // Fill orders ahead of the order of the same owner
+                        if amount_x_before > Decimal::zero() {
+                            self.last_price = price;
+                            let mut limit: Limit = *self.bid_limit_map.get(&price).unwrap();
+                            let first_order_id: u64 = limit.get_head_id();
+                            let (refills, rebate_x): (Vec<(u64, Decimal)>, Decimal) = limit.fill(&amount_x_before, &self.order_receipt_manager);
+                            amount_x_rebate += rebate_x;
+                            self.bid_limit_map.insert(price, limit);
+                            fills.push((price_dec, amount_x_before));
+                            let last_order_id: u64 = limit.get_last_filled_id(&self.order_receipt_manager);
+                            Self::emit_fill_event(false, price_dec, amount_x_before, first_order_id, last_order_id);
+                            amount_y_bought += amount_x_before * price_dec;
+                            amount_x_order -= amount_x_before;
+
+                            self.refill_orders(price, false, refills);
+                        }
+
+                        // Apply self trade prevention mode
+                        match self_trade_mode {
+                            SelfTradeMode::CancelResting => self.cancel_self_trade_order(order_id, price, false),
+                            SelfTradeMode::CancelIncoming => break,
+                            SelfTradeMode::SkipMaker => {
+                                self.skip_self_trade_order(order_id, price, false);
+                                skipped.push((order_id, price));
+                            },
+                        }
+                        continue;
+                    }
+                }
+
+                // Update last traded price
+                self.last_price = price;
+
+                // Get limit
+                let mut limit: Limit = *self.bid_limit_map.get(&price).unwrap();
+
+                // Calculate amounts
+                let amount_x_limit: Decimal = limit.get_amount_x();
+
+                // If order amount is greater or equal to the limit amount fill limit and continue,
+                // else partly fill limit and break
+                if amount_x_order >= amount_x_limit {
+                    let (first_order_id, last_order_id): (u64, u64) = (limit.get_head_id(), limit.get_tail_id());
+                    let (refills, rebate_x): (Vec<(u64, Decimal)>, Decimal) = limit.fully_fill(&self.order_receipt_manager);
+                    amount_x_rebate += rebate_x;
+                    self.bid_limit_map.insert(price, limit);
+                    fills.push((price_dec, amount_x_limit));
+                    Self::emit_fill_event(false, price_dec, amount_x_limit, first_order_id, last_order_id);
+                    let amount_y_limit: Decimal = amount_x_limit * price_dec;
+                    amount_y_bought += amount_y_limit;
+                    amount_x_order -= amount_x_limit;
+
+                    self.price_index.remove(price);
+                    self.refill_orders(price, false, refills);
+                } else {
+                    let first_order_id: u64 = limit.get_head_id();
+                    let (refills, rebate_x): (Vec<(u64, Decimal)>, Decimal) = limit.fill(&amount_x_order, &self.order_receipt_manager);
+                    amount_x_rebate += rebate_x;
+                    self.bid_limit_map.insert(price, limit);
+                    fills.push((price_dec, amount_x_order));
+                    let last_order_id: u64 = limit.get_last_filled_id(&self.order_receipt_manager);
+                    Self::emit_fill_event(false, price_dec, amount_x_order, first_order_id, last_order_id);
+                    let amount_y_order: Decimal = amount_x_order * price_dec;
+                    amount_y_bought += amount_y_order;
+                    amount_x_order = Decimal::zero();
+
+                    self.refill_orders(price, false, refills);
+                    break;
+                }
+            }
+
+            // Put skipped orders back at the front of their limits
+            self.restore_skipped_orders(skipped, false);
+
+            // Deposit and withdraw tokens
+            let tokens_y: Bucket = self.tokens_y.take_advanced(amount_y_bought, OUTGOING);
+            self.tokens_x.put(tokens_x.take_advanced(amount_x_lots - amount_x_order, INCOMING));
+
+            // Check execution mode
+            Self::check_market_order_mode(mode, amount_x_order, tokens_y.amount());
+
+            // If fee amount is not zero
+            if !tokens_fee.is_empty() {
+                // Return fee if tokens not used
+                if tokens_x.amount() != Decimal::zero() {
+                    let amount_unused: Decimal = tokens_x.amount() / amount_x_input * tokens_fee.amount();
+                    tokens_x.put(tokens_fee.take_advanced(amount_unused, OUTGOING));
+                }
+
+                // Keep maker rebates of the orders filled
+                let amount_taker_fee: Decimal = tokens_fee.amount();
+                let tokens_rebate: Bucket = tokens_fee.take_advanced(amount_x_rebate.min(amount_taker_fee), OUTGOING);
+                let amount_maker_rebate: Decimal = tokens_rebate.amount();
+                self.rebates_x.put(tokens_rebate);
 
                 // Emit fee event
                 Runtime::emit_event(ProtocolFeeEvent {
-                    token_address: self.tokens_y.resource_address(),
+                    token_address: self.tokens_x.resource_address(),
                     amount: tokens_fee.amount(),
+                    taker_fee: amount_taker_fee,
+                    maker_fee: Decimal::zero(),
+                    maker_rebate: amount_maker_rebate,
                 });
 
-                // Deposit fee
-                FEE_VAULTS.deposit(tokens_fee);
+                // Deposit fee
+                if !tokens_fee.is_empty() {
+                    FEE_VAULTS.deposit(tokens_fee);
+                } else {
+                    tokens_fee.drop_empty();
+                }
+            } else {
+                tokens_fee.drop_empty();
+            }
+
+            // Update candle
+            self.update_candle(&fills);
+
+            // Emit market order event
+            Runtime::emit_event(MarketOrderEvent {
+                is_buy: false,
+                fills,
+                mode,
+                is_limit_order,
+            });
+
+            // Return tokens
+            (tokens_y, tokens_x)
+        }
+
+        /// Helper method to quote a market order on ask limits in the order book.
+        /// Follows the same steps as `market_order_y_to_x` without changing any state.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_y` - Amount of tokens y used to execute the market order.
+        /// * `stop_price` - Stop price at which to stop executing market order.
+        /// 
+        /// # Returns
+        /// 
+        /// * `MarketOrderQuote` - Expected outcome of the market order.
+        /// 
+        fn quote_market_order_y_to_x(&self, amount_y: Decimal, stop_price: Price) -> MarketOrderQuote {
+            // Calculate fee
+            let protocol_fee: Decimal =  FEE_CONTROLLER.get_protocol_fee(Runtime::package_address());
+            let amount_fee: Decimal = amount_y * protocol_fee;
+            let amount_y_input: Decimal = amount_y - amount_fee;
+
+            // Initialize amounts
+            let epoch: u64 = Runtime::current_epoch().number();
+            let mut amount_x_bought: Decimal = Decimal::zero();
+            let mut amount_y_order: Decimal = amount_y_input;
+            let mut worst_price: Option<Price> = None;
+
+            // Loop through limits
+            let mut next: Option<Price> = self.price_index.current_ask();
+            while let Some(price) = next {
+                // Decompress price
+                let price_dec: Decimal = price.into();
+
+                // Check if price is past stop price or order is filled
+                if price > stop_price || amount_y_order == Decimal::zero() {
+                    break;
+                }
+
+                // Get next price
+                next = self.price_index.next_up(price);
+
+                // Calculate amounts, skip limit if only expired orders remain
+                let amount_x_limit: Decimal = self.get_amount_x_unexpired(price, true, epoch);
+                if amount_x_limit == Decimal::zero() {
+                    continue;
+                }
+                let amount_y_limit: Decimal = amount_x_limit * price_dec;
+                worst_price = Some(price);
+
+                // If order amount is greater or equal to the limit amount fill limit and continue,
+                // else partly fill limit
+                if amount_y_order >= amount_y_limit {
+                    amount_x_bought += amount_x_limit;
+                    amount_y_order -= amount_y_limit;
+                } else {
+                    amount_x_bought += amount_y_order / price_dec;
+                    amount_y_order = Decimal::zero();
+                }
+            }
+
+            // Calculate unused fee
+            let amount_fee_unused: Decimal = if amount_y_input != Decimal::zero() {
+                amount_y_order / amount_y_input * amount_fee
+            } else {
+                Decimal::zero()
+            };
+
+            // Calculate average price
+            let amount_y_filled: Decimal = amount_y_input - amount_y_order;
+            let average_price: Option<Decimal> = if amount_x_bought != Decimal::zero() {
+                Some(amount_y_filled / amount_x_bought)
+            } else {
+                None
+            };
+
+            // Return quote
+            MarketOrderQuote {
+                amount_out: amount_x_bought,
+                average_price,
+                protocol_fee: amount_fee - amount_fee_unused,
+                worst_price: worst_price.map(|price| price.into()),
+                amount_remaining: amount_y_order + amount_fee_unused,
+            }
+        }
+
+        /// Helper method to quote a market order on bid limits in the order book.
+        /// Follows the same steps as `market_order_x_to_y` without changing any state.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_x` - Amount of tokens x used to execute the market order.
+        /// * `stop_price` - Stop price at which to stop executing market order.
+        /// 
+        /// # Returns
+        /// 
+        /// * `MarketOrderQuote` - Expected outcome of the market order.
+        /// 
+        fn quote_market_order_x_to_y(&self, amount_x: Decimal, stop_price: Price) -> MarketOrderQuote {
+            // Calculate fee
+            let protocol_fee: Decimal =  FEE_CONTROLLER.get_protocol_fee(Runtime::package_address());
+            let amount_fee: Decimal = amount_x * protocol_fee;
+            let amount_x_input: Decimal = amount_x - amount_fee;
+
+            // Initialize amounts, rounding tokens x left after the fee down to the lot size
+            let epoch: u64 = Runtime::current_epoch().number();
+            let mut amount_y_bought: Decimal = Decimal::zero();
+            let amount_x_lots: Decimal = self.round_to_lot_size(amount_x_input);
+            let mut amount_x_order: Decimal = amount_x_lots;
+            let mut worst_price: Option<Price> = None;
+
+            // Loop through limits
+            let mut next: Option<Price> = self.price_index.current_bid();
+            while let Some(price) = next {
+                // Decompress price
+                let price_dec: Decimal = price.into();
+
+                // Check stop price or order is filled
+                if price < stop_price || amount_x_order == Decimal::zero() {
+                    break;
+                }
+
+                // Get next price
+                next = self.price_index.next_down(price);
+
+                // Calculate amounts, skip limit if only expired orders remain
+                let amount_x_limit: Decimal = self.get_amount_x_unexpired(price, false, epoch);
+                if amount_x_limit == Decimal::zero() {
+                    continue;
+                }
+                worst_price = Some(price);
+
+                // If order amount is greater or equal to the limit amount fill limit and continue,
+                // else partly fill limit
+                if amount_x_order >= amount_x_limit {
+                    amount_y_bought += amount_x_limit * price_dec;
+                    amount_x_order -= amount_x_limit;
+                } else {
+                    amount_y_bought += amount_x_order * price_dec;
+                    amount_x_order = Decimal::zero();
+                }
+            }
+
+            // Calculate unused fee
+            let amount_x_filled: Decimal = amount_x_lots - amount_x_order;
+            let amount_x_unused: Decimal = amount_x_input - amount_x_filled;
+            let amount_fee_unused: Decimal = if amount_x_input != Decimal::zero() {
+                amount_x_unused / amount_x_input * amount_fee
+            } else {
+                Decimal::zero()
+            };
+
+            // Calculate average price
+            let average_price: Option<Decimal> = if amount_x_filled != Decimal::zero() {
+                Some(amount_y_bought / amount_x_filled)
+            } else {
+                None
+            };
+
+            // Return quote
+            MarketOrderQuote {
+                amount_out: amount_y_bought,
+                average_price,
+                protocol_fee: amount_fee - amount_fee_unused,
+                worst_price: worst_price.map(|price| price.into()),
+                amount_remaining: amount_x_unused + amount_fee_unused,
+            }
+        }
+
+        /// Helper method to check a market order against its execution mode.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `mode` - Execution mode of the market order.
+        /// * `amount_unfilled` - Amount of input tokens not executed.
+        /// * `amount_bought` - Amount of tokens bought.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `mode` is `ImmediateOrCancel` and `amount_bought` is less than the minimum.
+        /// * If `mode` is `FillOrKill` and `amount_unfilled` is not zero.
+        /// 
+        fn check_market_order_mode(mode: MarketOrderMode, amount_unfilled: Decimal, amount_bought: Decimal) {
+            match mode {
+                MarketOrderMode::ImmediateOrCancel(min_amount_out) => {
+                    assert!(amount_bought >= min_amount_out, "Market order bought less than minimum amount.");
+                },
+                MarketOrderMode::FillOrKill => {
+                    assert!(amount_unfilled == Decimal::zero(), "Market order not fully filled.");
+                },
+            }
+        }
+
+        /// Helper method to remove expired orders from a limit so they are not filled.
+        /// Tokens of removed orders stay in the order book to be claimed with the order receipt.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `price` - Price of the limit.
+        /// * `is_ask` - Whether the limit is an ask or bid limit.
+        /// 
+        /// # Returns
+        /// 
+        /// * `bool` - True if the limit is empty after removing expired orders.
+        /// 
+        /// # Requires
+        /// 
+        /// * `price` - Must be the price of an existing limit.
+        /// 
+        fn remove_expired_orders(&mut self, price: Price, is_ask: bool) -> bool {
+            // Get limit
+            let mut limit: Limit = if is_ask {
+                *self.ask_limit_map.get(&price).unwrap()
+            } else {
+                *self.bid_limit_map.get(&price).unwrap()
+            };
+
+            // Return if no orders can have expired
+            let epoch: u64 = Runtime::current_epoch().number();
+            if !limit.has_expired_orders(epoch) {
+                return false;
+            }
+
+            // Loop through orders in limit
+            let mut min_expiry_epoch: Option<u64> = None;
+            let mut next_id: u64 = limit.get_head_id();
+            while next_id != 0 {
+                // Get order
+                let order_id: u64 = next_id;
+                let order_data: OrderReceipt = self.order_receipt_manager
+                    .get_non_fungible_data(&NonFungibleLocalId::integer(order_id));
+                next_id = order_data.next_id;
+
+                match order_data.expiry_epoch {
+                    // If expired remove order from limit and chain
+                    Some(expiry_epoch) if epoch > expiry_epoch => {
+                        let (_, amount_filled) = limit.claim_order(order_id, &order_data);
+                        self.remove_from_order_chain(&order_data);
+                        self.order_receipt_manager.update_non_fungible_data(
+                            &NonFungibleLocalId::integer(order_id),
+                            "expired_amount_filled",
+                            Some(amount_filled),
+                        );
+                    },
+                    // Else track lowest expiry epoch
+                    Some(expiry_epoch) => {
+                        min_expiry_epoch = Some(min_expiry_epoch.map_or(expiry_epoch, |min| min.min(expiry_epoch)));
+                    },
+                    None => {},
+                }
+            }
+            limit.set_min_expiry_epoch(min_expiry_epoch);
+
+            // Update limit
+            let is_empty: bool = limit.is_empty();
+            if is_ask {
+                self.ask_limit_map.insert(price, limit);
+            } else {
+                self.bid_limit_map.insert(price, limit);
+            }
+
+            // If limit is empty remove from price tree
+            if is_empty {
+                self.price_index.remove(price);
+            }
+
+            is_empty
+        }
+
+        /// Helper method to get the amount of a limit that has not expired, including the hidden reserves of iceberg orders.
+        /// Amount is calculated in tokens x.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `price` - Price of the limit.
+        /// * `is_ask` - Whether the limit is an ask or bid limit.
+        /// * `epoch` - Current epoch number.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Amount of tokens calculated in tokens x available to be filled.
+        /// 
+        /// # Requires
+        /// 
+        /// * `price` - Must be the price of an existing limit.
+        /// 
+        fn get_amount_x_unexpired(&self, price: Price, is_ask: bool, epoch: u64) -> Decimal {
+            // Get limit
+            let limit: Limit = if is_ask {
+                *self.ask_limit_map.get(&price).unwrap()
+            } else {
+                *self.bid_limit_map.get(&price).unwrap()
+            };
+
+            // Return if no orders can have expired
+            if !limit.has_expired_orders(epoch) {
+                return limit.get_amount_x() + limit.get_amount_x_reserve();
+            }
+
+            // Loop through orders in limit subtracting expired amounts
+            let mut amount_x: Decimal = limit.get_amount_x() + limit.get_amount_x_reserve();
+            let mut next_id: u64 = limit.get_head_id();
+            while next_id != 0 {
+                // Get order
+                let order_id: u64 = next_id;
+                let order_data: OrderReceipt = self.order_receipt_manager
+                    .get_non_fungible_data(&NonFungibleLocalId::integer(order_id));
+                next_id = order_data.next_id;
+
+                // If expired subtract remaining amount of order
+                match order_data.expiry_epoch {
+                    Some(expiry_epoch) if epoch > expiry_epoch => {
+                        amount_x -= if order_id == limit.get_head_id() {
+                            order_data.amount - limit.get_amount_x_unallocated() + order_data.amount_reserve
+                        } else {
+                            order_data.amount + order_data.amount_reserve
+                        };
+                    },
+                    _ => {},
+                }
+            }
+
+            amount_x
+        }
+
+        /// Helper method to get the owner of an order from an optional badge proof.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `owner_proof` - Optional proof of a badge identifying the owner.
+        /// * `self_trade_mode` - Optional self trade prevention mode.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Option<NonFungibleGlobalId>` - Global id of the badge. None if no proof was provided.
+        /// * `Option<(NonFungibleGlobalId, SelfTradeMode)>` - Owner and self trade prevention mode. None if no mode was provided.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `self_trade_mode` is provided without an `owner_proof`.
+        /// * If `owner_proof` is not a proof of a single non fungible badge.
+        /// 
+        fn get_owner(
+            owner_proof: Option<Proof>, 
+            self_trade_mode: Option<SelfTradeMode>,
+        ) -> (Option<NonFungibleGlobalId>, Option<(NonFungibleGlobalId, SelfTradeMode)>) {
+            // Check parameters
+            assert!(
+                self_trade_mode.is_none() || owner_proof.is_some(), 
+                "Self trade prevention requires an owner proof."
+            );
+
+            // Any badge can identify an owner, the proof only needs to be of a single non fungible
+            let owner: Option<NonFungibleGlobalId> = owner_proof.map(|owner_proof| {
+                let owner_proof = owner_proof.skip_checking();
+                let resource_address: ResourceAddress = owner_proof.resource_address();
+                NonFungibleGlobalId::new(resource_address, owner_proof.as_non_fungible().non_fungible_local_id())
+            });
+            let self_trade: Option<(NonFungibleGlobalId, SelfTradeMode)> = self_trade_mode
+                .map(|self_trade_mode| (owner.clone().unwrap(), self_trade_mode));
+
+            (owner, self_trade)
+        }
+
+        /// Helper method to find the first order of an owner in a limit that a market order would reach.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `price` - Price of the limit.
+        /// * `is_ask` - Whether the limit is an ask or bid limit.
+        /// * `owner` - Global id of the badge identifying the owner.
+        /// * `amount_x_order` - Amount of the market order calculated in tokens x.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Option<(u64, Decimal)>` - Id of the order of the owner and the amount calculated in tokens x of 
+        /// orders ahead of it. None if the market order is filled before reaching an order of the owner.
+        /// 
+        /// # Requires
+        /// 
+        /// * `price` - Must be the price of an existing limit.
+        /// 
+        fn find_self_trade(&self, price: Price, is_ask: bool, owner: &NonFungibleGlobalId, amount_x_order: Decimal) -> Option<(u64, Decimal)> {
+            // Get limit
+            let limit: Limit = if is_ask {
+                *self.ask_limit_map.get(&price).unwrap()
+            } else {
+                *self.bid_limit_map.get(&price).unwrap()
+            };
+
+            // Loop through orders in limit until market order is filled
+            let mut amount_x_before: Decimal = Decimal::zero();
+            let mut next_id: u64 = limit.get_head_id();
+            while next_id != 0 && amount_x_before < amount_x_order {
+                // Get order
+                let order_id: u64 = next_id;
+                let order_data: OrderReceipt = self.order_receipt_manager
+                    .get_non_fungible_data(&NonFungibleLocalId::integer(order_id));
+                next_id = order_data.next_id;
+
+                // Return if order has same owner
+                if order_data.owner.as_ref() == Some(owner) {
+                    return Some((order_id, amount_x_before));
+                }
+
+                // Add unfilled amount of order
+                amount_x_before += if order_id == limit.get_head_id() {
+                    order_data.amount - limit.get_amount_x_unallocated()
+                } else {
+                    order_data.amount
+                };
+            }
+
+            None
+        }
+
+        /// Helper method to cancel an order by self trade prevention.
+        /// Tokens of the order stay in the order book to be claimed with the order receipt.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_id` - Id of order.
+        /// * `price` - Price of the limit.
+        /// * `is_ask` - Whether the limit is an ask or bid limit.
+        /// 
+        /// # Requires
+        /// 
+        /// * `order_id` - Must be an unfilled order in the limit at `price`.
+        /// 
+        fn cancel_self_trade_order(&mut self, order_id: u64, price: Price, is_ask: bool) {
+            // Get order and limit
+            let order_receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(order_id);
+            let order_data: OrderReceipt = self.order_receipt_manager.get_non_fungible_data(&order_receipt_id);
+            let mut limit: Limit = if is_ask {
+                *self.ask_limit_map.get(&price).unwrap()
+            } else {
+                *self.bid_limit_map.get(&price).unwrap()
+            };
+
+            // Remove order from limit and chain
+            let (_, amount_filled) = limit.claim_order(order_id, &order_data);
+            self.remove_from_order_chain(&order_data);
+            self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "expired_amount_filled", Some(amount_filled));
+            self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "is_self_trade_canceled", true);
+
+            // Update limit
+            let is_empty: bool = limit.is_empty();
+            if is_ask {
+                self.ask_limit_map.insert(price, limit);
+            } else {
+                self.bid_limit_map.insert(price, limit);
+            }
+
+            // If limit is empty remove from price tree
+            if is_empty {
+                self.price_index.remove(price);
+            }
+        }
+
+        /// Helper method to set aside an order skipped by self trade prevention while a market order is matched.
+        /// Any filled part of the order is kept as a filled slice.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_id` - Id of order.
+        /// * `price` - Price of the limit.
+        /// * `is_ask` - Whether the limit is an ask or bid limit.
+        /// 
+        /// # Requires
+        /// 
+        /// * `order_id` - Must be an unfilled order in the limit at `price`.
+        /// * The order must be put back with `restore_skipped_orders` before the market order ends.
+        /// 
+        fn skip_self_trade_order(&mut self, order_id: u64, price: Price, is_ask: bool) {
+            // Get order and limit
+            let order_receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(order_id);
+            let order_data: OrderReceipt = self.order_receipt_manager.get_non_fungible_data(&order_receipt_id);
+            let mut limit: Limit = if is_ask {
+                *self.ask_limit_map.get(&price).unwrap()
+            } else {
+                *self.bid_limit_map.get(&price).unwrap()
+            };
+
+            // Remove order from limit and chain
+            let (amount_canceled, amount_filled) = limit.claim_order(order_id, &order_data);
+            self.remove_from_order_chain(&order_data);
+            let is_empty: bool = limit.is_empty();
+            if is_ask {
+                self.ask_limit_map.insert(price, limit);
             } else {
-                tokens_fee.drop_empty();
+                self.bid_limit_map.insert(price, limit);
             }
 
-            // Emit market order event
-            Runtime::emit_event(MarketOrderEvent {
-                is_buy: true,
-                fills,
-            });
+            // If limit is empty remove from price tree
+            if is_empty {
+                self.price_index.remove(price);
+            }
 
-            // Return tokens
-            (tokens_x, tokens_y)
+            // Update order receipt with the unfilled part of the order
+            let amount_x: Decimal = amount_canceled - order_data.amount_reserve;
+            self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount_slices_filled", amount_filled);
+            self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "amount", amount_x);
         }
 
-        /// Helper method to execute a market order on bid limits in the order book.
-        /// A fee is taken from the input tokens.
-        /// Price is calculated as `tokens_y / tokens_x`.
-        /// Buys tokens_y for tokens_x from limit orders until order is filled or stop_price is reached.
-        /// Returns (tokens_y, tokens_x).
+        /// Helper method to put orders skipped by self trade prevention back at the front of their limits, 
+        /// so they keep their place in the FIFO ordering ahead of the orders that were not filled.
+        /// Tokens filled and not allocated to the first order of a limit are kept as a filled slice of that order.
         /// 
         /// # Arguments
         /// 
-        /// * `tokens_x` - Tokens x used to execute the market order.
-        /// * `stop_price` - Stop price at which to stop executing market order.
-        /// 
-        /// # Returns
-        /// 
-        /// * `Bucket` - Contains any tokens y bought.
-        /// * `Bucket` - Contains any tokens x remaining.
+        /// * `skipped` - Vector of skipped orders in format (order_id, price), in the order they were skipped.
+        /// * `is_ask` - Whether the limits are ask or bid limits.
         /// 
         /// # Requires
         /// 
-        /// * `tokens_x` - Must be tokens x.
-        /// * `tokens_x` - Must not be empty.
-        /// 
-        /// # Events
+        /// * `skipped` - Orders must have been set aside with `skip_self_trade_order`.
         /// 
-        /// * `MarketOrderEvent` - Event emitted when market order is placed.
-        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
-        /// 
-        fn market_order_x_to_y(&mut self, mut tokens_x: Bucket, stop_price: Price) -> (Bucket, Bucket) {
-            // Take fee
-            let protocol_fee: Decimal =  FEE_CONTROLLER.get_protocol_fee(Runtime::package_address());
-            let mut tokens_fee: Bucket = tokens_x.take_advanced(tokens_x.amount() * protocol_fee, INCOMING);
-            let amount_x_input: Decimal = tokens_x.amount();
-
-            // Initialize amounts
-            let mut amount_y_bought: Decimal = Decimal::zero();
-            let mut amount_x_order: Decimal = tokens_x.amount();
-            let mut fills: Vec<(Decimal, Decimal)> = Vec::new();
-
-            // Loop through limits
-            while let Some(price) = self.price_index.current_bid() {
-                // Decompress price
-                let price_dec: Decimal = price.into();
+        fn restore_skipped_orders(&mut self, skipped: Vec<(u64, Price)>, is_ask: bool) {
+            // Put back in reverse so the first order skipped at a price is first in its limit
+            for (order_id, price) in skipped.into_iter().rev() {
+                // Get order and limit
+                let order_receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(order_id);
+                let order_data: OrderReceipt = self.order_receipt_manager.get_non_fungible_data(&order_receipt_id);
+                let mut limit: Limit = if is_ask {
+                    *self.ask_limit_map.get(&price).unwrap()
+                } else {
+                    *self.bid_limit_map.get(&price).unwrap()
+                };
 
-                // Check stop price
-                if price < stop_price {
-                    break;
+                // If empty limit add price to price tree
+                if limit.is_empty() {
+                    self.price_index.insert(price, is_ask);
                 }
 
-                // Update last traded price
-                self.last_price = price;
-
-                // Get limit
-                let limit: &mut Limit = &mut self.bid_limit_map.get_mut(&price).unwrap();
-
-                // Calculate amounts
-                let amount_x_limit: Decimal = limit.get_amount_x();
-
-                // If order amount is greater or equal to the limit amount fill limit and continue,
-                // else partly fill limit and break
-                if amount_x_order >= amount_x_limit {
-                    limit.fully_fill();
-                    fills.push((price_dec, amount_x_limit));
-                    let amount_y_limit: Decimal = amount_x_limit * price_dec;
-                    amount_y_bought += amount_y_limit;
-                    amount_x_order -= amount_x_limit;
+                // Keep the filled part of a partly filled first order as a filled slice
+                let amount_x_unallocated: Decimal = limit.take_amount_x_unallocated();
+                if amount_x_unallocated > Decimal::zero() {
+                    let head_receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(limit.get_head_id());
+                    let head_data: OrderReceipt = self.order_receipt_manager.get_non_fungible_data(&head_receipt_id);
+                    self.order_receipt_manager.update_non_fungible_data(&head_receipt_id, "amount", head_data.amount - amount_x_unallocated);
+                    self.order_receipt_manager.update_non_fungible_data(
+                        &head_receipt_id, 
+                        "amount_slices_filled", 
+                        head_data.amount_slices_filled + amount_x_unallocated,
+                    );
+                }
 
-                    self.price_index.remove(price);
+                // Add order to front of limit
+                let head_id: u64 = limit.add_skipped_order(order_id, &order_data.amount, &order_data.amount_reserve, order_data.expiry_epoch, self.sequence, order_data.maker_fee);
+                if is_ask {
+                    self.ask_limit_map.insert(price, limit);
                 } else {
-                    limit.fill(&amount_x_order, &self.order_receipt_manager);
-                    fills.push((price_dec, amount_x_order));
-                    let amount_y_order: Decimal = amount_x_order * price_dec;
-                    amount_y_bought += amount_y_order;
-                    amount_x_order = Decimal::zero();
-
-                    break;
+                    self.bid_limit_map.insert(price, limit);
                 }
-            }
-
-            // Deposit and withdraw tokens
-            let tokens_y: Bucket = self.tokens_y.take_advanced(amount_y_bought, OUTGOING);
-            self.tokens_x.put(tokens_x.take_advanced(tokens_x.amount() - amount_x_order, INCOMING));
 
-            // If fee amount is not zero
-            if !tokens_fee.is_empty() {
-                // Return fee if tokens not used
-                if tokens_x.amount() != Decimal::zero() {
-                    let amount_unused: Decimal = tokens_x.amount() / amount_x_input * tokens_fee.amount();
-                    tokens_x.put(tokens_fee.take_advanced(amount_unused, OUTGOING));
+                // Update order receipts
+                if head_id != 0 {
+                    self.order_receipt_manager.update_non_fungible_data(&NonFungibleLocalId::integer(head_id), "prev_id", order_id);
                 }
-
-                // Emit fee event
-                Runtime::emit_event(ProtocolFeeEvent {
-                    token_address: self.tokens_x.resource_address(),
-                    amount: tokens_fee.amount(),
-                });
-
-                // Deposit fee
-                FEE_VAULTS.deposit(tokens_fee);
-            } else {
-                tokens_fee.drop_empty();
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "next_id", head_id);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "prev_id", 0u64);
+                self.order_receipt_manager.update_non_fungible_data(&order_receipt_id, "is_skipped", true);
             }
-
-            // Emit market order event
-            Runtime::emit_event(MarketOrderEvent {
-                is_buy: false,
-                fills,
-            });
-
-            // Return tokens
-            (tokens_y, tokens_x)
         }
 
         /// Helper method to remove an order receipt from the limit order chain.
@@ -1292,12 +3894,14 @@ mod order_book {
         /// # Events
         /// 
         /// * `ClaimOrderEvent` - Event emitted when order is claimed.
+        /// * `ProtocolFeeEvent` - Event emitted when maker fee is collected.
         /// 
         fn claim_order_ask(&mut self, order_id: u64, order_data: &OrderReceipt) -> (Bucket, Bucket) {
             // Get claim amounts
             let price: Price = order_data.price.into();
             let limit: &mut Limit = &mut self.ask_limit_map.get_mut(&price).unwrap();
             let (amount_canceled, amount_filled) = limit.claim_order(order_id, order_data);
+            let amount_filled = amount_filled - order_data.amount_claimed;
             let amount_x = amount_canceled;
             let amount_y = amount_filled * order_data.price;
 
@@ -1309,7 +3913,10 @@ mod order_book {
 
             // Withdraw tokens
             let tokens_x: Bucket = self.tokens_x.take_advanced(amount_x, OUTGOING);
-            let tokens_y: Bucket = self.tokens_y.take_advanced(amount_y, OUTGOING);
+            let mut tokens_y: Bucket = self.tokens_y.take_advanced(amount_y, OUTGOING);
+
+            // Apply maker fee
+            let (maker_fee, maker_rebate) = self.apply_maker_fee(&mut tokens_y, order_data.maker_fee);
 
             // Emit claim order event
             Runtime::emit_event(ClaimOrderEvent {
@@ -1319,7 +3926,9 @@ mod order_book {
                 amount_canceled,
                 amount_filled,
                 amount_x,
-                amount_y,
+                amount_y: amount_y - maker_fee + maker_rebate,
+                maker_fee,
+                maker_rebate,
             });
 
             // Return tokens
@@ -1346,12 +3955,14 @@ mod order_book {
         /// # Events
         /// 
         /// * `ClaimOrderEvent` - Event emitted when order is claimed.
+        /// * `ProtocolFeeEvent` - Event emitted when maker fee is collected.
         /// 
         fn claim_order_bid(&mut self, order_id: u64, order_data: &OrderReceipt) -> (Bucket, Bucket) {
             // Get claim amounts
             let price: Price = order_data.price.into();
             let limit: &mut Limit = &mut self.bid_limit_map.get_mut(&price).unwrap();
             let (amount_canceled, amount_filled) = limit.claim_order(order_id, order_data);
+            let amount_filled = amount_filled - order_data.amount_claimed;
             let amount_y = amount_canceled * order_data.price;
             let amount_x = amount_filled;
 
@@ -1363,7 +3974,10 @@ mod order_book {
 
             // Withdraw tokens
             let tokens_y: Bucket = self.tokens_y.take_advanced(amount_y, OUTGOING);
-            let tokens_x: Bucket = self.tokens_x.take_advanced(amount_x, OUTGOING);
+            let mut tokens_x: Bucket = self.tokens_x.take_advanced(amount_x, OUTGOING);
+
+            // Apply maker fee
+            let (maker_fee, maker_rebate) = self.apply_maker_fee(&mut tokens_x, order_data.maker_fee);
 
             // Emit claim order event
             Runtime::emit_event(ClaimOrderEvent {
@@ -1372,12 +3986,309 @@ mod order_book {
                 price: order_data.price,
                 amount_canceled,
                 amount_filled,
+                amount_x: amount_x - maker_fee + maker_rebate,
+                amount_y,
+                maker_fee,
+                maker_rebate,
+            });
+
+            // Return tokens
+            (tokens_x, tokens_y)
+        }
+
+        /// Helper method to claim tokens owned by an expired limit order receipt
+        /// that has already been removed from its limit.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_id` - Id of order.
+        /// * `order_data` - Data of order receipt.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains any tokens x from the order.
+        /// * `Bucket` - Contains any tokens y from the order.
+        /// 
+        /// # Requires
+        /// 
+        /// * `order_id` - Must be a valid order id.
+        /// * `order_data` - Must match the order receipt id.
+        /// * `order_data` - Must have been removed from its limit for expiring.
+        /// 
+        /// # Events
+        /// 
+        /// * `ClaimOrderEvent` - Event emitted when order is claimed.
+        /// * `ProtocolFeeEvent` - Event emitted when maker fee is collected.
+        /// 
+        fn claim_order_expired(&mut self, order_id: u64, order_data: &OrderReceipt) -> (Bucket, Bucket) {
+            // Get claim amounts
+            let amount_filled: Decimal = order_data.expired_amount_filled.unwrap();
+            let amount_total: Decimal = order_data.amount_slices_filled + order_data.amount + order_data.amount_reserve;
+            let amount_canceled: Decimal = amount_total - amount_filled;
+            let amount_filled: Decimal = amount_filled - order_data.amount_claimed;
+            let (amount_x, amount_y) = if order_data.is_ask {
+                (amount_canceled, amount_filled * order_data.price)
+            } else {
+                (amount_filled, amount_canceled * order_data.price)
+            };
+
+            // Withdraw tokens
+            let mut tokens_x: Bucket = self.tokens_x.take_advanced(amount_x, OUTGOING);
+            let mut tokens_y: Bucket = self.tokens_y.take_advanced(amount_y, OUTGOING);
+
+            // Apply maker fee
+            let (maker_fee, maker_rebate) = if order_data.is_ask {
+                self.apply_maker_fee(&mut tokens_y, order_data.maker_fee)
+            } else {
+                self.apply_maker_fee(&mut tokens_x, order_data.maker_fee)
+            };
+            let (amount_x, amount_y) = if order_data.is_ask {
+                (amount_x, amount_y - maker_fee + maker_rebate)
+            } else {
+                (amount_x - maker_fee + maker_rebate, amount_y)
+            };
+
+            // Emit claim order event
+            Runtime::emit_event(ClaimOrderEvent {
+                order_id: NonFungibleLocalId::integer(order_id),
+                is_ask: order_data.is_ask,
+                price: order_data.price,
+                amount_canceled,
+                amount_filled,
                 amount_x,
                 amount_y,
+                maker_fee,
+                maker_rebate,
+            });
+
+            // Return tokens
+            (tokens_x, tokens_y)
+        }
+
+        /// Helper method to execute a triggered trigger order as a market order.
+        /// Tokens bought and remaining stay in the order book to be claimed with the order receipt.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_receipt_id` - Id of order receipt.
+        /// * `order_data` - Data of order receipt.
+        /// * `trigger` - Trigger of the order.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains the bounty of the order.
+        /// 
+        /// # Requires
+        /// 
+        /// * `order_data` - Must be a trigger order that has not been executed.
+        /// 
+        /// # Events
+        /// 
+        /// * `ExecuteTriggerOrderEvent` - Event emitted when trigger order is executed.
+        /// * `MarketOrderEvent` - Event emitted when market order is placed.
+        /// * `FillEvent` - Event emitted for each limit filled by a market order.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// 
+        fn execute_trigger_order(&mut self, order_receipt_id: &NonFungibleLocalId, order_data: &OrderReceipt, trigger: &Trigger) -> Bucket {
+            let last_price: Decimal = self.get_trigger_reference_price().unwrap().into();
+            let mode: MarketOrderMode = MarketOrderMode::ImmediateOrCancel(Decimal::zero());
+
+            // Execute market order with escrowed tokens, measuring filled amounts from the vaults
+            let (tokens_bounty, amount_out, amount_remaining, amount_x_filled, amount_y_filled) = if order_data.is_ask {
+                let tokens_bounty: Bucket = self.trigger_tokens_x.take_advanced(trigger.bounty, OUTGOING);
+                let tokens_x: Bucket = self.trigger_tokens_x.take_advanced(trigger.amount_in, OUTGOING);
+                let stop_price: Price = match trigger.stop_price {
+                    Some(price_dec) => price_dec.round_to_price_range().into(),
+                    None => Price::MIN,
+                };
+
+                let amount_x_vault: Decimal = self.tokens_x.amount();
+                let (tokens_y, tokens_x) = self.market_order_x_to_y(tokens_x, stop_price, mode, false, None);
+                let amount_x_filled: Decimal = self.tokens_x.amount() - amount_x_vault;
+                let (amount_out, amount_remaining): (Decimal, Decimal) = (tokens_y.amount(), tokens_x.amount());
+                self.trigger_tokens_y.put(tokens_y);
+                self.trigger_tokens_x.put(tokens_x);
+
+                (tokens_bounty, amount_out, amount_remaining, amount_x_filled, amount_out)
+            } else {
+                let tokens_bounty: Bucket = self.trigger_tokens_y.take_advanced(trigger.bounty, OUTGOING);
+                let tokens_y: Bucket = self.trigger_tokens_y.take_advanced(trigger.amount_in, OUTGOING);
+                let stop_price: Price = match trigger.stop_price {
+                    Some(price_dec) => price_dec.round_to_price_range().into(),
+                    None => Price::MAX,
+                };
+
+                let amount_y_vault: Decimal = self.tokens_y.amount();
+                let (tokens_x, tokens_y) = self.market_order_y_to_x(tokens_y, stop_price, mode, false, None);
+                let amount_y_filled: Decimal = self.tokens_y.amount() - amount_y_vault;
+                let (amount_out, amount_remaining): (Decimal, Decimal) = (tokens_x.amount(), tokens_y.amount());
+                self.trigger_tokens_x.put(tokens_x);
+                self.trigger_tokens_y.put(tokens_y);
+
+                (tokens_bounty, amount_out, amount_remaining, amount_out, amount_y_filled)
+            };
+
+            // Update order receipt with average price and filled amount
+            let price: Decimal = if amount_x_filled > Decimal::zero() {
+                amount_y_filled / amount_x_filled
+            } else {
+                trigger.trigger_price
+            };
+            self.order_receipt_manager.update_non_fungible_data(order_receipt_id, "price", price);
+            self.order_receipt_manager.update_non_fungible_data(order_receipt_id, "amount", amount_x_filled);
+            self.order_receipt_manager.update_non_fungible_data(order_receipt_id, "trigger_amounts", Some((amount_out, amount_remaining)));
+
+            // Emit execute trigger order event
+            Runtime::emit_event(ExecuteTriggerOrderEvent {
+                order_id: order_receipt_id.clone(),
+                is_ask: order_data.is_ask,
+                last_price,
+                amount_out,
+                amount_remaining,
+                bounty: tokens_bounty.amount(),
+            });
+
+            // Return bounty
+            tokens_bounty
+        }
+
+        /// Helper method to claim tokens owned by a trigger order receipt.
+        /// Returns the escrowed tokens and bounty if the order has not been executed, 
+        /// else the tokens bought and any input tokens remaining.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `order_id` - Id of order.
+        /// * `order_data` - Data of order receipt.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Contains any tokens x.
+        /// * `Bucket` - Contains any tokens y.
+        /// 
+        /// # Requires
+        /// 
+        /// * `order_data` - Must be a trigger order.
+        /// 
+        /// # Events
+        /// 
+        /// * `ClaimOrderEvent` - Event emitted when order is claimed.
+        /// 
+        fn claim_order_trigger(&mut self, order_id: u64, order_data: &OrderReceipt) -> (Bucket, Bucket) {
+            // Get claim amounts
+            let trigger: Trigger = order_data.trigger.unwrap();
+            let (amount_in, amount_out, amount_canceled, amount_filled) = match order_data.trigger_amounts {
+                Some((amount_out, amount_remaining)) => {
+                    let amount_canceled: Decimal = if order_data.is_ask {
+                        amount_remaining
+                    } else {
+                        amount_remaining / trigger.trigger_price
+                    };
+                    (amount_remaining, amount_out, amount_canceled, order_data.amount)
+                },
+                None => (trigger.amount_in + trigger.bounty, Decimal::zero(), order_data.amount, Decimal::zero()),
+            };
+            let (amount_x, amount_y) = if order_data.is_ask {
+                (amount_in, amount_out)
+            } else {
+                (amount_out, amount_in)
+            };
+
+            // Withdraw tokens
+            let tokens_x: Bucket = self.trigger_tokens_x.take_advanced(amount_x, OUTGOING);
+            let tokens_y: Bucket = self.trigger_tokens_y.take_advanced(amount_y, OUTGOING);
+
+            // Emit claim order event
+            Runtime::emit_event(ClaimOrderEvent {
+                order_id: NonFungibleLocalId::integer(order_id),
+                is_ask: order_data.is_ask,
+                price: order_data.price,
+                amount_canceled,
+                amount_filled,
+                amount_x: tokens_x.amount(),
+                amount_y: tokens_y.amount(),
+                maker_fee: Decimal::zero(),
+                maker_rebate: Decimal::zero(),
             });
 
             // Return tokens
             (tokens_x, tokens_y)
         }
+
+        /// Helper method to get the maker fee for a new limit order.
+        /// A maker rebate is limited to the taker fee it is paid from.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Maker fee as ratio. Negative for a rebate.
+        /// 
+        fn get_maker_fee() -> Decimal {
+            let (maker_fee, taker_fee): (Decimal, Decimal) = FEE_CONTROLLER.get_maker_taker_fees(Runtime::package_address());
+            maker_fee.max(-taker_fee)
+        }
+
+        /// Helper method to apply the maker fee of a limit order to tokens from the filled part of the order.
+        /// A maker fee is taken from the tokens and deposited in the fee vaults. A maker rebate is added to 
+        /// the tokens from the taker fees kept when the order was filled, at the same maker fee. 
+        /// It is limited to the amount available, which only falls short by rounding or if the taker fee was lowered below the rebate.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens` - Tokens from the filled part of the order.
+        /// * `maker_fee` - Maker fee of the order as ratio. Negative for a rebate.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Amount of maker fee taken from the tokens.
+        /// * `Decimal` - Amount of maker rebate added to the tokens.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tokens` - Must be tokens x or tokens y.
+        /// 
+        /// # Events
+        /// 
+        /// * `ProtocolFeeEvent` - Event emitted when maker fee is collected.
+        /// 
+        fn apply_maker_fee(&mut self, tokens: &mut Bucket, maker_fee: Decimal) -> (Decimal, Decimal) {
+            if maker_fee > Decimal::zero() {
+                // Take maker fee
+                let tokens_fee: Bucket = tokens.take_advanced(tokens.amount() * maker_fee, INCOMING);
+                if tokens_fee.is_empty() {
+                    tokens_fee.drop_empty();
+                    return (Decimal::zero(), Decimal::zero());
+                }
+                let amount_fee: Decimal = tokens_fee.amount();
+
+                // Emit fee event
+                Runtime::emit_event(ProtocolFeeEvent {
+                    token_address: tokens_fee.resource_address(),
+                    amount: amount_fee,
+                    taker_fee: Decimal::zero(),
+                    maker_fee: amount_fee,
+                    maker_rebate: Decimal::zero(),
+                });
+
+                // Deposit fee
+                FEE_VAULTS.deposit(tokens_fee);
+
+                (amount_fee, Decimal::zero())
+            } else if maker_fee < Decimal::zero() {
+                // Pay maker rebate
+                let rebates: &mut Vault = if tokens.resource_address() == self.rebates_x.resource_address() {
+                    &mut self.rebates_x
+                } else {
+                    &mut self.rebates_y
+                };
+                let amount_rebate: Decimal = (tokens.amount() * -maker_fee).min(rebates.amount());
+                let tokens_rebate: Bucket = rebates.take_advanced(amount_rebate, OUTGOING);
+                let amount_rebate: Decimal = tokens_rebate.amount();
+                tokens.put(tokens_rebate);
+
+                (Decimal::zero(), amount_rebate)
+            } else {
+                (Decimal::zero(), Decimal::zero())
+            }
+        }
     }
 }
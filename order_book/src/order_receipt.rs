@@ -1,19 +1,60 @@
 use scrypto::prelude::*;
 
-/// Order receipt NFT that represents a outstanding limit order.
+use crate::trigger::*;
+
+/// Order receipt NFT that represents a outstanding limit order or trigger order.
 /// Tokens are owned by the order receipt and can be claimed using the order receipt.
 #[derive(ScryptoSbor, NonFungibleData)]
 pub struct OrderReceipt {
     /// Is ask or bid order.
     pub is_ask: bool,
     /// Price of order.
+    #[mutable]
     pub price: Decimal,
-    /// Amount of tokens in order calculated in tokens x.
+    /// Amount of tokens in order calculated in tokens x. For an iceberg order this is the slice currently in the limit.
+    #[mutable]
     pub amount: Decimal,
+    /// Optional amount of tokens calculated in tokens x shown in the limit at a time. None if not an iceberg order.
+    pub display_amount: Option<Decimal>,
+    /// Amount of tokens calculated in tokens x held in the hidden reserve of an iceberg order.
+    #[mutable]
+    pub amount_reserve: Decimal,
+    /// Amount of tokens calculated in tokens x from previous slices of an iceberg order that have been filled.
+    #[mutable]
+    pub amount_slices_filled: Decimal,
+    /// Optional epoch after which the order expires and is no longer filled.
+    pub expiry_epoch: Option<u64>,
+    /// Maker fee as ratio when the order was placed. Negative for a rebate.
+    pub maker_fee: Decimal,
+    /// Position of order in FIFO ordering. Orders in a limit with a lower sequence are filled first.
+    #[mutable]
+    pub sequence: u64,
     /// Id of next order in FIFO ordering linked list.
     #[mutable]
     pub next_id: u64,
     /// Id of previous order in FIFO ordering linked list.
     #[mutable]
     pub prev_id: u64,
+    /// Amount of tokens filled calculated in tokens x that have already been claimed.
+    #[mutable]
+    pub amount_claimed: Decimal,
+    /// Amount of tokens filled calculated in tokens x when the order was removed from its limit 
+    /// after expiring or being canceled by self trade prevention. None if the order has not been removed.
+    #[mutable]
+    pub expired_amount_filled: Option<Decimal>,
+    /// Optional global id of the badge that placed the order, used for self trade prevention.
+    pub owner: Option<NonFungibleGlobalId>,
+    /// Whether the order was canceled by self trade prevention.
+    #[mutable]
+    pub is_self_trade_canceled: bool,
+    /// Whether the order was skipped by self trade prevention and put back at the front of its limit, 
+    /// ahead of orders with a lower sequence that were filled while it was skipped. Cleared once the order is filled.
+    #[mutable]
+    pub is_skipped: bool,
+    /// Optional trigger of a trigger order. None for a limit order.
+    pub trigger: Option<Trigger>,
+    /// Amount of tokens bought and amount of input tokens remaining after a trigger order was executed. 
+    /// None if the order has not been executed.
+    #[mutable]
+    pub trigger_amounts: Option<(Decimal, Decimal)>,
 }
@@ -9,20 +9,34 @@ pub struct OrderData {
     pub price: Decimal,
     /// Filled amount of tokens for order calculated in tokens x.
     pub amount_filled: Decimal,
+    /// Filled amount of tokens for order already claimed calculated in tokens x.
+    pub amount_claimed: Decimal,
+    /// Filled amount of tokens for order not yet claimed calculated in tokens x.
+    pub amount_unclaimed: Decimal,
     /// Total amount of tokens for order calculated in tokens x.
     pub amount_total: Decimal,
+    /// Unfilled amount of tokens for order shown in the limit calculated in tokens x.
+    pub amount_visible: Decimal,
+    /// Unfilled amount of tokens for order including any hidden reserve calculated in tokens x.
+    pub amount_remaining: Decimal,
 }
 
 /// Order status.
 ///
 /// * `Open(OrderData)` - Order is open and has the contained order data.
+/// * `Pending(OrderData)` - Trigger order is waiting to be triggered and has the contained order data.
 /// * `Filled(OrderData)` - Order has been filled and has the contained order data.
+/// * `Expired(OrderData)` - Order has expired before being filled and has the contained order data.
+/// * `Canceled(OrderData)` - Order has been canceled by self trade prevention and has the contained order data.
 /// * `Claimed` - Order has been claimed and no longer exists.
 /// * `Invalid` - Order id is invalid.
 #[derive(ScryptoSbor)]
 pub enum OrderStatus {
     Open(OrderData),
+    Pending(OrderData),
     Filled(OrderData),
+    Expired(OrderData),
+    Canceled(OrderData),
     Claimed,
     Invalid,
 }
@@ -0,0 +1,12 @@
+use scrypto::prelude::*;
+
+/// Post only mode for a limit order. A post only limit order is never matched against
+/// the other side of the order book when placed, so it only ever adds liquidity.
+///
+/// * `Reject` - Panic if the limit order would cross the order book.
+/// * `Reprice` - Move the limit order to the closest price that does not cross the order book.
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PostOnlyMode {
+    Reject,
+    Reprice,
+}
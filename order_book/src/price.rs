@@ -77,6 +77,120 @@ impl Price {
     pub fn get_significand(&self) -> u32 {
         self.0 & Self::SIG_MASK
     }
+
+    /// Get the next larger price.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Self>` - The next larger price. None if price is the maximum price.
+    ///
+    pub fn increment(&self) -> Option<Self> {
+        self.increment_with_precision(Self::PRECISION)
+    }
+
+    /// Get the next smaller price.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Self>` - The next smaller price. None if price is the minimum price.
+    ///
+    pub fn decrement(&self) -> Option<Self> {
+        self.decrement_with_precision(Self::PRECISION)
+    }
+
+    /// Get the next larger price with a number of significant figures.
+    ///
+    /// # Arguments
+    ///
+    /// * `precision` - Number of significant figures.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Self>` - The next larger price. None if price is the maximum price.
+    ///
+    /// # Requires
+    ///
+    /// * `precision` - Must be in range of [1, PRECISION].
+    /// * `self` - Must be truncated to `precision`.
+    ///
+    pub fn increment_with_precision(&self, precision: u32) -> Option<Self> {
+        if *self >= Self::MAX {
+            return None;
+        }
+
+        // Increment significand, carrying into exp if needed
+        let step: u32 = Self::step(precision);
+        let significand: u32 = self.get_significand();
+        if significand + step <= Self::SIG_MAX {
+            Some(Self::new(self.get_exp(), significand + step))
+        } else {
+            Some(Self::new(self.get_exp() + 1, Self::SIG_MIN))
+        }
+    }
+
+    /// Get the next smaller price with a number of significant figures.
+    ///
+    /// # Arguments
+    ///
+    /// * `precision` - Number of significant figures.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Self>` - The next smaller price. None if price is the minimum price.
+    ///
+    /// # Requires
+    ///
+    /// * `precision` - Must be in range of [1, PRECISION].
+    /// * `self` - Must be truncated to `precision`.
+    ///
+    pub fn decrement_with_precision(&self, precision: u32) -> Option<Self> {
+        if *self <= Self::MIN {
+            return None;
+        }
+
+        // Decrement significand, borrowing from exp if needed
+        let step: u32 = Self::step(precision);
+        let significand: u32 = self.get_significand();
+        if significand >= Self::SIG_MIN + step {
+            Some(Self::new(self.get_exp(), significand - step))
+        } else {
+            Some(Self::new(self.get_exp() - 1, Self::SIG_MAX + 1 - step))
+        }
+    }
+
+    /// Truncate the price to a number of significant figures.
+    ///
+    /// # Arguments
+    ///
+    /// * `precision` - Number of significant figures.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The truncated price.
+    ///
+    /// # Requires
+    ///
+    /// * `precision` - Must be in range of [1, PRECISION].
+    ///
+    pub fn truncate(&self, precision: u32) -> Self {
+        let step: u32 = Self::step(precision);
+        let significand: u32 = self.get_significand();
+        Self::new(self.get_exp(), significand - significand % step)
+    }
+
+    /// Helper method to get the difference in significand between adjacent prices with a number of significant figures.
+    ///
+    /// # Arguments
+    ///
+    /// * `precision` - Number of significant figures.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - Difference in significand.
+    ///
+    fn step(precision: u32) -> u32 {
+        10u32.pow(Self::PRECISION - precision)
+    }
 }
 
 // Implement Ord, PartialOrd, PartialEq, and Eq for Price
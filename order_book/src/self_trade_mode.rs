@@ -0,0 +1,15 @@
+use scrypto::prelude::*;
+
+/// Self trade prevention mode for an order. Applied when an order would be matched 
+/// against a resting limit order placed with the same owner badge.
+///
+/// * `CancelResting` - Cancel the resting limit order and continue matching.
+/// * `CancelIncoming` - Stop matching and return the remaining tokens of the incoming order.
+/// * `SkipMaker` - Move the resting limit order to the end of its limit and continue matching. 
+/// Matching stops if only skipped limit orders remain at the best price.
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SelfTradeMode {
+    CancelResting,
+    CancelIncoming,
+    SkipMaker,
+}
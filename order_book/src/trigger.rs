@@ -0,0 +1,48 @@
+use scrypto::prelude::*;
+
+use crate::trigger_mode::*;
+
+/// Condition and escrowed amounts of a trigger order stored on its order receipt.
+/// Prices are calculated as `tokens_y / tokens_x`.
+///
+/// # Responsible for
+///
+/// * Checking if the trigger condition is met.
+///
+/// # Not responsible for
+///
+/// * Holding tokens or executing the market order.
+///
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct Trigger {
+    /// Trigger mode of the order.
+    pub mode: TriggerMode,
+    /// Price at which the order is triggered.
+    pub trigger_price: Decimal,
+    /// Optional stop price of the market order executed when triggered.
+    pub stop_price: Option<Decimal>,
+    /// Amount of input tokens escrowed for the market order.
+    pub amount_in: Decimal,
+    /// Amount of input tokens escrowed as a bounty for activating the order.
+    pub bounty: Decimal,
+}
+
+impl Trigger {
+    /// Check if the trigger condition is met.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_ask` - Whether the order sells tokens x or buys tokens x.
+    /// * `last_price` - Last price a limit was filled at.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the order can be executed.
+    ///
+    pub fn is_triggered(&self, is_ask: bool, last_price: Decimal) -> bool {
+        match (self.mode, is_ask) {
+            (TriggerMode::StopLoss, true) | (TriggerMode::TakeProfit, false) => last_price <= self.trigger_price,
+            (TriggerMode::TakeProfit, true) | (TriggerMode::StopLoss, false) => last_price >= self.trigger_price,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use scrypto::prelude::*;
+
+/// Trigger mode for a trigger order. A trigger order is executed as a market order 
+/// once the last price crosses its trigger price.
+///
+/// * `StopLoss` - Sell tokens x once the last price falls to or below the trigger price, 
+/// or buy tokens x once the last price rises to or above the trigger price.
+/// * `TakeProfit` - Sell tokens x once the last price rises to or above the trigger price, 
+/// or buy tokens x once the last price falls to or below the trigger price.
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriggerMode {
+    StopLoss,
+    TakeProfit,
+}
@@ -40,6 +40,22 @@ pub fn set_protocol_fee_default_zero(vars: &mut Vars) {
             manifest_args!(0u16))
         .build();
 
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
+pub fn set_maker_fee(vars: &mut Vars, fee: i32) {
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_amount(vars.admin_account_component, vars.admin_badge, dec!(1))
+        .call_method(
+            vars.fee_controller_component,
+            "set_maker_fee",
+            manifest_args!(vars.order_book_package, fee))
+        .build();
+
     let receipt = vars.test_runner.execute_manifest_ignoring_fee(
         manifest,
         vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
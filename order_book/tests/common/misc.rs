@@ -12,6 +12,10 @@ pub fn round_up(amount: Decimal, divisibility: u8) -> Decimal {
     amount.checked_round(divisibility, RoundingMode::ToPositiveInfinity).unwrap()
 }
 
+pub fn set_time(round: u64, seconds: i64, vars: &mut Vars) {
+    vars.test_runner.advance_to_round_at_timestamp(Round::of(round), seconds * 1000).expect_commit_success();
+}
+
 pub fn assert_balance(resource: ResourceAddress, amount: Decimal, vars: &mut Vars) {
     assert_eq!(
         vars.test_runner.get_component_balance(vars.account_component, resource),
@@ -1,8 +1,10 @@
 use scrypto::prelude::*;
 use transaction::{builder::ManifestBuilder, model::TransactionManifestV1};
-use radix_engine::transaction::TransactionReceipt;
+use radix_engine::transaction::{TransactionReceipt, TransactionResult};
 
 use crate::common::vars::*;
+pub use ::order_book::candle::Candle;
+pub use ::order_book::depth_level::DepthLevel;
 
 /// Order data.
 #[derive(ScryptoSbor, Clone, Copy)]
@@ -13,24 +15,119 @@ pub struct OrderData {
     pub price: Decimal,
     /// Filled amount of tokens for order calculated in tokens x.
     pub amount_filled: Decimal,
+    /// Filled amount of tokens for order already claimed calculated in tokens x.
+    pub amount_claimed: Decimal,
+    /// Filled amount of tokens for order not yet claimed calculated in tokens x.
+    pub amount_unclaimed: Decimal,
     /// Total amount of tokens for order calculated in tokens x.
     pub amount_total: Decimal,
+    /// Unfilled amount of tokens for order shown in the limit calculated in tokens x.
+    pub amount_visible: Decimal,
+    /// Unfilled amount of tokens for order including any hidden reserve calculated in tokens x.
+    pub amount_remaining: Decimal,
 }
 
 /// Order status.
 ///
 /// * `Open(OrderData)` - Order is open and has the contained order data.
+/// * `Pending(OrderData)` - Trigger order is waiting to be triggered and has the contained order data.
 /// * `Filled(OrderData)` - Order has been filled and has the contained order data.
+/// * `Expired(OrderData)` - Order has expired before being filled and has the contained order data.
+/// * `Canceled(OrderData)` - Order has been canceled by self trade prevention and has the contained order data.
 /// * `Claimed` - Order has been claimed and no longer exists.
 /// * `Invalid` - Order id is invalid.
 #[derive(ScryptoSbor)]
 pub enum OrderStatus {
     Open(OrderData),
+    Pending(OrderData),
     Filled(OrderData),
+    Expired(OrderData),
+    Canceled(OrderData),
     Claimed,
     Invalid,
 }
 
+/// Expected outcome of a market order.
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct MarketOrderQuote {
+    /// Amount of tokens bought.
+    pub amount_out: Decimal,
+    /// Average price of fills. None if nothing would be filled.
+    pub average_price: Option<Decimal>,
+    /// Amount of input tokens taken as protocol fee.
+    pub protocol_fee: Decimal,
+    /// Last limit price reached. None if nothing would be filled.
+    pub worst_price: Option<Decimal>,
+    /// Amount of input tokens returned unused.
+    pub amount_remaining: Decimal,
+}
+
+/// Post only mode.
+///
+/// * `Reject` - Panic if the limit order would cross the order book.
+/// * `Reprice` - Move the limit order to the closest price that does not cross the order book.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum PostOnlyMode {
+    Reject,
+    Reprice,
+}
+
+/// Self trade prevention mode.
+///
+/// * `CancelResting` - Cancel the resting limit order and continue matching.
+/// * `CancelIncoming` - Stop matching and return the remaining tokens of the incoming order.
+/// * `SkipMaker` - Move the resting limit order to the end of its limit and continue matching.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum SelfTradeMode {
+    CancelResting,
+    CancelIncoming,
+    SkipMaker,
+}
+
+/// Trigger mode.
+///
+/// * `StopLoss` - Sell tokens x once the last price falls to or below the trigger price, or buy once it rises to or above.
+/// * `TakeProfit` - Sell tokens x once the last price rises to or above the trigger price, or buy once it falls to or below.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum TriggerMode {
+    StopLoss,
+    TakeProfit,
+}
+
+/// Ladder distribution.
+///
+/// * `Flat` - Every level gets the same amount of tokens.
+/// * `Linear` - Each level gets an amount proportional to its position, starting at one.
+/// * `Geometric(Decimal)` - Each level gets the amount of the previous level multiplied by the ratio.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum LadderDistribution {
+    Flat,
+    Linear,
+    Geometric(Decimal),
+}
+
+/// Market order mode.
+///
+/// * `ImmediateOrCancel(Decimal)` - Execute as much as possible, panic if less than the minimum amount is bought.
+/// * `FillOrKill` - Panic unless all tokens are executed.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum MarketOrderMode {
+    ImmediateOrCancel(Decimal),
+    FillOrKill,
+}
+
+/// Order book state.
+///
+/// * `Active` - All actions are allowed.
+/// * `CancelOnly` - Only order receipts can be claimed.
+/// * `Halted` - No actions that move tokens are allowed.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrderBookState {
+    Active,
+    CancelOnly,
+    Halted,
+}
+
 pub fn build_manifest(
     order_book_package: PackageAddress,
     owner_rule: AccessRule,
@@ -38,15 +135,100 @@ pub fn build_manifest(
     token_x_address: ResourceAddress, 
     token_y_address: ResourceAddress, 
     ) -> TransactionManifestV1 {
+    build_manifest_with_config(order_book_package, owner_rule, user_rule, token_x_address, token_y_address, dec!(0), None, 5)
+}
+
+pub fn build_manifest_with_config(
+    order_book_package: PackageAddress,
+    owner_rule: AccessRule,
+    user_rule: AccessRule,
+    token_x_address: ResourceAddress, 
+    token_y_address: ResourceAddress, 
+    min_order_size: Decimal, 
+    lot_size: Option<Decimal>, 
+    price_precision: u32, 
+    ) -> TransactionManifestV1 {
     ManifestBuilder::new()
         .call_function(
             order_book_package,
             "OrderBook",
             "new",
-            manifest_args!(owner_rule, user_rule, token_x_address, token_y_address, None::<ManifestAddressReservation>))
+            manifest_args!(owner_rule, user_rule, AccessRule::DenyAll, token_x_address, token_y_address, min_order_size, lot_size, price_precision, None::<ManifestAddressReservation>))
         .build()
 }
 
+pub fn new_order_book_with_config(min_order_size: Decimal, lot_size: Option<Decimal>, price_precision: u32, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = build_manifest_with_config(
+        vars.order_book_package,
+        rule!(require(vars.admin_badge)),
+        AccessRule::AllowAll,
+        vars.token_x, 
+        vars.token_y, 
+        min_order_size,
+        lot_size,
+        price_precision,
+    );
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
+    );
+
+    if let TransactionResult::Commit(commit) = &receipt.transaction_result {
+        if !commit.new_component_addresses().is_empty() {
+            vars.order_book_component = commit.new_component_addresses()[0];
+            vars.order_receipt = commit.new_resource_addresses()[0];
+        }
+    }
+
+    receipt
+}
+
+pub fn set_state(state: OrderBookState, with_proof: bool, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = if with_proof {
+        ManifestBuilder::new()
+            .create_proof_from_account_of_amount(vars.admin_account_component, vars.admin_badge, dec!(1))
+            .call_method(
+                vars.order_book_component,
+                "set_state",
+                manifest_args!(state))
+            .build()
+    } else {
+        ManifestBuilder::new()
+            .call_method(
+                vars.order_book_component,
+                "set_state",
+                manifest_args!(state))
+            .build()
+    };
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
+    );
+    // println!("\nSET STATE\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn get_state(vars: &mut Vars) -> OrderBookState {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.order_book_component,
+        "get_state",
+        manifest_args!(),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nGET STATE\n");
+    // println!("{:?}", receipt);
+    receipt.expect_commit_success().output::<OrderBookState>(1)
+}
+
 pub fn get_fee_controller_address(vars: &mut Vars) -> ComponentAddress {
     let manifest = ManifestBuilder::new()
         .call_method(
@@ -83,6 +265,54 @@ pub fn get_fee_vaults_address(vars: &mut Vars) -> ComponentAddress {
     receipt.expect_commit_success().output::<ComponentAddress>(1)
 }
 
+pub fn get_min_order_size(vars: &mut Vars) -> Decimal {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.order_book_component,
+        "get_min_order_size",
+        manifest_args!(),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    receipt.expect_commit_success().output::<Decimal>(1)
+}
+
+pub fn get_lot_size(vars: &mut Vars) -> Option<Decimal> {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.order_book_component,
+        "get_lot_size",
+        manifest_args!(),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    receipt.expect_commit_success().output::<Option<Decimal>>(1)
+}
+
+pub fn get_price_precision(vars: &mut Vars) -> u32 {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.order_book_component,
+        "get_price_precision",
+        manifest_args!(),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    receipt.expect_commit_success().output::<u32>(1)
+}
+
 pub fn get_token_x_address(vars: &mut Vars) -> ResourceAddress {
     let manifest = ManifestBuilder::new()
     .call_method(
@@ -137,6 +367,51 @@ pub fn get_order_receipt_address(vars: &mut Vars) -> ResourceAddress {
     receipt.expect_commit_success().output::<ResourceAddress>(1)
 }
 
+pub fn sweep_rebates(amount_x: Decimal, amount_y: Decimal, with_proof: bool, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = if with_proof {
+        ManifestBuilder::new()
+            .create_proof_from_account_of_amount(vars.admin_account_component, vars.admin_badge, dec!(1))
+            .call_method(
+                vars.order_book_component,
+                "sweep_rebates",
+                manifest_args!(amount_x, amount_y))
+            .build()
+    } else {
+        ManifestBuilder::new()
+            .call_method(
+                vars.order_book_component,
+                "sweep_rebates",
+                manifest_args!(amount_x, amount_y))
+            .build()
+    };
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
+    );
+    // println!("\nSWEEP REBATES\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn get_rebates(vars: &mut Vars) -> (Decimal, Decimal) {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.order_book_component,
+        "get_rebates",
+        manifest_args!(),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nGET REBATES\n");
+    // println!("{:?}", receipt);
+    receipt.expect_commit_success().output::<(Decimal, Decimal)>(1)
+}
+
 pub fn get_amount_x(vars: &mut Vars) -> Decimal {
     let manifest = ManifestBuilder::new()
     .call_method(
@@ -263,6 +538,25 @@ pub fn get_bid_limits(start_price: Option<Decimal>, stop_price: Option<Decimal>,
     receipt.expect_commit_success().output::<Vec<(Decimal, Decimal)>>(1)
 }
 
+pub fn get_depth_receipt(levels: u32, bucket_size: Option<Decimal>, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            vars.order_book_component,
+            "get_depth",
+            manifest_args!(levels, bucket_size),
+        )
+        .build();
+
+    vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)]
+    )
+}
+
+pub fn get_depth(levels: u32, bucket_size: Option<Decimal>, vars: &mut Vars) -> (Vec<DepthLevel>, Vec<DepthLevel>) {
+    get_depth_receipt(levels, bucket_size, vars).expect_commit_success().output::<(Vec<DepthLevel>, Vec<DepthLevel>)>(1)
+}
+
 pub fn get_order_status(id: u64, vars: &mut Vars) -> OrderStatus {
     let id = NonFungibleLocalId::integer(id);
     let manifest = ManifestBuilder::new()
@@ -301,15 +595,106 @@ pub fn get_order_statuses(ids: Vec<u64>, vars: &mut Vars) -> Vec<OrderStatus> {
     receipt.expect_commit_success().output::<Vec<OrderStatus>>(1)
 }
 
-pub fn limit_order(token: ResourceAddress, amount: Decimal, price: Decimal, vars: &mut Vars) -> TransactionReceipt {
+pub fn quote_market_order(token: ResourceAddress, amount: Decimal, stop_price: Option<Decimal>, vars: &mut Vars) -> MarketOrderQuote {
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            vars.order_book_component,
+            "quote_market_order",
+            manifest_args!(token, amount, stop_price),
+        )
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)]
+    );
+    // println!("\nQUOTE MARKET ORDER\n");
+    // println!("{:?}", receipt);
+    receipt.expect_commit_success().output::<MarketOrderQuote>(1)
+}
+
+pub fn get_candle(epoch: u64, vars: &mut Vars) -> Option<Candle> {
     let manifest = ManifestBuilder::new()
+        .call_method(
+            vars.order_book_component,
+            "get_candle",
+            manifest_args!(epoch),
+        )
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)]
+    );
+    receipt.expect_commit_success().output::<Option<Candle>>(1)
+}
+
+pub fn get_twap_receipt(start_time: i64, end_time: i64, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            vars.order_book_component,
+            "get_twap",
+            manifest_args!(start_time, end_time),
+        )
+        .build();
+
+    vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)]
+    )
+}
+
+pub fn get_twap(start_time: i64, end_time: i64, vars: &mut Vars) -> Decimal {
+    get_twap_receipt(start_time, end_time, vars).expect_commit_success().output::<Decimal>(1)
+}
+
+pub fn limit_order(token: ResourceAddress, amount: Decimal, price: Decimal, vars: &mut Vars) -> TransactionReceipt {
+    limit_order_with_options(token, amount, price, None, None, None, None, None, vars)
+}
+
+pub fn limit_order_post_only(token: ResourceAddress, amount: Decimal, price: Decimal, post_only: Option<PostOnlyMode>, vars: &mut Vars) -> TransactionReceipt {
+    limit_order_with_options(token, amount, price, post_only, None, None, None, None, vars)
+}
+
+pub fn limit_order_with_expiry(token: ResourceAddress, amount: Decimal, price: Decimal, expiry_epoch: Option<u64>, vars: &mut Vars) -> TransactionReceipt {
+    limit_order_with_options(token, amount, price, None, expiry_epoch, None, None, None, vars)
+}
+
+pub fn limit_order_iceberg(token: ResourceAddress, amount: Decimal, price: Decimal, display_amount: Decimal, vars: &mut Vars) -> TransactionReceipt {
+    limit_order_with_options(token, amount, price, None, None, Some(display_amount), None, None, vars)
+}
+
+pub fn limit_order_with_owner(
+    token: ResourceAddress, 
+    amount: Decimal, 
+    price: Decimal, 
+    owner_badge: Option<(ResourceAddress, u64)>, 
+    self_trade_mode: Option<SelfTradeMode>, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
+    limit_order_with_options(token, amount, price, None, None, None, owner_badge, self_trade_mode, vars)
+}
+
+pub fn limit_order_with_options(
+    token: ResourceAddress, 
+    amount: Decimal, 
+    price: Decimal, 
+    post_only: Option<PostOnlyMode>, 
+    expiry_epoch: Option<u64>, 
+    display_amount: Option<Decimal>, 
+    owner_badge: Option<(ResourceAddress, u64)>, 
+    self_trade_mode: Option<SelfTradeMode>, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
+    let manifest = build_owner_proof(ManifestBuilder::new(), owner_badge, vars)
         .withdraw_from_account(vars.account_component, token, amount)
         .take_all_from_worktop(token, "tokens")
         .with_name_lookup(|builder, lookup| {
+            let owner_proof = owner_badge.map(|_| lookup.proof("owner_proof"));
             builder.call_method(
                 vars.order_book_component,
                 "limit_order",
-                manifest_args!(lookup.bucket("tokens") , price))
+                manifest_args!(lookup.bucket("tokens") , price, post_only, expiry_epoch, display_amount, owner_proof, self_trade_mode))
         })
         .call_method(
             vars.account_component,
@@ -327,6 +712,10 @@ pub fn limit_order(token: ResourceAddress, amount: Decimal, price: Decimal, vars
 }
 
 pub fn limit_order_batch(positions: Vec<(ResourceAddress, Decimal, Decimal)>, vars: &mut Vars) -> TransactionReceipt {
+    limit_order_batch_post_only(positions, None, vars)
+}
+
+pub fn limit_order_batch_post_only(positions: Vec<(ResourceAddress, Decimal, Decimal)>, post_only: Option<PostOnlyMode>, vars: &mut Vars) -> TransactionReceipt {
     let mut counter = 0;
     let mut args0: Vec<(String, Decimal)> = vec![];
 
@@ -351,7 +740,7 @@ pub fn limit_order_batch(positions: Vec<(ResourceAddress, Decimal, Decimal)>, va
             builder.call_method(
                 vars.order_book_component,
                 "limit_order_batch",
-                manifest_args!(args1))
+                manifest_args!(args1, post_only, None::<u64>, None::<ManifestProof>, None::<SelfTradeMode>))
         })
         .call_method(
             vars.account_component,
@@ -368,15 +757,83 @@ pub fn limit_order_batch(positions: Vec<(ResourceAddress, Decimal, Decimal)>, va
     receipt
 }
 
-pub fn market_order(token: ResourceAddress, amount: Decimal, stop_price: Option<Decimal>, vars: &mut Vars) -> TransactionReceipt {
+pub fn limit_order_ladder(
+    token: ResourceAddress, 
+    amount: Decimal, 
+    start_price: Decimal, 
+    end_price: Decimal, 
+    levels: u32, 
+    distribution: LadderDistribution, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
     let manifest = ManifestBuilder::new()
         .withdraw_from_account(vars.account_component, token, amount)
         .take_all_from_worktop(token, "tokens")
         .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.order_book_component,
+                "limit_order_ladder",
+                manifest_args!(lookup.bucket("tokens"), start_price, end_price, levels, distribution, None::<u64>, None::<ManifestProof>))
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nLIMIT ORDER LADDER\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn market_order(token: ResourceAddress, amount: Decimal, stop_price: Option<Decimal>, vars: &mut Vars) -> TransactionReceipt {
+    market_order_with_mode(token, amount, stop_price, None, None, vars)
+}
+
+pub fn market_order_with_mode(
+    token: ResourceAddress, 
+    amount: Decimal, 
+    stop_price: Option<Decimal>, 
+    mode: Option<MarketOrderMode>, 
+    max_amount_in: Option<Decimal>, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
+    market_order_with_options(token, amount, stop_price, mode, max_amount_in, None, None, vars)
+}
+
+pub fn market_order_with_owner(
+    token: ResourceAddress, 
+    amount: Decimal, 
+    owner_badge: Option<(ResourceAddress, u64)>, 
+    self_trade_mode: Option<SelfTradeMode>, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
+    market_order_with_options(token, amount, None, None, None, owner_badge, self_trade_mode, vars)
+}
+
+pub fn market_order_with_options(
+    token: ResourceAddress, 
+    amount: Decimal, 
+    stop_price: Option<Decimal>, 
+    mode: Option<MarketOrderMode>, 
+    max_amount_in: Option<Decimal>, 
+    owner_badge: Option<(ResourceAddress, u64)>, 
+    self_trade_mode: Option<SelfTradeMode>, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
+    let manifest = build_owner_proof(ManifestBuilder::new(), owner_badge, vars)
+        .withdraw_from_account(vars.account_component, token, amount)
+        .take_all_from_worktop(token, "tokens")
+        .with_name_lookup(|builder, lookup| {
+            let owner_proof = owner_badge.map(|_| lookup.proof("owner_proof"));
             builder.call_method(
                 vars.order_book_component,
                 "market_order",
-                manifest_args!(lookup.bucket("tokens") , stop_price))
+                manifest_args!(lookup.bucket("tokens") , stop_price, mode, max_amount_in, owner_proof, self_trade_mode))
         })
         .call_method(
             vars.account_component,
@@ -393,6 +850,91 @@ pub fn market_order(token: ResourceAddress, amount: Decimal, stop_price: Option<
     receipt
 }
 
+pub fn trigger_order(
+    token: ResourceAddress, 
+    amount: Decimal, 
+    trigger_price: Decimal, 
+    mode: TriggerMode, 
+    stop_price: Option<Decimal>, 
+    bounty: Decimal, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(vars.account_component, token, amount)
+        .take_all_from_worktop(token, "tokens")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.order_book_component,
+                "trigger_order",
+                manifest_args!(lookup.bucket("tokens") , trigger_price, mode, stop_price, bounty))
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nTRIGGER ORDER\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn execute_trigger_orders(ids: Vec<u64>, account: ComponentAddress, public_key: Secp256k1PublicKey, vars: &mut Vars) -> TransactionReceipt {
+    let ids: Vec<NonFungibleLocalId> = ids.into_iter().map(NonFungibleLocalId::integer).collect();
+    let manifest = ManifestBuilder::new()
+        .call_method(
+            vars.order_book_component,
+            "execute_trigger_orders",
+            manifest_args!(ids))
+        .call_method(
+            account,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    // println!("\nEXECUTE TRIGGER ORDERS\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn market_order_and_execute_trigger_orders(token: ResourceAddress, amount: Decimal, ids: Vec<u64>, vars: &mut Vars) -> TransactionReceipt {
+    let ids: Vec<NonFungibleLocalId> = ids.into_iter().map(NonFungibleLocalId::integer).collect();
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(vars.account_component, token, amount)
+        .take_all_from_worktop(token, "tokens")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.order_book_component,
+                "market_order",
+                manifest_args!(lookup.bucket("tokens"), None::<Decimal>, None::<MarketOrderMode>, None::<Decimal>, None::<ManifestProof>, None::<SelfTradeMode>))
+        })
+        .call_method(
+            vars.order_book_component,
+            "execute_trigger_orders",
+            manifest_args!(ids))
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nMARKET ORDER AND EXECUTE TRIGGER ORDERS\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
 pub fn claim_orders(ids: BTreeSet<NonFungibleLocalId>, vars: &mut Vars) -> TransactionReceipt {
     let manifest = ManifestBuilder::new()
         .withdraw_non_fungibles_from_account(vars.account_component, vars.order_receipt, ids.clone())
@@ -417,3 +959,67 @@ pub fn claim_orders(ids: BTreeSet<NonFungibleLocalId>, vars: &mut Vars) -> Trans
     // println!("{:?}", receipt);
     receipt
 }
+
+pub fn claim_filled(id: u64, vars: &mut Vars) -> TransactionReceipt {
+    let ids = vec![NonFungibleLocalId::integer(id)];
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_non_fungibles(vars.account_component, vars.order_receipt, ids.iter().cloned())
+        .create_proof_from_auth_zone_of_non_fungibles(vars.order_receipt, ids.iter().cloned(), "order_proof")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.order_book_component,
+                "claim_filled",
+                manifest_args!(lookup.proof("order_proof")))
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nCLAIM FILLED\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn amend_order(id: u64, amount: Decimal, price: Option<Decimal>, vars: &mut Vars) -> TransactionReceipt {
+    let ids = vec![NonFungibleLocalId::integer(id)];
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_non_fungibles(vars.account_component, vars.order_receipt, ids.iter().cloned())
+        .create_proof_from_auth_zone_of_non_fungibles(vars.order_receipt, ids.iter().cloned(), "order_proof")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.order_book_component,
+                "amend_order",
+                manifest_args!(lookup.proof("order_proof"), amount, price))
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nAMEND ORDER\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn build_owner_proof(builder: ManifestBuilder, owner_badge: Option<(ResourceAddress, u64)>, vars: &Vars) -> ManifestBuilder {
+    match owner_badge {
+        Some((badge, id)) => {
+            let ids = vec![NonFungibleLocalId::integer(id)];
+            builder
+                .create_proof_from_account_of_non_fungibles(vars.account_component, badge, ids.iter().cloned())
+                .create_proof_from_auth_zone_of_non_fungibles(badge, ids.iter().cloned(), "owner_proof")
+        },
+        None => builder,
+    }
+}
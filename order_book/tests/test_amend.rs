@@ -0,0 +1,251 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_amend_reduce_keeps_position_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!("0.5"), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!("1.5"))]
+    );
+
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!("0.5"));
+            assert_eq!(order_data.amount_total, dec!("0.5"));
+        },
+        _ => panic!(),
+    }
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Open(order_data) => assert_eq!(order_data.amount_filled, dec!("0.5")),
+        _ => panic!(),
+    }
+
+    assert_balance(vars.token_x, vars.amount_x - dec!("0.5"), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_amend_reduce_keeps_position_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!("0.5"), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!("1.5"))]
+    );
+
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(order_data) => assert_eq!(order_data.amount_total, dec!("0.5")),
+        _ => panic!(),
+    }
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Open(order_data) => assert_eq!(order_data.amount_filled, dec!("0.5")),
+        _ => panic!(),
+    }
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!("0.5"), &mut vars);
+}
+
+#[test]
+fn test_amend_reduce_partially_filled_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    amend_order(1, dec!("0.5"), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(1));
+            assert_eq!(order_data.amount_total, dec!("1.5"));
+        },
+        _ => panic!(),
+    }
+
+    market_order(vars.token_y, dec!("0.5"), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_amend_price_moves_to_tail_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    amend_order(1, dec!(1), Some(dec!(2)), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(2), dec!(2))]
+    );
+
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.price, dec!(2));
+            assert_eq!(order_data.amount_filled, dec!(0));
+        },
+        _ => panic!(),
+    }
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1), NonFungibleLocalId::integer(2)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_amend_price_partially_filled_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+    amend_order(1, dec!(1), Some(dec!("0.5")), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!("0.5"), dec!(1))]
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.price, dec!("0.5"));
+            assert_eq!(order_data.amount_filled, dec!(0));
+            assert_eq!(order_data.amount_total, dec!(1));
+        },
+        _ => panic!(),
+    }
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!("0.5"), &mut vars);
+}
+
+#[test]
+fn test_amend_increase_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!(2), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Amended order must not require more tokens.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_amend_bid_higher_price_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!(1), Some(dec!(2)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Amended order must not require more tokens.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_amend_cross_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    amend_order(2, dec!(1), Some(dec!(1)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Amended order would cross the order book.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_amend_filled_invalid() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    amend_order(1, dec!("0.5"), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order is not open.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_amend_zero_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!(0), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order size must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
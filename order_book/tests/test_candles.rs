@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+// Start time in seconds, aligned to the minute
+const START: i64 = 1_699_999_980;
+
+#[test]
+fn test_candle_none() {
+    let mut vars: Vars = setup();
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    assert!(get_candle(epoch, &mut vars).is_none());
+}
+
+#[test]
+fn test_candle_fills_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    let candle = get_candle(epoch, &mut vars).unwrap();
+
+    assert_eq!(candle.open, dec!(1));
+    assert_eq!(candle.high, dec!(2));
+    assert_eq!(candle.low, dec!(1));
+    assert_eq!(candle.close, dec!(2));
+    assert_eq!(candle.volume_x, dec!(2));
+    assert_eq!(candle.volume_y, dec!(3));
+    assert_eq!(candle.prev_epoch, None);
+}
+
+#[test]
+fn test_candle_fills_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order(vars.token_y, dec!(2), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(2), None, &mut vars).expect_commit_success();
+
+    let candle = get_candle(epoch, &mut vars).unwrap();
+
+    assert_eq!(candle.open, dec!(2));
+    assert_eq!(candle.high, dec!(2));
+    assert_eq!(candle.low, dec!(1));
+    assert_eq!(candle.close, dec!(1));
+    assert_eq!(candle.volume_x, dec!(2));
+    assert_eq!(candle.volume_y, dec!(3));
+}
+
+#[test]
+fn test_candle_links_prev_epoch() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 3));
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    let candle = get_candle(epoch + 3, &mut vars).unwrap();
+
+    assert_eq!(candle.prev_epoch, Some(epoch));
+}
+
+#[test]
+fn test_twap() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    set_time(1, START, &mut vars);
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    set_time(2, START + 600, &mut vars);
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+    set_time(3, START + 1800, &mut vars);
+
+    assert_eq!(get_twap(START, START + 1800, &mut vars), dec!(3000) / dec!(1800));
+    assert_eq!(get_twap(START, START + 600, &mut vars), dec!(1));
+    assert_eq!(get_twap(START + 600, START + 1800, &mut vars), dec!(2));
+    assert_eq!(get_twap(START + 300, START + 900, &mut vars), dec!("1.5"));
+}
+
+#[test]
+fn test_twap_weights_fills_by_time() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(100), &mut vars).expect_commit_success();
+    set_time(1, START, &mut vars);
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    set_time(2, START + 600, &mut vars);
+    market_order(vars.token_y, dec!(100), None, &mut vars).expect_commit_success();
+
+    // Price moved by the latest fill has not been held yet
+    assert_eq!(get_twap(START, START + 600, &mut vars), dec!(1));
+}
+
+#[test]
+fn test_twap_before_history_invalid() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    set_time(1, START + 60, &mut vars);
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    set_time(2, START + 120, &mut vars);
+
+    get_twap_receipt(START, START + 120, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("No price history for time")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_twap_no_fills_invalid() {
+    let mut vars: Vars = setup();
+    set_time(1, START + 60, &mut vars);
+
+    get_twap_receipt(START, START + 60, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("No price observations.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_twap_future_invalid() {
+    let mut vars: Vars = setup();
+    set_time(1, START, &mut vars);
+
+    get_twap_receipt(START, START + 60, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("End time must not be after the current time.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_twap_range_invalid() {
+    let mut vars: Vars = setup();
+    set_time(1, START, &mut vars);
+
+    get_twap_receipt(START, START, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Start time must be before end time.")
+            },
+            _ => false,
+        }
+    });
+}
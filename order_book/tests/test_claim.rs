@@ -887,3 +887,114 @@ fn test_claim_batch_receipt_consumed() {
         dec!(0)
     );
 }
+
+#[test]
+fn test_claim_filled_partially_filled_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    claim_filled(1, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(1));
+            assert_eq!(order_data.amount_claimed, dec!(1));
+            assert_eq!(order_data.amount_unclaimed, dec!(0));
+        },
+        _ => panic!(),
+    }
+
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(2));
+            assert_eq!(order_data.amount_claimed, dec!(1));
+            assert_eq!(order_data.amount_unclaimed, dec!(1));
+        },
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_claim_filled_partially_filled_then_cancel_y() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+    claim_filled(1, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+
+    assert_eq!(
+        get_amount_x(&mut vars),
+        dec!(0)
+    );
+
+    assert_eq!(
+        get_amount_y(&mut vars),
+        dec!(0)
+    );
+}
+
+#[test]
+fn test_claim_filled_twice_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    claim_filled(1, &mut vars).expect_commit_success();
+    claim_filled(1, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_claim_filled_unfilled_receipt_kept() {
+    let mut vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    claim_filled(1, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+
+    assert_eq!(
+        vars.test_runner.get_component_balance(vars.account_component, vars.order_receipt),
+        dec!(1)
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(0));
+            assert_eq!(order_data.amount_claimed, dec!(0));
+        },
+        _ => panic!(),
+    }
+}
@@ -0,0 +1,175 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+fn level(price: Decimal, amount_x: Decimal, amount_y: Decimal, cumulative_x: Decimal, cumulative_y: Decimal) -> DepthLevel {
+    DepthLevel {
+        price,
+        amount_x,
+        amount_y,
+        cumulative_x,
+        cumulative_y,
+    }
+}
+
+#[test]
+fn test_depth_empty() {
+    let mut vars: Vars = setup();
+
+    assert_eq!(
+        get_depth(10, None, &mut vars),
+        (vec![], vec![])
+    );
+}
+
+#[test]
+fn test_depth_no_bucket() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(2), dec!(3), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_depth(10, None, &mut vars),
+        (
+            vec![
+                level(dec!(2), dec!(1), dec!(2), dec!(1), dec!(2)),
+                level(dec!(3), dec!(2), dec!(6), dec!(3), dec!(8)),
+            ],
+            vec![
+                level(dec!(1), dec!(1), dec!(1), dec!(1), dec!(1)),
+            ],
+        )
+    );
+}
+
+#[test]
+fn test_depth_bucket_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!("1.1"), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!("1.9"), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!("2.5"), &mut vars).expect_commit_success();
+
+    let (asks, bids) = get_depth(10, Some(dec!(1)), &mut vars);
+
+    assert_eq!(
+        asks,
+        vec![
+            level(dec!(2), dec!(2), dec!(3), dec!(2), dec!(3)),
+            level(dec!(3), dec!(1), dec!("2.5"), dec!(3), dec!("5.5")),
+        ]
+    );
+    assert_eq!(bids, vec![]);
+}
+
+#[test]
+fn test_depth_bucket_y() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(2), dec!("0.5"), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(2), dec!("0.4"), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!("0.25"), &mut vars).expect_commit_success();
+
+    let (asks, bids) = get_depth(10, Some(dec!("0.2")), &mut vars);
+
+    assert_eq!(asks, vec![]);
+    assert_eq!(
+        bids,
+        vec![
+            level(dec!("0.4"), dec!(9), dec!(4), dec!(9), dec!(4)),
+            level(dec!("0.2"), dec!(4), dec!(1), dec!(13), dec!(5)),
+        ]
+    );
+}
+
+#[test]
+fn test_depth_levels() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(3), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!("0.5"), dec!("0.5"), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!("0.4"), dec!("0.4"), &mut vars).expect_commit_success();
+
+    let (asks, bids) = get_depth(1, None, &mut vars);
+
+    assert_eq!(asks, vec![level(dec!(1), dec!(1), dec!(1), dec!(1), dec!(1))]);
+    assert_eq!(bids, vec![level(dec!("0.5"), dec!(1), dec!("0.5"), dec!(1), dec!("0.5"))]);
+}
+
+#[test]
+fn test_depth_levels_zero() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_depth(0, None, &mut vars),
+        (vec![], vec![])
+    );
+}
+
+#[test]
+fn test_depth_bucket_size_invalid() {
+    let mut vars: Vars = setup();
+
+    get_depth_receipt(10, Some(dec!(0)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Bucket size must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_depth_expired_x() {
+    let mut vars: Vars = setup();
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(epoch), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(2), Some(epoch), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(3), &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+
+    assert_eq!(
+        get_depth(10, None, &mut vars),
+        (
+            vec![
+                level(dec!(1), dec!(2), dec!(2), dec!(2), dec!(2)),
+                level(dec!(3), dec!(1), dec!(3), dec!(3), dec!(5)),
+            ],
+            vec![],
+        )
+    );
+}
+
+#[test]
+fn test_depth_iceberg_reserve_x() {
+    let mut vars: Vars = setup();
+
+    limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_depth(10, None, &mut vars),
+        (
+            vec![
+                level(dec!(1), dec!(3), dec!(3), dec!(3), dec!(3)),
+            ],
+            vec![],
+        )
+    );
+}
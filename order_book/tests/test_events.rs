@@ -57,6 +57,9 @@ fn test_new_order_book_event() {
     assert_eq!(event.order_receipt_address, order_receipt);
     assert_eq!(event.token_x_address, vars.token_x);
     assert_eq!(event.token_y_address, vars.token_y);
+    assert_eq!(event.min_order_size, dec!(0));
+    assert_eq!(event.lot_size, None);
+    assert_eq!(event.price_precision, 5);
 }
 
 #[test]
@@ -85,6 +88,8 @@ fn test_limit_order_event_x() {
     assert!(event.is_ask);
     assert_eq!(event.amount, dec!(1));
     assert_eq!(event.price, dec!(1));
+    assert!(!event.is_post_only);
+    assert!(!event.is_repriced);
 }
 
 #[test]
@@ -113,6 +118,63 @@ fn test_limit_order_event_y() {
     assert!(!event.is_ask);
     assert_eq!(event.amount, dec!(1));
     assert_eq!(event.price, dec!(1));
+    assert!(!event.is_post_only);
+    assert!(!event.is_repriced);
+}
+
+#[test]
+fn test_limit_order_event_post_only_repriced() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    let events = limit_order_post_only(vars.token_x, dec!(1), dec!("0.5"), Some(PostOnlyMode::Reprice), &mut vars).expect_commit_success().application_events.clone();
+
+    let (_, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<LimitOrderEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<LimitOrderEvent>(&event_data).unwrap();
+
+    assert_eq!(event.order_id, NonFungibleLocalId::integer(2));
+    assert!(event.is_ask);
+    assert_eq!(event.amount, dec!(1));
+    assert_eq!(event.price, dec!("1.0001"));
+    assert!(event.is_post_only);
+    assert!(event.is_repriced);
+}
+
+#[test]
+fn test_amend_order_event_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    let events = amend_order(1, dec!(1), Some(dec!(2)), &mut vars).expect_commit_success().application_events.clone();
+
+    let (event_type_identifier, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<AmendOrderEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<AmendOrderEvent>(&event_data).unwrap();
+
+    assert_eq!(
+        event_type_identifier,
+        EventTypeIdentifier(
+            Emitter::Method(*vars.order_book_component.as_node_id(), ObjectModuleId::Main),
+            event_type_identifier.1.clone(),
+        )
+    );
+
+    assert_eq!(event.order_id, NonFungibleLocalId::integer(1));
+    assert!(event.is_ask);
+    assert_eq!(event.old_price, dec!(1));
+    assert_eq!(event.new_price, dec!(2));
+    assert_eq!(event.amount, dec!(1));
+    assert_eq!(event.amount_x, dec!(1));
+    assert_eq!(event.amount_y, dec!(0));
 }
 
 #[test]
@@ -179,6 +241,134 @@ fn test_market_order_event_y() {
         event.fills, 
         vec![(dec!(1), dec!(1)), (dec!(2), dec!(1)), (dec!(3), dec!(1))]
     );
+    assert_eq!(event.mode, ::order_book::market_order_mode::MarketOrderMode::ImmediateOrCancel(dec!(0)));
+}
+
+#[test]
+fn test_market_order_event_fill_or_kill() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(10), dec!(1), &mut vars).expect_commit_success();
+
+    let events = market_order_with_mode(vars.token_y, dec!(1), None, Some(MarketOrderMode::FillOrKill), None, &mut vars).expect_commit_success().application_events.clone();
+
+    let (_, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<MarketOrderEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<MarketOrderEvent>(&event_data).unwrap();
+
+    assert!(event.is_buy);
+    assert!(!event.is_limit_order);
+    assert_eq!(event.mode, ::order_book::market_order_mode::MarketOrderMode::FillOrKill);
+}
+
+#[test]
+fn test_market_order_event_limit_order() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(10), dec!(1), &mut vars).expect_commit_success();
+
+    let events = limit_order(vars.token_y, dec!(2), dec!(1), &mut vars).expect_commit_success().application_events.clone();
+
+    let (_, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<MarketOrderEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<MarketOrderEvent>(&event_data).unwrap();
+
+    assert!(event.is_buy);
+    assert!(event.is_limit_order);
+    assert_eq!(event.mode, ::order_book::market_order_mode::MarketOrderMode::ImmediateOrCancel(dec!(0)));
+}
+
+#[test]
+fn test_fill_event_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    let events = market_order(vars.token_x, dec!(2), None, &mut vars).expect_commit_success().application_events.clone();
+
+    let events: Vec<FillEvent> = events
+        .into_iter()
+        .filter(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<FillEvent>(event_type_identifier)
+        })
+        .map(|(_, event_data)| scrypto_decode::<FillEvent>(&event_data).unwrap())
+        .collect();
+
+    assert_eq!(events.len(), 1);
+    assert!(!events[0].is_buy);
+    assert_eq!(events[0].price, dec!(1));
+    assert_eq!(events[0].amount_x, dec!(2));
+    assert_eq!(events[0].amount_y, dec!(2));
+    assert_eq!(events[0].first_order_id, NonFungibleLocalId::integer(1));
+    assert_eq!(events[0].last_order_id, NonFungibleLocalId::integer(2));
+}
+
+#[test]
+fn test_fill_event_partly_filled_head_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!("0.5"), None, &mut vars).expect_commit_success();
+
+    let events = market_order(vars.token_x, dec!("0.25"), None, &mut vars).expect_commit_success().application_events.clone();
+
+    let (_, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<FillEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<FillEvent>(&event_data).unwrap();
+
+    assert_eq!(event.amount_x, dec!("0.25"));
+    assert_eq!(event.first_order_id, NonFungibleLocalId::integer(1));
+    assert_eq!(event.last_order_id, NonFungibleLocalId::integer(1));
+}
+
+#[test]
+fn test_fill_event_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+
+    let events = market_order(vars.token_y, dec!(10), None, &mut vars).expect_commit_success().application_events.clone();
+
+    let events: Vec<FillEvent> = events
+        .into_iter()
+        .filter(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<FillEvent>(event_type_identifier)
+        })
+        .map(|(_, event_data)| scrypto_decode::<FillEvent>(&event_data).unwrap())
+        .collect();
+
+    assert_eq!(events.len(), 2);
+    assert!(events[0].is_buy);
+    assert_eq!(events[0].price, dec!(1));
+    assert_eq!(events[0].amount_x, dec!(1));
+    assert_eq!(events[0].amount_y, dec!(1));
+    assert_eq!(events[0].first_order_id, NonFungibleLocalId::integer(1));
+    assert_eq!(events[0].last_order_id, NonFungibleLocalId::integer(1));
+    assert_eq!(events[1].price, dec!(2));
+    assert_eq!(events[1].amount_x, dec!(2));
+    assert_eq!(events[1].amount_y, dec!(4));
+    assert_eq!(events[1].first_order_id, NonFungibleLocalId::integer(2));
+    assert_eq!(events[1].last_order_id, NonFungibleLocalId::integer(3));
 }
 
 #[test]
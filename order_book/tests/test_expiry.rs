@@ -0,0 +1,252 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_expiry_not_expired_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(epoch + 1), &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(order_data) => assert_eq!(order_data.amount_filled, dec!(1)),
+        _ => panic!(),
+    }
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_expiry_skipped_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(epoch), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_eq!(
+        get_last_price(&mut vars),
+        dec!(2)
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Expired(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(0));
+            assert_eq!(order_data.amount_total, dec!(1));
+        },
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Claimed => (),
+        _ => panic!(),
+    }
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(2), &mut vars);
+}
+
+#[test]
+fn test_expiry_skipped_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_y, dec!(2), dec!(2), Some(epoch), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Expired(order_data) => assert_eq!(order_data.amount_filled, dec!(0)),
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_expiry_skipped_middle_of_limit_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(epoch), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+    market_order(vars.token_y, dec!("1.5"), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!("0.5"))]
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Expired(order_data) => assert_eq!(order_data.amount_filled, dec!(0)),
+        _ => panic!(),
+    }
+
+    match get_order_status(3, &mut vars) {
+        OrderStatus::Open(order_data) => assert_eq!(order_data.amount_filled, dec!("0.5")),
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([
+        NonFungibleLocalId::integer(1),
+        NonFungibleLocalId::integer(2),
+        NonFungibleLocalId::integer(3),
+    ]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_expiry_partially_filled_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_x, dec!(2), dec!(1), Some(epoch), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Expired(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(1));
+            assert_eq!(order_data.amount_total, dec!(2));
+        },
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+
+    assert_eq!(
+        get_amount_x(&mut vars),
+        dec!(0)
+    );
+
+    assert_eq!(
+        get_amount_y(&mut vars),
+        dec!(0)
+    );
+}
+
+#[test]
+fn test_expiry_status_before_removed_x() {
+    let mut vars: Vars = setup();
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(epoch), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(_) => (),
+        _ => panic!(),
+    }
+
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Expired(order_data) => assert_eq!(order_data.amount_filled, dec!(0)),
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+}
+
+#[test]
+fn test_expiry_new_order_after_removed_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(epoch), &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1), NonFungibleLocalId::integer(2)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_expiry_past_epoch_invalid() {
+    let mut vars: Vars = setup();
+    vars.test_runner.set_current_epoch(Epoch::of(10));
+
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(9), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Expiry epoch must not be before the current epoch.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -0,0 +1,191 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::order_book::events::*;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_iceberg_shows_display_amount_x() {
+    let mut vars: Vars = setup();
+
+    limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_total, dec!(3));
+            assert_eq!(order_data.amount_visible, dec!(1));
+            assert_eq!(order_data.amount_remaining, dec!(3));
+        },
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_iceberg_refills_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!("1.5"), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!("0.5"))]
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!("1.5"));
+            assert_eq!(order_data.amount_total, dec!(3));
+            assert_eq!(order_data.amount_visible, dec!("0.5"));
+            assert_eq!(order_data.amount_remaining, dec!("1.5"));
+        },
+        _ => panic!(),
+    }
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(3) + dec!("1.5"), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!("1.5"), &mut vars);
+}
+
+#[test]
+fn test_iceberg_refills_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order_iceberg(vars.token_y, dec!(3), dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(3), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(3));
+            assert_eq!(order_data.amount_total, dec!(3));
+        },
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_iceberg_refill_moves_to_tail_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order_iceberg(vars.token_x, dec!(2), dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(1));
+            assert_eq!(order_data.amount_visible, dec!(1));
+        },
+        _ => panic!(),
+    }
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_iceberg_claim_returns_reserve_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!("0.5"), None, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_iceberg_quote_includes_reserve_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    let quote = quote_market_order(vars.token_y, dec!(3), None, &mut vars);
+
+    assert_eq!(quote.amount_out, dec!(3));
+    assert_eq!(quote.amount_remaining, dec!(0));
+}
+
+#[test]
+fn test_iceberg_limit_order_event() {
+    let mut vars: Vars = setup();
+
+    let receipt = limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(1), &mut vars);
+    let (_, event_data) = receipt.expect_commit_success().application_events
+        .iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<LimitOrderEvent>(event_type_identifier)
+        })
+        .unwrap();
+    let event: LimitOrderEvent = scrypto_decode(event_data).unwrap();
+
+    assert_eq!(event.amount, dec!(3));
+    assert_eq!(event.display_amount, Some(dec!(1)));
+}
+
+#[test]
+fn test_iceberg_amend_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!(2), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Iceberg orders can not be amended.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_iceberg_display_amount_zero_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order_iceberg(vars.token_x, dec!(3), dec!(1), dec!(0), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Display amount must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -128,7 +128,7 @@ fn test_instantiation_with_limit_after() {
                     vars.order_book_package,
                     "OrderBook",
                     "new",
-                manifest_args!(owner_rule, user_rule, vars.token_x, vars.token_y, Some(lookup.address_reservation("order_book_reservation")))
+                manifest_args!(owner_rule, user_rule, AccessRule::DenyAll, vars.token_x, vars.token_y, dec!(0), None::<Decimal>, 5u32, Some(lookup.address_reservation("order_book_reservation")))
                 )
                 .call_method(
                     lookup.named_address("order_book_address"),
@@ -0,0 +1,146 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_ladder_flat_x() {
+    let mut vars: Vars = setup();
+
+    limit_order_ladder(vars.token_x, dec!(3), dec!(1), dec!(3), 3, LadderDistribution::Flat, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1)), (dec!(2), dec!(1)), (dec!(3), dec!(1))]
+    );
+
+    for id in 1..=3 {
+        match get_order_status(id, &mut vars) {
+            OrderStatus::Open(order_data) => {
+                assert_eq!(order_data.is_ask, true);
+                assert_eq!(order_data.amount_total, dec!(1));
+            },
+            _ => panic!(),
+        }
+    }
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(3), &mut vars);
+}
+
+#[test]
+fn test_ladder_linear_y() {
+    let mut vars: Vars = setup();
+
+    limit_order_ladder(vars.token_y, dec!(6), dec!("0.5"), dec!("0.3"), 3, LadderDistribution::Linear, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!("0.5"), dec!(2)), (dec!("0.4"), dec!(5)), (dec!("0.3"), dec!(10))]
+    );
+
+    assert_balance(vars.token_y, vars.amount_y - dec!(6), &mut vars);
+}
+
+#[test]
+fn test_ladder_geometric_x() {
+    let mut vars: Vars = setup();
+
+    limit_order_ladder(vars.token_x, dec!(7), dec!(1), dec!(2), 3, LadderDistribution::Geometric(dec!(2)), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1)), (dec!("1.5"), dec!(2)), (dec!(2), dec!(4))]
+    );
+}
+
+#[test]
+fn test_ladder_single_level_x() {
+    let mut vars: Vars = setup();
+
+    limit_order_ladder(vars.token_x, dec!(2), dec!(1), dec!(5), 1, LadderDistribution::Linear, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(2))]
+    );
+}
+
+#[test]
+fn test_ladder_lot_size_dust_x() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), Some(dec!(1)), 5, &mut vars).expect_commit_success();
+
+    limit_order_ladder(vars.token_x, dec!(10), dec!(1), dec!(3), 3, LadderDistribution::Flat, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(3)), (dec!(2), dec!(3)), (dec!(3), dec!(3))]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(9), &mut vars);
+}
+
+#[test]
+fn test_ladder_crossing_invalid_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_ladder(vars.token_x, dec!(3), dec!(1), dec!(3), 3, LadderDistribution::Flat, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Ladder must not cross the order book.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_ladder_min_order_size_invalid_y() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(2), None, 5, &mut vars).expect_commit_success();
+
+    limit_order_ladder(vars.token_y, dec!(3), dec!(1), dec!("0.5"), 3, LadderDistribution::Flat, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order size must be at least the minimum order size.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_ladder_levels_zero_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order_ladder(vars.token_x, dec!(3), dec!(1), dec!(3), 0, LadderDistribution::Flat, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Number of levels must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_ladder_geometric_ratio_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order_ladder(vars.token_x, dec!(3), dec!(1), dec!(3), 3, LadderDistribution::Geometric(dec!(0)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Geometric ratio must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
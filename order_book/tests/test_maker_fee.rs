@@ -0,0 +1,251 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::order_book::events::*;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+fn find_event<T: ScryptoEvent + ScryptoDecode>(
+    events: Vec<(EventTypeIdentifier, Vec<u8>)>,
+    vars: &mut Vars
+) -> T {
+    let (_, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<T>(event_type_identifier)
+        }).expect("Event not found");
+
+    scrypto_decode::<T>(&event_data).unwrap()
+}
+
+#[test]
+fn test_maker_rebate_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let event: ClaimOrderEvent = find_event(events, &mut vars);
+
+    assert_eq!(event.amount_filled, dec!(1));
+    assert_eq!(event.maker_fee, dec!(0));
+    assert_eq!(event.maker_rebate, dec!("0.0001"));
+    assert_eq!(event.amount_y, dec!("1.0001"));
+}
+
+#[test]
+fn test_maker_rebate_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(2), None, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let event: ClaimOrderEvent = find_event(events, &mut vars);
+
+    assert_eq!(event.amount_filled, dec!(1));
+    assert_eq!(event.maker_rebate, dec!("0.0001"));
+    assert_eq!(event.amount_x, dec!("1.0001"));
+}
+
+#[test]
+fn test_maker_rebate_protocol_fee_event_y() {
+    let mut vars: Vars = setup();
+    let fee = fee_controller::get_protocol_fee_default(&mut vars);
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+
+    let amount_fee = round_up(dec!(1) * fee, vars.divisibility_y);
+    let amount_rebate = round_down((dec!(1) - amount_fee) * dec!("0.0001"), vars.divisibility_y);
+    let events = market_order(vars.token_y, dec!(1), None, &mut vars)
+        .expect_commit_success()
+        .application_events
+        .clone();
+    let event: ProtocolFeeEvent = find_event(events, &mut vars);
+
+    assert_eq!(event.token_address, vars.token_y);
+    assert_eq!(event.taker_fee, amount_fee);
+    assert_eq!(event.maker_fee, dec!(0));
+    assert_eq!(event.maker_rebate, amount_rebate);
+    assert_eq!(event.amount, amount_fee - amount_rebate);
+}
+
+#[test]
+fn test_maker_fee_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, 100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let claim_event: ClaimOrderEvent = find_event(events.clone(), &mut vars);
+    let fee_event: ProtocolFeeEvent = find_event(events, &mut vars);
+
+    assert_eq!(claim_event.maker_fee, dec!("0.0001"));
+    assert_eq!(claim_event.maker_rebate, dec!(0));
+    assert_eq!(claim_event.amount_y, dec!("0.9999"));
+
+    assert_eq!(fee_event.token_address, vars.token_y);
+    assert_eq!(fee_event.amount, dec!("0.0001"));
+    assert_eq!(fee_event.taker_fee, dec!(0));
+    assert_eq!(fee_event.maker_fee, dec!("0.0001"));
+}
+
+#[test]
+fn test_maker_fee_claim_filled_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_maker_fee(&mut vars, 100i32);
+
+    limit_order(vars.token_y, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+    claim_filled(1, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!("0.0001"), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_maker_rebate_set_when_placed_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    fee_controller::set_maker_fee(&mut vars, 0i32);
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(4), None, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(2)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let event: ClaimOrderEvent = find_event(events, &mut vars);
+
+    assert_eq!(event.maker_rebate, dec!(0));
+    assert_eq!(event.amount_y, dec!(1));
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let event: ClaimOrderEvent = find_event(events, &mut vars);
+
+    // Rebate kept at the maker fee of the order after the maker fee was reset
+    assert_eq!(event.maker_rebate, dec!("0.0001"));
+    assert_eq!(event.amount_y, dec!("1.0001"));
+    assert_eq!(get_rebates(&mut vars), (dec!(0), dec!(0)));
+}
+
+#[test]
+fn test_maker_rebate_partial_fill_set_when_placed_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    fee_controller::set_maker_fee(&mut vars, 0i32);
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!("1.5"), None, &mut vars).expect_commit_success();
+    assert_eq!(get_rebates(&mut vars), (dec!(0), dec!("0.0001")));
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let event: ClaimOrderEvent = find_event(events, &mut vars);
+
+    assert_eq!(event.maker_rebate, dec!("0.0001"));
+    assert_eq!(get_rebates(&mut vars), (dec!(0), dec!(0)));
+}
+
+#[test]
+fn test_maker_rebate_increased_after_placed_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    fee_controller::set_maker_fee(&mut vars, -200i32);
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let event: ClaimOrderEvent = find_event(events, &mut vars);
+
+    assert_eq!(event.maker_rebate, dec!("0.0001"));
+    assert_eq!(get_rebates(&mut vars), (dec!(0), dec!(0)));
+}
+
+#[test]
+fn test_maker_rebate_not_kept_for_order_placed_without_rebate_y() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+    assert_eq!(get_rebates(&mut vars), (dec!(0), dec!(0)));
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    let events = claim_orders(ids, &mut vars).expect_commit_success().application_events.clone();
+    let event: ClaimOrderEvent = find_event(events, &mut vars);
+
+    assert_eq!(event.maker_rebate, dec!(0));
+    assert_eq!(event.amount_y, dec!(1));
+}
+
+#[test]
+fn test_sweep_rebates_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+    assert_eq!(get_rebates(&mut vars), (dec!(0), dec!("0.0001")));
+
+    let events = sweep_rebates(dec!(0), dec!("0.0001"), true, &mut vars)
+        .expect_commit_success()
+        .application_events
+        .clone();
+    let sweep_event: SweepRebatesEvent = find_event(events.clone(), &mut vars);
+
+    assert_eq!(sweep_event.amount_x, dec!(0));
+    assert_eq!(sweep_event.amount_y, dec!("0.0001"));
+    assert!(events.iter().all(|(event_type_identifier, _)| {
+        !vars.test_runner.is_event_name_equal::<ProtocolFeeEvent>(event_type_identifier)
+    }));
+    assert_eq!(get_rebates(&mut vars), (dec!(0), dec!(0)));
+}
+
+#[test]
+fn test_sweep_rebates_too_much_invalid() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    sweep_rebates(dec!(0), dec!("0.0002"), true, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => msg.contains("Not enough rebates to sweep."),
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_sweep_rebates_without_proof_invalid() {
+    let mut vars: Vars = setup();
+    fee_controller::set_maker_fee(&mut vars, -100i32);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    sweep_rebates(dec!(0), dec!("0.0001"), false, &mut vars).expect_auth_failure();
+}
@@ -1102,3 +1102,181 @@ fn test_market_other_token_invalid() {
         }
     });
 }
+
+#[test]
+fn test_market_fill_or_kill_filled_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(1), None, Some(MarketOrderMode::FillOrKill), None, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_market_fill_or_kill_filled_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_y, dec!(1), None, Some(MarketOrderMode::FillOrKill), None, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_market_fill_or_kill_more_than_book_invalid_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(2), None, Some(MarketOrderMode::FillOrKill), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Market order not fully filled.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_market_fill_or_kill_more_than_book_invalid_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_y, dec!(2), None, Some(MarketOrderMode::FillOrKill), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Market order not fully filled.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_market_fill_or_kill_stop_price_invalid_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!("0.5"), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(2), Some(dec!(1)), Some(MarketOrderMode::FillOrKill), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Market order not fully filled.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_market_immediate_or_cancel_min_met_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(10), None, Some(MarketOrderMode::ImmediateOrCancel(dec!(1))), None, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_market_immediate_or_cancel_min_not_met_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(10), None, Some(MarketOrderMode::ImmediateOrCancel(dec!("1.1"))), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Market order bought less than minimum amount.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_market_immediate_or_cancel_min_not_met_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_y, dec!(1), None, Some(MarketOrderMode::ImmediateOrCancel(dec!(1))), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Market order bought less than minimum amount.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_market_max_amount_in_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(5), None, None, Some(dec!(2)), &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(2), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(8), &mut vars);
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(8))]
+    );
+}
+
+#[test]
+fn test_market_max_amount_in_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_y, dec!(5), None, None, Some(dec!(2)), &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(8), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(2), &mut vars);
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(8))]
+    );
+}
+
+#[test]
+fn test_market_max_amount_in_fill_or_kill_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(2), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(5), None, Some(MarketOrderMode::FillOrKill), Some(dec!(2)), &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(2), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_market_max_amount_in_zero_invalid_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_mode(vars.token_x, dec!(1), None, None, Some(dec!(0)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Maximum input must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -0,0 +1,276 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_order_size_defaults() {
+    let mut vars: Vars = setup();
+
+    assert_eq!(get_min_order_size(&mut vars), dec!(0));
+    assert_eq!(get_lot_size(&mut vars), None);
+    assert_eq!(get_price_precision(&mut vars), 5);
+}
+
+#[test]
+fn test_order_size_config() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(10), Some(dec!("0.1")), 3, &mut vars).expect_commit_success();
+
+    assert_eq!(get_min_order_size(&mut vars), dec!(10));
+    assert_eq!(get_lot_size(&mut vars), Some(dec!("0.1")));
+    assert_eq!(get_price_precision(&mut vars), 3);
+}
+
+#[test]
+fn test_order_size_precision_invalid() {
+    let mut vars: Vars = setup();
+
+    new_order_book_with_config(dec!(0), None, 0, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Price precision must be between 1 and 5.")
+            },
+            _ => false,
+        }
+    });
+    new_order_book_with_config(dec!(0), None, 6, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Price precision must be between 1 and 5.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_order_size_lot_size_invalid() {
+    let mut vars: Vars = setup();
+
+    new_order_book_with_config(dec!(0), Some(dec!(0)), 5, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Lot size must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_order_size_min_order_size_invalid() {
+    let mut vars: Vars = setup();
+
+    new_order_book_with_config(dec!(-1), None, 5, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Minimum order size must not be negative.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_order_size_price_precision_x() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), None, 3, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_x, dec!(1), dec!("1.2345"), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!("1.23"), dec!(1))]
+    );
+}
+
+#[test]
+fn test_order_size_price_precision_reprice_x() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), None, 3, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_x, dec!(1), dec!("0.5"), Some(PostOnlyMode::Reprice), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!("1.01"), dec!(1))]
+    );
+}
+
+#[test]
+fn test_order_size_price_precision_amend_y() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), None, 2, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!(1), Some(dec!("0.789")), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!("0.78"), dec!(1))]
+    );
+}
+
+#[test]
+fn test_order_size_lot_size_x() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), Some(dec!("0.1")), 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_x, dec!("1.25"), dec!(1), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!("1.2"))]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!("1.2"), &mut vars);
+}
+
+#[test]
+fn test_order_size_lot_size_y() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), Some(dec!(1)), 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_y, dec!(5), dec!(2), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(2), dec!(2))]
+    );
+
+    assert_balance(vars.token_y, vars.amount_y - dec!(4), &mut vars);
+}
+
+#[test]
+fn test_order_size_lot_size_limit_invalid() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), Some(dec!(1)), 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_x, dec!("0.5"), dec!(1), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order size must be at least the lot size.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_order_size_lot_size_market_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    new_order_book_with_config(dec!(0), Some(dec!(1)), 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_y, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!("2.5"), None, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(2), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(8), &mut vars);
+}
+
+#[test]
+fn test_order_size_lot_size_market_x_after_fee() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), Some(dec!(1)), 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_y, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(3), None, &mut vars).expect_commit_success();
+
+    // Fills are whole lots of the tokens left after the fee
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(8))]
+    );
+}
+
+#[test]
+fn test_order_size_min_order_size_market_x_no_bids_invalid() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(10), None, 5, &mut vars).expect_commit_success();
+
+    market_order(vars.token_x, dec!(100), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order size must be at least the minimum order size.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_order_size_lot_size_amend_invalid() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(0), Some(dec!("0.1")), 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    amend_order(1, dec!("0.55"), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order size must be a multiple of the lot size.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_order_size_min_order_size_limit_x() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(10), None, 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_x, dec!(5), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(4), dec!(2), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order size must be at least the minimum order size.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_order_size_min_order_size_market_y() {
+    let mut vars: Vars = setup();
+    new_order_book_with_config(dec!(10), None, 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_x, dec!(20), dec!(1), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(5), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order size must be at least the minimum order size.")
+            },
+            _ => false,
+        }
+    });
+    market_order(vars.token_y, dec!(10), None, &mut vars).expect_commit_success();
+}
+
+#[test]
+fn test_order_size_min_order_size_remainder_returned_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    new_order_book_with_config(dec!(1), None, 5, &mut vars).expect_commit_success();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!("1.5"), dec!(1), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
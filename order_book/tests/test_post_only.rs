@@ -0,0 +1,234 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_post_only_reject_no_cross_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_x, dec!(1), dec!(2), Some(PostOnlyMode::Reject), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(2), dec!(1))]
+    );
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+}
+
+#[test]
+fn test_post_only_reject_no_cross_y() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_y, dec!(1), dec!(1), Some(PostOnlyMode::Reject), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(2), dec!(1))]
+    );
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+}
+
+#[test]
+fn test_post_only_reject_empty_book_x() {
+    let mut vars: Vars = setup();
+
+    limit_order_post_only(vars.token_x, dec!(1), dec!(1), Some(PostOnlyMode::Reject), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+}
+
+#[test]
+fn test_post_only_reject_cross_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_x, dec!(1), dec!(1), Some(PostOnlyMode::Reject), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Post only order would cross the order book.")
+            },
+            _ => false,
+        }
+    });
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+}
+
+#[test]
+fn test_post_only_reject_cross_y() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_y, dec!(1), dec!(2), Some(PostOnlyMode::Reject), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Post only order would cross the order book.")
+            },
+            _ => false,
+        }
+    });
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![]
+    );
+}
+
+#[test]
+fn test_post_only_reprice_cross_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_x, dec!(1), dec!("0.5"), Some(PostOnlyMode::Reprice), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!("1.0001"), dec!(1))]
+    );
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_post_only_reprice_cross_y() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_y, dec!("0.99999"), dec!(2), Some(PostOnlyMode::Reprice), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!("0.99999"), dec!(1))]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!("0.99999"), &mut vars);
+}
+
+#[test]
+fn test_post_only_reprice_no_cross_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_x, dec!(1), dec!(3), Some(PostOnlyMode::Reprice), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(3), dec!(1))]
+    );
+}
+
+#[test]
+fn test_post_only_reprice_max_price_invalid_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!("100000000000"), &mut vars).expect_commit_success();
+    limit_order_post_only(vars.token_x, dec!(1), dec!(1), Some(PostOnlyMode::Reprice), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("No price available for post only order.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_post_only_batch_reject_cross() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(1), dec!(2), &mut vars).expect_commit_success();
+
+    let positions = vec![
+        (vars.token_x, dec!(1), dec!(3)),
+        (vars.token_x, dec!(1), dec!(2)),
+    ];
+
+    limit_order_batch_post_only(positions, Some(PostOnlyMode::Reject), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Post only order would cross the order book.")
+            },
+            _ => false,
+        }
+    });
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+}
+
+#[test]
+fn test_post_only_batch_reprice_mix() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(2), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(3), &mut vars).expect_commit_success();
+
+    let positions = vec![
+        (vars.token_x, dec!(1), dec!(1)),
+        (vars.token_x, dec!(1), dec!(4)),
+        (vars.token_y, dec!(2), dec!(5)),
+    ];
+
+    limit_order_batch_post_only(positions, Some(PostOnlyMode::Reprice), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!("2.0001"), dec!(1)), (dec!(3), dec!(1)), (dec!(4), dec!(1))]
+    );
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!(2), dec!(2))]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(3), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(4), &mut vars);
+}
@@ -147,6 +147,41 @@ fn test_max() {
     assert_eq!(Price::EXP_MAX, Price::MAX.get_exp());
 }
 
+#[test]
+fn test_price_increment() {
+    assert_eq!(Price::from(dec!("1.2345")).increment(), Some(Price::from(dec!("1.2346"))));
+    assert_eq!(Price::from(dec!("9.9999")).increment(), Some(Price::from(dec!(10))));
+    assert_eq!(Price::MAX.increment(), None);
+}
+
+#[test]
+fn test_price_decrement() {
+    assert_eq!(Price::from(dec!("1.2345")).decrement(), Some(Price::from(dec!("1.2344"))));
+    assert_eq!(Price::from(dec!(10)).decrement(), Some(Price::from(dec!("9.9999"))));
+    assert_eq!(Price::MIN.decrement(), None);
+}
+
+#[test]
+fn test_price_truncate() {
+    assert_eq!(Price::from(dec!("1.2345")).truncate(5), Price::from(dec!("1.2345")));
+    assert_eq!(Price::from(dec!("1.2345")).truncate(3), Price::from(dec!("1.23")));
+    assert_eq!(Price::from(dec!("98765")).truncate(1), Price::from(dec!("90000")));
+}
+
+#[test]
+fn test_price_increment_with_precision() {
+    assert_eq!(Price::from(dec!("1.23")).increment_with_precision(3), Some(Price::from(dec!("1.24"))));
+    assert_eq!(Price::from(dec!("9.99")).increment_with_precision(3), Some(Price::from(dec!(10))));
+    assert_eq!(Price::MAX.increment_with_precision(3), None);
+}
+
+#[test]
+fn test_price_decrement_with_precision() {
+    assert_eq!(Price::from(dec!("1.23")).decrement_with_precision(3), Some(Price::from(dec!("1.22"))));
+    assert_eq!(Price::from(dec!(10)).decrement_with_precision(3), Some(Price::from(dec!("9.99"))));
+    assert_eq!(Price::MIN.decrement_with_precision(3), None);
+}
+
 #[test]
 fn round_to_price_range_no_round() {
     let price = dec!("1.23456");
@@ -0,0 +1,198 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_quote_empty_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    let quote = quote_market_order(vars.token_x, dec!(1), None, &mut vars);
+
+    assert_eq!(quote.amount_out, dec!(0));
+    assert_eq!(quote.average_price, None);
+    assert_eq!(quote.protocol_fee, dec!(0));
+    assert_eq!(quote.worst_price, None);
+    assert_eq!(quote.amount_remaining, dec!(1));
+}
+
+#[test]
+fn test_quote_empty_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    let quote = quote_market_order(vars.token_y, dec!(1), None, &mut vars);
+
+    assert_eq!(quote.amount_out, dec!(0));
+    assert_eq!(quote.average_price, None);
+    assert_eq!(quote.protocol_fee, dec!(0));
+    assert_eq!(quote.worst_price, None);
+    assert_eq!(quote.amount_remaining, dec!(1));
+}
+
+#[test]
+fn test_quote_multiple_limits_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(2), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    let quote = quote_market_order(vars.token_x, dec!("1.5"), None, &mut vars);
+
+    assert_eq!(quote.amount_out, dec!("2.5"));
+    assert_eq!(quote.average_price, Some(dec!("2.5") / dec!("1.5")));
+    assert_eq!(quote.protocol_fee, dec!(0));
+    assert_eq!(quote.worst_price, Some(dec!(1)));
+    assert_eq!(quote.amount_remaining, dec!(0));
+
+    market_order(vars.token_x, dec!("1.5"), None, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!("1.5"), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(3) + quote.amount_out, &mut vars);
+}
+
+#[test]
+fn test_quote_multiple_limits_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+
+    let quote = quote_market_order(vars.token_y, dec!(2), None, &mut vars);
+
+    assert_eq!(quote.amount_out, dec!("1.5"));
+    assert_eq!(quote.average_price, Some(dec!(2) / dec!("1.5")));
+    assert_eq!(quote.protocol_fee, dec!(0));
+    assert_eq!(quote.worst_price, Some(dec!(2)));
+    assert_eq!(quote.amount_remaining, dec!(0));
+
+    market_order(vars.token_y, dec!(2), None, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(2) + quote.amount_out, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(2), &mut vars);
+}
+
+#[test]
+fn test_quote_stop_price_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(2), dec!(2), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    let quote = quote_market_order(vars.token_x, dec!(3), Some(dec!(2)), &mut vars);
+
+    assert_eq!(quote.amount_out, dec!(2));
+    assert_eq!(quote.average_price, Some(dec!(2)));
+    assert_eq!(quote.worst_price, Some(dec!(2)));
+    assert_eq!(quote.amount_remaining, dec!(2));
+}
+
+#[test]
+fn test_quote_stop_price_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+
+    let quote = quote_market_order(vars.token_y, dec!(3), Some(dec!(1)), &mut vars);
+
+    assert_eq!(quote.amount_out, dec!(1));
+    assert_eq!(quote.average_price, Some(dec!(1)));
+    assert_eq!(quote.worst_price, Some(dec!(1)));
+    assert_eq!(quote.amount_remaining, dec!(2));
+}
+
+#[test]
+fn test_quote_fee_filled_y() {
+    let mut vars: Vars = setup();
+    let fee = fee_controller::get_protocol_fee_default(&mut vars);
+
+    limit_order(vars.token_x, dec!(2), dec!(1), &mut vars).expect_commit_success();
+
+    let quote = quote_market_order(vars.token_y, dec!(1), None, &mut vars);
+    let amount_fee = dec!(1) * fee;
+
+    assert_eq!(quote.amount_out, dec!(1) - amount_fee);
+    assert_eq!(quote.average_price, Some(dec!(1)));
+    assert_eq!(quote.protocol_fee, amount_fee);
+    assert_eq!(quote.amount_remaining, dec!(0));
+}
+
+#[test]
+fn test_quote_fee_partially_filled_x() {
+    let mut vars: Vars = setup();
+    let fee = fee_controller::get_protocol_fee_default(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+
+    let quote = quote_market_order(vars.token_x, dec!(2), None, &mut vars);
+    let amount_fee = dec!(2) * fee;
+    let amount_input = dec!(2) - amount_fee;
+    let amount_unfilled = amount_input - dec!(1);
+    let amount_fee_unused = amount_unfilled / amount_input * amount_fee;
+
+    assert_eq!(quote.amount_out, dec!(1));
+    assert_eq!(quote.protocol_fee, amount_fee - amount_fee_unused);
+    assert_eq!(quote.amount_remaining, amount_unfilled + amount_fee_unused);
+}
+
+#[test]
+fn test_quote_does_not_change_state() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+
+    quote_market_order(vars.token_y, dec!(2), None, &mut vars);
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1)), (dec!(2), dec!(1))]
+    );
+
+    assert_eq!(
+        get_last_price(&mut vars),
+        Decimal::from(order_book::price::Price::MIN)
+    );
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => assert_eq!(order_data.amount_filled, dec!(0)),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_quote_skips_expired_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    let epoch = vars.test_runner.get_current_epoch().number();
+
+    limit_order_with_expiry(vars.token_x, dec!(1), dec!(1), Some(epoch), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(2), &mut vars).expect_commit_success();
+    vars.test_runner.set_current_epoch(Epoch::of(epoch + 1));
+
+    let quote = quote_market_order(vars.token_y, dec!(3), None, &mut vars);
+
+    assert_eq!(quote.amount_out, dec!(2));
+    assert_eq!(quote.worst_price, Some(dec!(2)));
+    assert_eq!(quote.amount_remaining, dec!(0));
+
+    market_order(vars.token_y, dec!(3), None, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+}
@@ -0,0 +1,281 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+fn setup_badge(vars: &mut Vars) -> ResourceAddress {
+    fee_controller::set_protocol_fee_default_zero(vars);
+    vars.test_runner.create_non_fungible_resource(vars.account_component)
+}
+
+#[test]
+fn test_self_trade_cancel_resting_x() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!(1), Some((badge, 1)), Some(SelfTradeMode::CancelResting), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Canceled(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(0));
+            assert_eq!(order_data.amount_total, dec!(1));
+        },
+        _ => panic!(),
+    }
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1), NonFungibleLocalId::integer(2)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_self_trade_cancel_resting_partly_filled_y() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_y, dec!(2), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_x, dec!(1), Some((badge, 1)), Some(SelfTradeMode::CancelResting), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Canceled(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(1));
+            assert_eq!(order_data.amount_unclaimed, dec!(1));
+        },
+        _ => panic!(),
+    }
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![]
+    );
+}
+
+#[test]
+fn test_self_trade_cancel_incoming_x() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!(3), Some((badge, 1)), Some(SelfTradeMode::CancelIncoming), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(0));
+        },
+        _ => panic!(),
+    }
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(2))]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(3), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_self_trade_skip_maker_x() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!(2), Some((badge, 1)), Some(SelfTradeMode::SkipMaker), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(0));
+            assert_eq!(order_data.amount_remaining, dec!(1));
+        },
+        _ => panic!(),
+    }
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+
+    assert_balance(vars.token_y, vars.amount_y - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_self_trade_skip_maker_keeps_position_y() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_y, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_x, dec!(1), Some((badge, 1)), Some(SelfTradeMode::SkipMaker), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Open(_) => (),
+        _ => panic!(),
+    }
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+
+    // Skipped order is still ahead of the order placed after it
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(1));
+        },
+        _ => panic!(),
+    }
+    match get_order_status(3, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(0));
+        },
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_self_trade_skip_maker_keeps_position_partial_fill_x() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!("0.5"), Some((badge, 1)), Some(SelfTradeMode::SkipMaker), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!(1));
+        },
+        _ => panic!(),
+    }
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Open(order_data) => {
+            assert_eq!(order_data.amount_filled, dec!("0.5"));
+            assert_eq!(order_data.amount_remaining, dec!("0.5"));
+        },
+        _ => panic!(),
+    }
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(2)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+}
+
+#[test]
+fn test_self_trade_different_owner_matched_x() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!(1), Some((badge, 2)), Some(SelfTradeMode::CancelIncoming), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_self_trade_no_mode_matched_x() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_self_trade_limit_order_crossing_y() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    limit_order_with_owner(vars.token_y, dec!(1), dec!(1), Some((badge, 1)), Some(SelfTradeMode::CancelIncoming), &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![(dec!(1), dec!(1))]
+    );
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_self_trade_amend_canceled_invalid() {
+    let mut vars: Vars = setup();
+    let badge = setup_badge(&mut vars);
+
+    limit_order_with_owner(vars.token_x, dec!(1), dec!(1), Some((badge, 1)), None, &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!(1), Some((badge, 1)), Some(SelfTradeMode::CancelResting), &mut vars).expect_commit_success();
+    amend_order(1, dec!("0.5"), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order is not open.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_self_trade_mode_without_proof_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    market_order_with_owner(vars.token_y, dec!(1), None, Some(SelfTradeMode::CancelResting), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Self trade prevention requires an owner proof.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -0,0 +1,141 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::order_book::events::*;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+fn expect_not_active(receipt: TransactionReceipt) {
+    receipt.expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order book is not active.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_state_default_active() {
+    let mut vars: Vars = setup();
+
+    assert_eq!(get_state(&mut vars), OrderBookState::Active);
+}
+
+#[test]
+fn test_set_state() {
+    let mut vars: Vars = setup();
+
+    set_state(OrderBookState::CancelOnly, true, &mut vars).expect_commit_success();
+    assert_eq!(get_state(&mut vars), OrderBookState::CancelOnly);
+
+    set_state(OrderBookState::Halted, true, &mut vars).expect_commit_success();
+    assert_eq!(get_state(&mut vars), OrderBookState::Halted);
+
+    set_state(OrderBookState::Active, true, &mut vars).expect_commit_success();
+    assert_eq!(get_state(&mut vars), OrderBookState::Active);
+}
+
+#[test]
+fn test_set_state_without_proof_invalid() {
+    let mut vars: Vars = setup();
+
+    set_state(OrderBookState::Halted, false, &mut vars).expect_auth_failure();
+    assert_eq!(get_state(&mut vars), OrderBookState::Active);
+}
+
+#[test]
+fn test_set_state_event() {
+    let mut vars: Vars = setup();
+
+    let receipt = set_state(OrderBookState::CancelOnly, true, &mut vars);
+    let (_, event_data) = receipt.expect_commit_success().application_events
+        .iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<SetStateEvent>(event_type_identifier)
+        })
+        .unwrap();
+    let event: SetStateEvent = scrypto_decode(event_data).unwrap();
+
+    assert_eq!(event.state, ::order_book::order_book_state::OrderBookState::CancelOnly);
+}
+
+#[test]
+fn test_cancel_only_orders_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    set_state(OrderBookState::CancelOnly, true, &mut vars).expect_commit_success();
+
+    expect_not_active(limit_order(vars.token_x, dec!(1), dec!(1), &mut vars));
+    expect_not_active(limit_order_batch(vec![(vars.token_y, dec!(1), dec!("0.5"))], &mut vars));
+    expect_not_active(market_order(vars.token_y, dec!(1), None, &mut vars));
+    expect_not_active(amend_order(1, dec!("0.5"), None, &mut vars));
+    expect_not_active(execute_trigger_orders(vec![], vars.account_component, vars.public_key, &mut vars));
+}
+
+#[test]
+fn test_cancel_only_claim_orders() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(1), dec!("0.5"), &mut vars).expect_commit_success();
+    set_state(OrderBookState::CancelOnly, true, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1), NonFungibleLocalId::integer(2)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x, &mut vars);
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_halted_claim_orders_invalid() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    set_state(OrderBookState::Halted, true, &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Order book is halted.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_active_after_halted() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    set_state(OrderBookState::Halted, true, &mut vars).expect_commit_success();
+    set_state(OrderBookState::Active, true, &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Filled(_) => (),
+        _ => panic!(),
+    }
+}
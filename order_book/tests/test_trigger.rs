@@ -0,0 +1,259 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::order_book::events::*;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::order_book::*;
+pub use crate::common::fee_controller;
+
+fn execute_as_keeper(ids: Vec<u64>, vars: &mut Vars) -> TransactionReceipt {
+    let account = vars.admin_account_component;
+    let public_key = vars.admin_public_key;
+    execute_trigger_orders(ids, account, public_key, vars)
+}
+
+fn keeper_balance(resource: ResourceAddress, vars: &mut Vars) -> Decimal {
+    vars.test_runner.get_component_balance(vars.admin_account_component, resource)
+}
+
+#[test]
+fn test_trigger_order_pending_x() {
+    let mut vars: Vars = setup();
+
+    trigger_order(vars.token_x, dec!(2), dec!(1), TriggerMode::StopLoss, None, dec!("0.1"), &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Pending(order_data) => {
+            assert_eq!(order_data.is_ask, true);
+            assert_eq!(order_data.price, dec!(1));
+            assert_eq!(order_data.amount_total, dec!("1.9"));
+            assert_eq!(order_data.amount_filled, dec!(0));
+        },
+        _ => panic!(),
+    }
+
+    assert_eq!(
+        get_ask_limits(None, None, None, &mut vars),
+        vec![]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(2), &mut vars);
+}
+
+#[test]
+fn test_trigger_order_stop_loss_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    trigger_order(vars.token_x, dec!("2.1"), dec!(1), TriggerMode::StopLoss, None, dec!("0.1"), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+    execute_as_keeper(vec![2], &mut vars).expect_commit_success();
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Filled(order_data) => {
+            assert_eq!(order_data.price, dec!(1));
+            assert_eq!(order_data.amount_filled, dec!(2));
+        },
+        _ => panic!(),
+    }
+    assert_eq!(keeper_balance(vars.token_x, &mut vars), dec!("0.1"));
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(2)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!("3.1"), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!(10) + dec!(1) + dec!(2), &mut vars);
+}
+
+#[test]
+fn test_trigger_order_take_profit_y() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_x, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    trigger_order(vars.token_y, dec!("2.1"), dec!(1), TriggerMode::TakeProfit, None, dec!("0.1"), &mut vars).expect_commit_success();
+    market_order(vars.token_y, dec!(1), None, &mut vars).expect_commit_success();
+    execute_as_keeper(vec![2], &mut vars).expect_commit_success();
+
+    assert_eq!(keeper_balance(vars.token_y, &mut vars), dec!("0.1"));
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(2)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(10) + dec!(1) + dec!(2), &mut vars);
+    assert_balance(vars.token_y, vars.amount_y - dec!("3.1"), &mut vars);
+}
+
+#[test]
+fn test_trigger_order_stop_price_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!("0.5"), dec!("0.5"), &mut vars).expect_commit_success();
+    trigger_order(vars.token_x, dec!(2), dec!(1), TriggerMode::StopLoss, Some(dec!("0.9")), dec!(0), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!("0.5"), None, &mut vars).expect_commit_success();
+    execute_as_keeper(vec![3], &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(3)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_bid_limits(None, None, None, &mut vars),
+        vec![(dec!("0.5"), dec!(1))]
+    );
+
+    assert_balance(vars.token_x, vars.amount_x - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_trigger_order_not_triggered_skipped_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    trigger_order(vars.token_x, dec!(2), dec!("0.5"), TriggerMode::StopLoss, None, dec!("0.1"), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+    execute_as_keeper(vec![2], &mut vars).expect_commit_success();
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Pending(_) => (),
+        _ => panic!(),
+    }
+    assert_eq!(keeper_balance(vars.token_x, &mut vars), dec!(0));
+}
+
+#[test]
+fn test_trigger_order_not_triggered_before_first_fill_x() {
+    let mut vars: Vars = setup();
+
+    limit_order(vars.token_y, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    trigger_order(vars.token_x, dec!(2), dec!(1), TriggerMode::StopLoss, None, dec!("0.1"), &mut vars).expect_commit_success();
+    execute_as_keeper(vec![2], &mut vars).expect_commit_success();
+
+    match get_order_status(2, &mut vars) {
+        OrderStatus::Pending(_) => (),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_trigger_order_not_triggered_same_transaction_x() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(1), dec!(1), &mut vars).expect_commit_success();
+    limit_order(vars.token_y, dec!(5), dec!("0.5"), &mut vars).expect_commit_success();
+    market_order(vars.token_x, dec!("0.5"), None, &mut vars).expect_commit_success();
+    trigger_order(vars.token_x, dec!("2.1"), dec!("0.5"), TriggerMode::StopLoss, None, dec!("0.1"), &mut vars).expect_commit_success();
+    market_order_and_execute_trigger_orders(vars.token_x, dec!(1), vec![3], &mut vars).expect_commit_success();
+
+    assert_eq!(get_last_price(&mut vars), dec!("0.5"));
+    match get_order_status(3, &mut vars) {
+        OrderStatus::Pending(_) => (),
+        _ => panic!(),
+    }
+
+    execute_as_keeper(vec![3], &mut vars).expect_commit_success();
+
+    match get_order_status(3, &mut vars) {
+        OrderStatus::Filled(order_data) => {
+            assert_eq!(order_data.price, dec!("0.5"));
+            assert_eq!(order_data.amount_filled, dec!(2));
+        },
+        _ => panic!(),
+    }
+    assert_eq!(keeper_balance(vars.token_x, &mut vars), dec!("0.1"));
+}
+
+#[test]
+fn test_trigger_order_escrow_not_in_amounts() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+
+    limit_order(vars.token_y, dec!(10), dec!(1), &mut vars).expect_commit_success();
+    trigger_order(vars.token_x, dec!("2.1"), dec!(1), TriggerMode::StopLoss, None, dec!("0.1"), &mut vars).expect_commit_success();
+    trigger_order(vars.token_y, dec!(2), dec!(2), TriggerMode::StopLoss, None, dec!(0), &mut vars).expect_commit_success();
+
+    assert_eq!(get_amount_x(&mut vars), dec!(0));
+    assert_eq!(get_amount_y(&mut vars), dec!(10));
+
+    market_order(vars.token_x, dec!(1), None, &mut vars).expect_commit_success();
+    execute_as_keeper(vec![2], &mut vars).expect_commit_success();
+
+    assert_eq!(get_amount_x(&mut vars), dec!(3));
+    assert_eq!(get_amount_y(&mut vars), dec!(7));
+}
+
+#[test]
+fn test_trigger_order_claim_pending_y() {
+    let mut vars: Vars = setup();
+
+    trigger_order(vars.token_y, dec!(2), dec!(1), TriggerMode::StopLoss, None, dec!("0.1"), &mut vars).expect_commit_success();
+
+    let ids = BTreeSet::from([NonFungibleLocalId::integer(1)]);
+    claim_orders(ids, &mut vars).expect_commit_success();
+
+    match get_order_status(1, &mut vars) {
+        OrderStatus::Claimed => (),
+        _ => panic!(),
+    }
+    assert_balance(vars.token_y, vars.amount_y, &mut vars);
+}
+
+#[test]
+fn test_trigger_order_event() {
+    let mut vars: Vars = setup();
+
+    let receipt = trigger_order(vars.token_x, dec!(2), dec!(1), TriggerMode::TakeProfit, Some(dec!("0.9")), dec!("0.1"), &mut vars);
+    let (_, event_data) = receipt.expect_commit_success().application_events
+        .iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<TriggerOrderEvent>(event_type_identifier)
+        })
+        .unwrap();
+    let event: TriggerOrderEvent = scrypto_decode(event_data).unwrap();
+
+    assert_eq!(event.order_id, NonFungibleLocalId::integer(1));
+    assert_eq!(event.is_ask, true);
+    assert_eq!(event.trigger_price, dec!(1));
+    assert_eq!(event.stop_price, Some(dec!("0.9")));
+    assert_eq!(event.amount_in, dec!("1.9"));
+    assert_eq!(event.bounty, dec!("0.1"));
+}
+
+#[test]
+fn test_trigger_order_amend_invalid() {
+    let mut vars: Vars = setup();
+
+    trigger_order(vars.token_x, dec!(2), dec!(1), TriggerMode::StopLoss, None, dec!(0), &mut vars).expect_commit_success();
+    amend_order(1, dec!(1), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Trigger orders can not be amended.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_trigger_order_negative_bounty_invalid() {
+    let mut vars: Vars = setup();
+
+    trigger_order(vars.token_x, dec!(2), dec!(1), TriggerMode::StopLoss, None, dec!(-1), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Bounty must not be negative.")
+            },
+            _ => false,
+        }
+    });
+}
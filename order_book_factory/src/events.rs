@@ -1,5 +1,7 @@
 use scrypto::prelude::*;
 
+use crate::order_book_state::*;
+
 /// Event emitted when the owner rule default is set.
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct SetOwnerRuleDefaultEvent {
@@ -21,6 +23,15 @@ pub struct SetTokenValidatorEvent {
     pub token_validator_address: ComponentAddress,
 }
 
+/// Event emitted when the state of order books is set.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SetOrderBooksStateEvent {
+    /// The addresses of the order books whose state was set.
+    pub order_book_addresses: Vec<ComponentAddress>,
+    /// The new state of the order books.
+    pub state: OrderBookState,
+}
+
 /// Event emitted when a new order book is created.
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct NewOrderBookEvent {
@@ -32,4 +43,10 @@ pub struct NewOrderBookEvent {
     pub token_x_address: ResourceAddress,
     /// The address of the token x for the new order book.
     pub token_y_address: ResourceAddress,
+    /// The minimum value of an order for the new order book valued in tokens y.
+    pub min_order_size: Decimal,
+    /// The optional lot size for the new order book valued in tokens x.
+    pub lot_size: Option<Decimal>,
+    /// The price precision for the new order book in significant figures.
+    pub price_precision: u32,
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
 pub mod events;
 pub mod list;
+pub mod order_book_state;
 pub mod order_book_factory;
\ No newline at end of file
@@ -2,6 +2,7 @@ use scrypto::prelude::*;
 
 use crate::events::*;
 use crate::list::*;
+use crate::order_book_state::*;
 
 pub type Pair = (ResourceAddress, ResourceAddress);
 
@@ -10,6 +11,7 @@ pub type Pair = (ResourceAddress, ResourceAddress);
     SetOwnerRuleDefaultEvent,
     SetUserRuleDefaultEvent,
     SetTokenValidatorEvent,
+    SetOrderBooksStateEvent,
     NewOrderBookEvent,
 )]
 #[types(
@@ -23,8 +25,9 @@ mod order_book_factory {
     extern_blueprint!(
         "package_sim1p5tk86x78nq08k9q8hy9n7w99fv5zefkekeujkyerkrtydzunvrpzu",
         OrderBook {
-            fn new(owner_rule_default: AccessRule, user_rule_default: AccessRule, token_x_address: ResourceAddress, token_y_address: ResourceAddress, reservation: Option<GlobalAddressReservation>) -> Global<OrderBook>;
+            fn new(owner_rule_default: AccessRule, user_rule_default: AccessRule, state_manager_rule: AccessRule, token_x_address: ResourceAddress, token_y_address: ResourceAddress, min_order_size: Decimal, lot_size: Option<Decimal>, price_precision: u32, reservation: Option<GlobalAddressReservation>) -> Global<OrderBook>;
             fn get_order_receipt_address(&self) -> ResourceAddress;
+            fn set_state(&mut self, state: OrderBookState);
         }
     );
 
@@ -37,6 +40,7 @@ mod order_book_factory {
             set_owner_rule_default => restrict_to: [OWNER];
             set_user_rule_default => restrict_to: [OWNER];
             set_token_validator => restrict_to: [OWNER];
+            set_order_books_state => restrict_to: [OWNER];
             new_order_book => restrict_to: [user];
             get_owner_rule_default => PUBLIC;
             get_user_rule_default => PUBLIC;
@@ -84,6 +88,7 @@ mod order_book_factory {
         /// * `set_owner_rule_default` - Owner required.
         /// * `set_user_rule_default` - Owner required.
         /// * `set_token_validator` - Owner required.
+        /// * `set_order_books_state` - Owner required.
         /// * `new_order_book` - User role required.
         /// * `get_owner_rule_default` - Public.
         /// * `get_user_rule_default` - Public.
@@ -176,6 +181,36 @@ mod order_book_factory {
             });
         }
 
+        /// OWNER: Set the state of order books created by this factory.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `state` - New state of the order books.
+        /// * `start` - Optional start index of range of order books to set, included.
+        /// * `end` - Optional end index of range of order books to set, excluded.
+        /// 
+        /// # Events
+        /// 
+        /// * `SetOrderBooksStateEvent` - Event emitted when state of order books is set.
+        /// 
+        pub fn set_order_books_state(&mut self, state: OrderBookState, start: Option<u64>, end: Option<u64>) {
+            let start = start.unwrap_or(0);
+            let end = end.unwrap_or(self.order_books_list.len());
+
+            // Set state of each order book in range
+            let order_book_addresses = self.order_books_list.range(start, end);
+            for order_book_address in order_book_addresses.iter() {
+                let mut order_book: Global<OrderBook> = Global::from(*order_book_address);
+                order_book.set_state(state);
+            }
+
+            // Emit set order books state event
+            Runtime::emit_event(SetOrderBooksStateEvent {
+                order_book_addresses,
+                state,
+            });
+        }
+
         /// Get owner rule default.
         /// 
         /// # Returns
@@ -278,6 +313,9 @@ mod order_book_factory {
         /// 
         /// * `token_x_address` - Token x resource address.
         /// * `token_y_address` - Token y resource address.
+        /// * `min_order_size` - Minimum value of an order calculated in tokens y.
+        /// * `lot_size` - Optional size calculated in tokens x of which limit order amounts must be a multiple. If none, any amount is allowed.
+        /// * `price_precision` - Number of significant figures to which prices are truncated.
         /// * `reservation` - Optional global address reservation.
         /// 
         /// # Returns
@@ -287,6 +325,9 @@ mod order_book_factory {
         /// # Panics
         /// 
         /// * If tokens are invalid.
+        /// * If `min_order_size` is negative.
+        /// * If `lot_size` is not greater than zero.
+        /// * If `price_precision` is not in the range of [1, 5].
         /// 
         /// # Events
         /// 
@@ -296,6 +337,9 @@ mod order_book_factory {
             &mut self, 
             token_x_address: ResourceAddress,
             token_y_address: ResourceAddress,
+            min_order_size: Decimal,
+            lot_size: Option<Decimal>,
+            price_precision: u32,
             reservation: Option<GlobalAddressReservation>,
         ) -> Global<OrderBook> {
             // Validate tokens
@@ -306,8 +350,12 @@ mod order_book_factory {
             let order_book = Blueprint::<OrderBook>::new(
                 self.owner_rule_default.clone(),
                 self.user_rule_default.clone(),
+                rule!(require(global_caller(Runtime::global_address()))),
                 token_x_address,
                 token_y_address,
+                min_order_size,
+                lot_size,
+                price_precision,
                 reservation,
             );
 
@@ -334,6 +382,9 @@ mod order_book_factory {
                 order_receipt_address: order_book.get_order_receipt_address(),
                 token_x_address,
                 token_y_address,
+                min_order_size,
+                lot_size,
+                price_precision,
             });
 
             order_book
@@ -0,0 +1,13 @@
+use scrypto::prelude::*;
+
+/// State of an order book, set by the owner or state manager to halt matching during an incident.
+///
+/// * `Active` - All actions are allowed.
+/// * `CancelOnly` - Only order receipts can be claimed, no orders can be placed, amended, or matched.
+/// * `Halted` - No actions that move tokens are allowed.
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrderBookState {
+    Active,
+    CancelOnly,
+    Halted,
+}
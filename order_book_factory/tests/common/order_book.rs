@@ -18,6 +18,50 @@ pub enum OrderStatus {
     Invalid,
 }
 
+/// Post only mode.
+///
+/// * `Reject` - Panic if the limit order would cross the order book.
+/// * `Reprice` - Move the limit order to the closest price that does not cross the order book.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum PostOnlyMode {
+    Reject,
+    Reprice,
+}
+
+/// Self trade prevention mode.
+///
+/// * `CancelResting` - Cancel the resting limit order and continue matching.
+/// * `CancelIncoming` - Stop matching and return the remaining tokens of the incoming order.
+/// * `SkipMaker` - Move the resting limit order to the end of its limit and continue matching.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum SelfTradeMode {
+    CancelResting,
+    CancelIncoming,
+    SkipMaker,
+}
+
+/// Order book state.
+///
+/// * `Active` - All actions are allowed.
+/// * `CancelOnly` - Only order receipts can be claimed.
+/// * `Halted` - No actions that move tokens are allowed.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrderBookState {
+    Active,
+    CancelOnly,
+    Halted,
+}
+
+/// Market order mode.
+///
+/// * `ImmediateOrCancel(Decimal)` - Execute as much as possible, panic if less than the minimum amount is bought.
+/// * `FillOrKill` - Panic unless all tokens are executed.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum MarketOrderMode {
+    ImmediateOrCancel(Decimal),
+    FillOrKill,
+}
+
 pub fn build_manifest(
     order_book_package: PackageAddress,
     owner_rule: AccessRule,
@@ -30,7 +74,7 @@ pub fn build_manifest(
             order_book_package,
             "OrderBook",
             "new",
-            manifest_args!(owner_rule, user_rule, token_x_address, token_y_address, None::<ManifestAddressReservation>))
+            manifest_args!(owner_rule, user_rule, AccessRule::DenyAll, token_x_address, token_y_address, dec!(0), None::<Decimal>, 5u32, None::<ManifestAddressReservation>))
         .build()
 }
 
@@ -204,6 +248,24 @@ pub fn get_amount_y(vars: &mut Vars) -> Decimal {
     receipt.expect_commit_success().output::<Decimal>(1)
 }
 
+pub fn get_state(vars: &mut Vars) -> OrderBookState {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.order_book_component,
+        "get_state",
+        manifest_args!(),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    println!("\nGET STATE\n");
+    println!("{:?}", receipt);
+    receipt.expect_commit_success().output::<OrderBookState>(1)
+}
+
 pub fn get_last_price(vars: &mut Vars) -> Decimal {
     let manifest = ManifestBuilder::new()
     .call_method(
@@ -319,7 +381,7 @@ pub fn limit_order(token: ResourceAddress, amount: Decimal, price: Decimal, vars
             builder.call_method(
                 vars.order_book_component,
                 "limit_order",
-                manifest_args!(lookup.bucket("tokens") , price))
+                manifest_args!(lookup.bucket("tokens") , price, None::<PostOnlyMode>, None::<u64>, None::<Decimal>, None::<ManifestProof>, None::<SelfTradeMode>))
         })
         .call_method(
             vars.account_component,
@@ -361,7 +423,7 @@ pub fn limit_order_batch(positions: Vec<(ResourceAddress, Decimal, Decimal)>, va
             builder.call_method(
                 vars.order_book_component,
                 "limit_order_batch",
-                manifest_args!(args1))
+                manifest_args!(args1, None::<PostOnlyMode>, None::<u64>, None::<ManifestProof>, None::<SelfTradeMode>))
         })
         .call_method(
             vars.account_component,
@@ -386,7 +448,7 @@ pub fn market_order(token: ResourceAddress, amount: Decimal, stop_price: Option<
             builder.call_method(
                 vars.order_book_component,
                 "market_order",
-                manifest_args!(lookup.bucket("tokens") , stop_price))
+                manifest_args!(lookup.bucket("tokens") , stop_price, None::<MarketOrderMode>, None::<Decimal>, None::<ManifestProof>, None::<SelfTradeMode>))
         })
         .call_method(
             vars.account_component,
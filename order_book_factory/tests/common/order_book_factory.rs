@@ -3,6 +3,7 @@ use transaction::{builder::ManifestBuilder, model::TransactionManifestV1};
 use radix_engine::transaction::TransactionReceipt;
 
 use crate::common::vars::*;
+use crate::common::order_book::OrderBookState;
 
 pub fn build_manifest(
     order_book_factory_package: PackageAddress,
@@ -146,6 +147,34 @@ pub fn set_token_validator(token_validator_address: ComponentAddress, with_proof
     receipt
 }
 
+pub fn set_order_books_state(state: OrderBookState, with_proof: bool, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = if with_proof {
+        ManifestBuilder::new()
+            .create_proof_from_account_of_amount(vars.admin_account_component, vars.admin_badge, dec!(1))
+            .call_method(
+                vars.order_book_factory_component,
+                "set_order_books_state",
+                manifest_args!(state, None::<u64>, None::<u64>))
+            .build()
+    } else {
+        ManifestBuilder::new()
+            // .create_proof_from_account(vars.admin_account_component, vars.admin_badge)
+            .call_method(
+                vars.order_book_factory_component,
+                "set_order_books_state",
+                manifest_args!(state, None::<u64>, None::<u64>))
+            .build()
+    };
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.admin_public_key)],
+    );
+    println!("\nSET ORDER BOOKS STATE\n");
+    println!("{:?}", receipt);
+    receipt
+}
+
 pub fn get_owner_rule_default(vars: &mut Vars) -> AccessRule {
     let manifest = ManifestBuilder::new()
         .call_method(
@@ -273,11 +302,22 @@ pub fn get_order_books_by_pair(token_x_address: ResourceAddress, token_y_address
 }
 
 pub fn new_order_book(token_x: ResourceAddress, token_y: ResourceAddress, vars: &mut Vars) -> TransactionReceipt {
+    new_order_book_with_config(token_x, token_y, dec!(0), None, 5, vars)
+}
+
+pub fn new_order_book_with_config(
+    token_x: ResourceAddress, 
+    token_y: ResourceAddress, 
+    min_order_size: Decimal, 
+    lot_size: Option<Decimal>, 
+    price_precision: u32, 
+    vars: &mut Vars
+    ) -> TransactionReceipt {
     let manifest = ManifestBuilder::new()
         .call_method(
             vars.order_book_factory_component,
             "new_order_book",
-            manifest_args!(token_x, token_y, None::<ManifestAddressReservation>))
+            manifest_args!(token_x, token_y, min_order_size, lot_size, price_precision, None::<ManifestAddressReservation>))
         .build();
 
     let receipt = vars.test_runner.execute_manifest_ignoring_fee(
@@ -97,6 +97,42 @@ fn test_set_token_validator_event() {
     );
 }
 
+#[test]
+fn test_set_order_books_state_event() {
+    let mut vars: Vars = setup();
+
+    let receipt = new_order_book(vars.token_x, vars.token_y, &mut vars);
+    let order_book_component = receipt.expect_commit_success().new_component_addresses()[0];
+
+    let events = set_order_books_state(order_book::OrderBookState::Halted, true, &mut vars).expect_commit_success().application_events.clone();
+
+    let (event_type_identifier, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<SetOrderBooksStateEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<SetOrderBooksStateEvent>(&event_data).unwrap();
+
+    assert_eq!(
+        event_type_identifier,
+        EventTypeIdentifier(
+            Emitter::Method(*vars.order_book_factory_component.as_node_id(), ObjectModuleId::Main),
+            event_type_identifier.1.clone(),
+        )
+    );
+
+    assert_eq!(
+        event.order_book_addresses,
+        vec![order_book_component]
+    );
+
+    assert_eq!(
+        event.state,
+        ::order_book_factory::order_book_state::OrderBookState::Halted
+    );
+}
+
 #[test]
 fn test_new_order_book_event() {
     let mut vars: Vars = setup();
@@ -136,4 +172,36 @@ fn test_new_order_book_event() {
     assert_eq!(event.order_receipt_address, order_receipt);
     assert_eq!(event.token_x_address, vars.token_x);
     assert_eq!(event.token_y_address, vars.token_y);
+    assert_eq!(event.min_order_size, dec!(0));
+    assert_eq!(event.lot_size, None);
+    assert_eq!(event.price_precision, 5);
+}
+
+#[test]
+fn test_new_order_book_event_with_config() {
+    let mut vars: Vars = setup();
+
+    let receipt = new_order_book_with_config(
+        vars.token_x,
+        vars.token_y,
+        dec!(10),
+        Some(dec!("0.1")),
+        3,
+        &mut vars,
+    );
+
+    let events = receipt.expect_commit_success().application_events.clone();
+
+    let (_, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<NewOrderBookEvent>(event_type_identifier) &&
+            event_type_identifier.0 == Emitter::Method(*vars.order_book_factory_component.as_node_id(), ObjectModuleId::Main)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<NewOrderBookEvent>(&event_data).unwrap();
+
+    assert_eq!(event.min_order_size, dec!(10));
+    assert_eq!(event.lot_size, Some(dec!("0.1")));
+    assert_eq!(event.price_precision, 3);
 }
@@ -71,12 +71,12 @@ fn test_new_order_book_with_limit() {
                 .call_method(
                 vars.order_book_factory_component,
                 "new_order_book",
-                manifest_args!(vars.token_x, vars.token_y, Some(lookup.address_reservation("order_book_reservation")))
+                manifest_args!(vars.token_x, vars.token_y, dec!(0), None::<Decimal>, 5u32, Some(lookup.address_reservation("order_book_reservation")))
             )
             .call_method(
                 lookup.named_address("order_book_address"),
                 "limit_order",
-                manifest_args!(lookup.bucket("tokens") , dec!(1))
+                manifest_args!(lookup.bucket("tokens") , dec!(1), None::<order_book::PostOnlyMode>, None::<u64>, None::<Decimal>, None::<ManifestProof>, None::<order_book::SelfTradeMode>)
             )
         })
         .call_method(
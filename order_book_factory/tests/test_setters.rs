@@ -93,3 +93,36 @@ fn test_set_token_validator_without_admin_invalid() {
 
     set_token_validator(token_validator_component, false, &mut vars).expect_auth_failure();
 }
+
+#[test]
+fn test_set_order_books_state_valid() {
+    let mut vars: Vars = setup();
+
+    let receipt = new_order_book(vars.token_x, vars.token_y, &mut vars);
+    let order_book_a = receipt.expect_commit_success().new_component_addresses()[0];
+    let receipt = new_order_book(vars.token_y, vars.token_x, &mut vars);
+    let order_book_b = receipt.expect_commit_success().new_component_addresses()[0];
+
+    set_order_books_state(order_book::OrderBookState::CancelOnly, true, &mut vars).expect_commit_success();
+
+    vars.order_book_component = order_book_a;
+    assert_eq!(
+        order_book::get_state(&mut vars),
+        order_book::OrderBookState::CancelOnly
+    );
+
+    vars.order_book_component = order_book_b;
+    assert_eq!(
+        order_book::get_state(&mut vars),
+        order_book::OrderBookState::CancelOnly
+    );
+}
+
+#[test]
+fn test_set_order_books_state_without_admin_invalid() {
+    let mut vars: Vars = setup();
+
+    new_order_book(vars.token_x, vars.token_y, &mut vars).expect_commit_success();
+
+    set_order_books_state(order_book::OrderBookState::Halted, false, &mut vars).expect_auth_failure();
+}
@@ -59,6 +59,64 @@ pub struct RemoveLiquidityEvent {
     pub removed_y: Vec<(u32, Decimal)>,
 }
 
+/// Event emitted when liquidity is rebalanced.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RebalanceLiquidityEvent {
+    /// The id of the liquidity receipt that was rebalanced.
+    pub liquidity_receipt_id: NonFungibleLocalId,
+    /// The net change in tokens x for the pool.
+    pub amount_change_x: Decimal,
+    /// The net change in tokens y for the pool.
+    pub amount_change_y: Decimal,
+    /// The positions tokens x were removed from.
+    pub removed_x: Vec<(u32, Decimal)>,
+    /// The positions tokens y were removed from.
+    pub removed_y: Vec<(u32, Decimal)>,
+    /// The positions tokens x were added to.
+    pub added_x: Vec<(u32, Decimal)>,
+    /// The positions tokens y were added to.
+    pub added_y: Vec<(u32, Decimal)>,
+}
+
+/// Event emitted when a range order is added.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct AddRangeOrderEvent {
+    /// The id of the liquidity receipt to which the range order was added.
+    pub liquidity_receipt_id: NonFungibleLocalId,
+    /// The tick of the bin the range order was added to.
+    pub tick: u32,
+    /// The change in tokens x for the pool.
+    pub amount_change_x: Decimal,
+    /// The change in tokens y for the pool.
+    pub amount_change_y: Decimal,
+}
+
+/// Event emitted when a range order is removed.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RemoveRangeOrderEvent {
+    /// The id of the liquidity receipt from which the range order was removed.
+    pub liquidity_receipt_id: NonFungibleLocalId,
+    /// The tick of the bin the range order was placed on.
+    pub tick: u32,
+    /// The change in tokens x for the pool.
+    pub amount_change_x: Decimal,
+    /// The change in tokens y for the pool.
+    pub amount_change_y: Decimal,
+    /// Whether the range order had been settled.
+    pub settled: bool,
+}
+
+/// Event emitted when the range orders of a bin are settled.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SettleRangeOrdersEvent {
+    /// The tick of the bin the range orders were placed on.
+    pub tick: u32,
+    /// The amount of tokens x set aside for the range orders.
+    pub amount_x: Decimal,
+    /// The amount of tokens y set aside for the range orders.
+    pub amount_y: Decimal,
+}
+
 /// Event emitted when a swap is performed.
 #[derive(ScryptoSbor, ScryptoEvent)]
 pub struct SwapEvent {
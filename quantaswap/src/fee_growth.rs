@@ -0,0 +1,11 @@
+use scrypto::prelude::*;
+
+/// Cumulative liquidity fees earned per unit of claim on a bin.
+/// Grows every time a swap pays liquidity fees into the bin while it is active.
+#[derive(ScryptoSbor, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FeeGrowth {
+    /// Fees in tokens x per unit of claim.
+    pub x: Decimal,
+    /// Fees in tokens y per unit of claim.
+    pub y: Decimal,
+}
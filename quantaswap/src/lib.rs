@@ -1,8 +1,14 @@
 pub mod bin;
 pub mod consts;
 pub mod events;
+pub mod fee_growth;
 pub mod liquidity_receipt;
+pub mod liquidity_shape;
+pub mod observation;
 pub mod quantaswap;
+pub mod range_order;
 pub mod swap_math;
+pub mod swap_quote;
+pub mod swap_walk;
 pub mod tick_index;
 pub mod tick;
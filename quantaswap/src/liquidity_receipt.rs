@@ -1,5 +1,8 @@
 use scrypto::prelude::*;
 
+use crate::fee_growth::*;
+use crate::range_order::*;
+
 /// Liquidity receipt NFT that stores liquidity claims.
 /// Each claim is a pair of the tick and the claim amount on the bin which the tick maps to.
 /// Liquidity claims can be added, removed, and adjusted without need for another liquidity 
@@ -9,4 +12,20 @@ use scrypto::prelude::*;
 pub struct LiquidityReceipt {
     /// Map of ticks to liquidity claim amounts.
     #[mutable] pub liquidity_claims: HashMap<u32, Decimal>,
+    /// Map of ticks to fee checkpoints of the liquidity claims.
+    #[mutable] pub fee_checkpoints: HashMap<u32, FeeCheckpoint>,
+    /// Map of ticks to range orders.
+    #[mutable] pub range_orders: HashMap<u32, RangeOrder>,
+}
+
+/// Checkpoint of the fees earned by a liquidity claim.
+/// Fees earned since the checkpoint are the claim amount times the growth in fees per claim of the bin.
+#[derive(ScryptoSbor, Clone, Copy, Default, Debug)]
+pub struct FeeCheckpoint {
+    /// Fee growth of the bin when the checkpoint was last updated.
+    pub fee_growth: FeeGrowth,
+    /// Fees in tokens x earned up to the checkpoint.
+    pub earned_x: Decimal,
+    /// Fees in tokens y earned up to the checkpoint.
+    pub earned_y: Decimal,
 }
\ No newline at end of file
@@ -0,0 +1,94 @@
+use scrypto::prelude::*;
+
+/// Shape of a liquidity distribution across bins.
+/// Weights are based on the distance in bins from the active bin.
+///
+/// * `Uniform` - Every bin gets the same weight.
+/// * `Normal(Decimal)` - Weight follows a normal curve centered on the active bin, with the contained standard deviation in bins.
+/// * `BidAsk` - Weight increases linearly with distance from the active bin.
+/// * `Curve` - Weight decreases linearly with distance from the active bin.
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LiquidityShape {
+    Uniform,
+    Normal(Decimal),
+    BidAsk,
+    Curve,
+}
+
+impl LiquidityShape {
+    /// Get the weight of a bin.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `distance` - Distance in bins from the active bin.
+    /// * `max_distance` - Largest distance in bins from the active bin on the same side.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Decimal` - Weight of the bin.
+    /// 
+    /// # Requires
+    /// 
+    /// * `distance` - Distance is not greater than `max_distance`.
+    /// * `Normal` - Standard deviation is greater than zero.
+    /// 
+    pub fn weight(&self, distance: u32, max_distance: u32) -> Decimal {
+        match self {
+            LiquidityShape::Uniform => Decimal::ONE,
+            LiquidityShape::Normal(std_dev) => {
+                // Divide before squaring, so a small standard deviation can not underflow or overflow
+                let score = Decimal::from(distance) / *std_dev;
+                if score > dec!(10) {
+                    Decimal::zero()
+                } else {
+                    exp_neg(score * score / 2)
+                }
+            },
+            LiquidityShape::BidAsk => Decimal::from(distance + 1),
+            LiquidityShape::Curve => Decimal::from(max_distance - distance + 1),
+        }
+    }
+}
+
+/// Calculate e to the power of a negative number.
+/// 
+/// # Arguments
+/// 
+/// * `x` - Magnitude of the exponent.
+/// 
+/// # Returns
+/// 
+/// * `Decimal` - Value of e^-x.
+/// 
+/// # Requires
+/// 
+/// * `x` - Value is not less than zero.
+/// 
+fn exp_neg(x: Decimal) -> Decimal {
+    // Values below the precision of decimal
+    if x > dec!(42) {
+        return Decimal::zero();
+    }
+
+    // Reduce exponent so the series converges quickly
+    let mut reduced = x;
+    let mut halvings = 0;
+    while reduced > Decimal::ONE {
+        reduced = reduced / 2;
+        halvings += 1;
+    }
+
+    // Taylor series of e^-reduced
+    let mut term = Decimal::ONE;
+    let mut sum = Decimal::ONE;
+    for n in 1..20 {
+        term = -term * reduced / n;
+        sum += term;
+    }
+
+    // Square back to e^-x
+    for _ in 0..halvings {
+        sum = sum * sum;
+    }
+    sum
+}
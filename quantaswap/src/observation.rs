@@ -0,0 +1,11 @@
+use scrypto::prelude::*;
+
+/// Observation of the cumulative price of the pool at a point in time.
+/// The time weighted average price between two observations is the difference in cumulative price divided by the time elapsed.
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct Observation {
+    /// Time of the observation in seconds since the unix epoch, rounded to the minute.
+    pub timestamp: i64,
+    /// Sum of the price multiplied by the seconds it was held for, up to the time of the observation.
+    pub price_cumulative: Decimal,
+}
@@ -2,8 +2,14 @@ use scrypto::prelude::*;
 
 use crate::bin::*;
 use crate::events::*;
+use crate::fee_growth::*;
 use crate::liquidity_receipt::*;
+use crate::liquidity_shape::*;
+use crate::observation::*;
+use crate::range_order::*;
 use crate::swap_math::*;
+use crate::swap_quote::*;
+use crate::swap_walk::*;
 use crate::tick_index::*;
 use crate::tick::*;
 
@@ -14,6 +20,10 @@ use crate::tick::*;
     BurnLiquidityReceiptEvent,
     AddLiquidityEvent,
     RemoveLiquidityEvent,
+    RebalanceLiquidityEvent,
+    AddRangeOrderEvent,
+    RemoveRangeOrderEvent,
+    SettleRangeOrdersEvent,
     SwapEvent,
     ValuationEvent,
     ProtocolFeeEvent,
@@ -22,8 +32,13 @@ use crate::tick::*;
 #[types(
     Tick,
     Bin,
+    FeeGrowth,
+    RangeOrderGroup,
+    SettledRangeOrders,
+    Observation,
     LiquidityReceipt,
     u32,
+    u64,
     IndexNode,
 )]
 mod quantaswap {
@@ -64,10 +79,17 @@ mod quantaswap {
             mint_liquidity_receipt => restrict_to: [user];
             add_liquidity => restrict_to: [user];
             add_liquidity_to_receipt => restrict_to: [user];
+            add_liquidity_with_distribution => restrict_to: [user];
+            rebalance_liquidity => restrict_to: [user];
+            add_range_order => restrict_to: [user];
             swap => restrict_to: [user];
+            swap_exact_output => restrict_to: [user];
+            split_liquidity_receipt => restrict_to: [user];
             burn_liquidity_receipt => PUBLIC;
+            merge_liquidity_receipts => PUBLIC;
             remove_liquidity => PUBLIC;
             remove_specific_liquidity => PUBLIC;
+            remove_range_order => PUBLIC;
             get_fee_controller_address => PUBLIC;
             get_fee_vaults_address => PUBLIC;
             get_token_x_address => PUBLIC;
@@ -75,6 +97,7 @@ mod quantaswap {
             get_liquidity_receipt_address => PUBLIC;
             get_bin_span => PUBLIC;
             get_liquidity_claims => PUBLIC;
+            get_earned_fees => PUBLIC;
             get_amount_x => PUBLIC;
             get_amount_y => PUBLIC;
             get_active_tick => PUBLIC;
@@ -85,6 +108,10 @@ mod quantaswap {
             get_bins_below => PUBLIC;
             get_redemption_value => PUBLIC;
             get_redemption_bin_values => PUBLIC;
+            get_range_orders => PUBLIC;
+            get_twap => PUBLIC;
+            observe => PUBLIC;
+            quote_swap => PUBLIC;
         }
     }
 
@@ -92,6 +119,9 @@ mod quantaswap {
     const INCOMING: WithdrawStrategy = WithdrawStrategy::Rounded(RoundingMode::ToPositiveInfinity);
     const OUTGOING: WithdrawStrategy = WithdrawStrategy::Rounded(RoundingMode::ToZero);
 
+    // Set maximum number of price observations kept
+    const MAX_OBSERVATIONS: u32 = 1440;
+
     struct QuantaSwap {
         /// Span of ticks a bin covers. Bins are aligned to the bin span.
         bin_span: u32,
@@ -99,6 +129,20 @@ mod quantaswap {
         tick_index: TickIndex,
         /// Map of ticks to dormant bins.
         bin_map: KeyValueStore<Tick, Bin>,
+        /// Map of ticks to cumulative liquidity fees per unit of claim on the bin.
+        fee_growth_map: KeyValueStore<Tick, FeeGrowth>,
+        /// Map of ticks to the range order group resting in the bin.
+        range_order_map: KeyValueStore<Tick, RangeOrderGroup>,
+        /// Map of range order group ids to tokens set aside for settled range orders.
+        settled_range_order_map: KeyValueStore<u64, SettledRangeOrders>,
+        /// Number of range order groups created. Used as the id of the latest group.
+        range_order_group_count: u64,
+        /// Ring buffer of price observations, indexed by position in the buffer.
+        observations: KeyValueStore<u32, Observation>,
+        /// Position of the latest price observation in the ring buffer.
+        observation_index: u32,
+        /// Number of price observations in the ring buffer.
+        observation_count: u32,
         /// Lower price sqrt limit of the active bin.
         lower_limit: Decimal,
         /// Upper price sqrt limit of the active bin.
@@ -115,6 +159,10 @@ mod quantaswap {
         tokens_x: Vault,
         /// Vault for tokens y.
         tokens_y: Vault,
+        /// Vault for tokens x set aside for settled range orders.
+        settled_tokens_x: Vault,
+        /// Vault for tokens y set aside for settled range orders.
+        settled_tokens_y: Vault,
     }
 
     impl QuantaSwap {
@@ -139,6 +187,7 @@ mod quantaswap {
         /// `add_liquidity` - User role required.
         /// `add_liquidity_to_receipt` - User role required.
         /// `swap` - User role required.
+        /// `swap_exact_output` - User role required.
         /// `burn_liquidity_receipt` - Public.
         /// `remove_liquidity` - Public.
         /// `remove_specific_liquidity` - Public.
@@ -159,6 +208,7 @@ mod quantaswap {
         /// `get_liquidity_claims` - Public.
         /// `get_redemption_value` - Public.
         /// `get_redemption_bins` - Public.
+        /// `quote_swap` - Public.
         /// 
         /// # Panics
         /// 
@@ -240,6 +290,13 @@ mod quantaswap {
                 bin_span,
                 tick_index: TickIndex::new(),
                 bin_map: KeyValueStore::new_with_registered_type(),
+                fee_growth_map: KeyValueStore::new_with_registered_type(),
+                range_order_map: KeyValueStore::new_with_registered_type(),
+                settled_range_order_map: KeyValueStore::new_with_registered_type(),
+                range_order_group_count: 0,
+                observations: KeyValueStore::new_with_registered_type(),
+                observation_index: 0,
+                observation_count: 0,
                 lower_limit: Decimal::zero(),
                 upper_limit: Decimal::zero(),
                 active_x: Decimal::zero(),
@@ -248,6 +305,8 @@ mod quantaswap {
                 liquidity_receipt_manager,
                 tokens_x: Vault::new(token_x_address),
                 tokens_y: Vault::new(token_y_address),
+                settled_tokens_x: Vault::new(token_x_address),
+                settled_tokens_y: Vault::new(token_y_address),
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(owner_rule))
@@ -329,6 +388,7 @@ mod quantaswap {
         }
 
         /// Get total amount of tokens x in protocol.
+        /// Tokens set aside for settled range orders are not included.
         /// 
         /// # Returns
         /// 
@@ -339,6 +399,7 @@ mod quantaswap {
         }
 
         /// Get total amount of tokens y in the protocol.
+        /// Tokens set aside for settled range orders are not included.
         /// 
         /// # Returns
         /// 
@@ -610,6 +671,36 @@ mod quantaswap {
                 .liquidity_claims
         }
 
+        /// Get the liquidity fees earned by each liquidity claim of a liquidity receipt by id.
+        /// Fees are counted from when the claim was first added, and reset once the claim is fully removed.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `liquidity_receipt_id` - Id of the liquidity receipt.
+        /// 
+        /// # Returns
+        /// 
+        /// * `HashMap<u32, (Decimal, Decimal)>` - Fees earned for each claim on a bin in the format tick => (fees_x, fees_y).
+        /// 
+        /// # Panics
+        /// 
+        /// * If the liquidity receipt does not exist.
+        /// 
+        pub fn get_earned_fees(&self, liquidity_receipt_id: NonFungibleLocalId) -> HashMap<u32, (Decimal, Decimal)> {
+            // Get liquidity claims and fee checkpoints
+            let LiquidityReceipt { liquidity_claims, fee_checkpoints, .. } = self.liquidity_receipt_manager
+                .get_non_fungible_data::<LiquidityReceipt>(&liquidity_receipt_id);
+
+            // Add fees earned since the checkpoint of each claim
+            liquidity_claims.into_iter().map(|(tick, claim)| {
+                let checkpoint = fee_checkpoints.get(&tick).copied().unwrap_or_default();
+                let fee_growth = self.get_fee_growth(Tick(tick));
+                let earned_x = checkpoint.earned_x + claim * (fee_growth.x - checkpoint.fee_growth.x);
+                let earned_y = checkpoint.earned_y + claim * (fee_growth.y - checkpoint.fee_growth.y);
+                (tick, (earned_x, earned_y))
+            }).collect()
+        }
+
         /// Get the redemption value of a liquidity receipt in tokens x and y by id.
         /// 
         /// # Arguments
@@ -708,6 +799,154 @@ mod quantaswap {
             redemptions
         }
 
+        /// Get the range orders of a liquidity receipt by id.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `liquidity_receipt_id` - Id of the liquidity receipt.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Vec<(u32, Decimal, Decimal, bool)>` - Redemption values for each range order in the format (tick, amount_x, amount_y, settled).
+        /// 
+        /// # Panics
+        /// 
+        /// * If the liquidity receipt does not exist.
+        /// 
+        pub fn get_range_orders(&self, liquidity_receipt_id: NonFungibleLocalId) -> Vec<(u32, Decimal, Decimal, bool)> {
+            // Get range orders
+            let range_orders = self.liquidity_receipt_manager.get_non_fungible_data::<LiquidityReceipt>(&liquidity_receipt_id).range_orders;
+
+            // Range order redemption values
+            let mut redemptions: Vec<(u32, Decimal, Decimal, bool)> = Vec::new();
+
+            // Loop through range orders
+            let current_tick = self.tick_index.current();
+            for (tick, range_order) in range_orders {
+                if let Some(settled) = self.settled_range_order_map.get(&range_order.group_id) { // settled
+                    let share = range_order.claim / settled.claim;
+                    redemptions.push((tick, settled.amount_x * share, settled.amount_y * share, true));
+                    continue;
+                }
+
+                // Resting in a bin
+                let current_tick = current_tick.unwrap();
+                let tick: Tick = Tick(tick);
+                if tick < current_tick { // bin below
+                    let bin = self.bin_map.get(&tick).unwrap();
+                    let amount_y = range_order.claim / bin.total_claim * bin.amount;
+                    redemptions.push((tick.0, Decimal::zero(), amount_y, false));
+                } else if tick > current_tick { // bin above
+                    let bin = self.bin_map.get(&tick).unwrap();
+                    let amount_x = range_order.claim / bin.total_claim * bin.amount;
+                    redemptions.push((tick.0, amount_x, Decimal::zero(), false));
+                } else { // active bin
+                    let liquidity_share = range_order.claim / self.active_total_claim;
+                    redemptions.push((tick.0, self.active_x * liquidity_share, self.active_y * liquidity_share, false));
+                }
+            }
+
+            // Sort redemptions by tick
+            redemptions.sort_by(|a, b| a.0.cmp(&b.0));
+
+            // Return range order redemptions
+            redemptions
+        }
+
+        /// Get the time weighted average price over a period ending now.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `seconds_ago` - Length of the period in seconds.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Time weighted average price over the period.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `seconds_ago` is not greater than zero.
+        /// * If there are no price observations.
+        /// * If the start of the period is before the oldest price observation.
+        /// 
+        pub fn get_twap(&self, seconds_ago: u32) -> Decimal {
+            assert!(seconds_ago > 0, "Seconds ago must be greater than zero.");
+            let price_cumulatives = self.observe(vec![seconds_ago, 0]);
+            (price_cumulatives[1] - price_cumulatives[0]) / Decimal::from(seconds_ago)
+        }
+
+        /// Get the cumulative price at each of the given times.
+        /// The time weighted average price between two times is the difference in cumulative price divided by the seconds between them.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `seconds_agos` - Times to get the cumulative price at, in seconds before now.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Vec<Decimal>` - Cumulative price at each of the times.
+        /// 
+        /// # Panics
+        /// 
+        /// * If there are no price observations.
+        /// * If a time is before the oldest price observation.
+        /// 
+        pub fn observe(&self, seconds_agos: Vec<u32>) -> Vec<Decimal> {
+            assert!(self.observation_count > 0, "No price observations.");
+            let now = Clock::current_time_rounded_to_minutes().seconds_since_unix_epoch;
+            seconds_agos
+                .into_iter()
+                .map(|seconds_ago| self.price_cumulative_at(now - i64::from(seconds_ago)))
+                .collect()
+        }
+
+        /// Get a quote for a swap without executing it.
+        /// Follows the same steps as `swap` on copies of the active bin state.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource` - Address of tokens to swap.
+        /// * `amount` - Amount of tokens to swap.
+        /// * `limit_price` - Optional limit price, a floor when swapping tokens x and a ceiling when swapping tokens y. If none, swap will not stop until filled.
+        /// 
+        /// # Returns
+        /// 
+        /// * `SwapQuote` - Expected outcome of the swap.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `amount` is not greater than zero.
+        /// * If `limit_price` is not greater than zero.
+        /// * If `resource` is not tokens x or tokens y.
+        /// 
+        /// # Swap Quote
+        /// 
+        /// * `amount_out` - Amount of tokens bought.
+        /// * `price_after` - Price after the swap. None if the pool has no active bin.
+        /// * `bins_crossed` - Number of bins crossed before the swap ends.
+        /// * `protocol_fee` - Amount of input tokens taken as protocol fee.
+        /// * `liquidity_fee` - Amount of input tokens taken as liquidity fee.
+        /// * `amount_remaining` - Amount of input tokens returned unused.
+        /// 
+        pub fn quote_swap(&self, resource: ResourceAddress, amount: Decimal, limit_price: Option<Decimal>) -> SwapQuote {
+            assert!(amount > Decimal::zero(), "Amount must be greater than zero.");
+
+            // Convert limit price to price sqrt
+            let limit_price_sqrt = limit_price.map(|price| {
+                assert!(price > Decimal::zero(), "Limit price must be greater than zero.");
+                calculate_price_sqrt(&price)
+            });
+
+            // Quote swap
+            if resource == self.tokens_x.resource_address() {
+                self.quote_swap_x(amount, limit_price_sqrt)
+            } else if resource == self.tokens_y.resource_address() {
+                self.quote_swap_y(amount, limit_price_sqrt)
+            } else {
+                panic!("Invalid token address.")
+            }
+        }
+
         /// Mint a liquidity receipt that is used to track and manage liquidity positions.
         /// 
         /// # Returns
@@ -721,7 +960,9 @@ mod quantaswap {
         pub fn mint_liquidity_receipt(&mut self) -> Bucket {
             // Mint liquidity receipt
             let liquidity_receipt = self.liquidity_receipt_manager.mint_ruid_non_fungible(LiquidityReceipt { 
-                liquidity_claims: HashMap::new() 
+                liquidity_claims: HashMap::new(),
+                fee_checkpoints: HashMap::new(),
+                range_orders: HashMap::new(),
             });
 
             // Emit mint liquidity receipt event
@@ -743,6 +984,7 @@ mod quantaswap {
         /// 
         /// * If the liquidity receipt is invalid.
         /// * If the liquidity receipt has liquidity claims.
+        /// * If the liquidity receipt has range orders.
         /// 
         /// # Events
         /// 
@@ -758,13 +1000,17 @@ mod quantaswap {
             let liquidity_receipt = liquidity_receipt.as_non_fungible();
             let liquidity_receipt_id =  liquidity_receipt.non_fungible_local_id();
             let liquidity_receipt_nft = liquidity_receipt.non_fungible::<LiquidityReceipt>();
-            let liquidity_claims = liquidity_receipt_nft.data().liquidity_claims;
+            let LiquidityReceipt { liquidity_claims, range_orders, .. } = liquidity_receipt_nft.data();
 
-            // Assert that there are no liquidity claims
+            // Assert that there are no liquidity claims or range orders
             assert!(
                 liquidity_claims.is_empty(),
                 "Cannot burn liquidity receipt with liquidity claims."
             );
+            assert!(
+                range_orders.is_empty(),
+                "Cannot burn liquidity receipt with range orders."
+            );
 
             // Burn liquidity receipt
             liquidity_receipt.burn();
@@ -775,118 +1021,177 @@ mod quantaswap {
             });
         }
 
-        /// Add tokens as liquidity at the specified positions to a liquidity receipt.
-        /// If the position is above the active bin, tokens x will be used.
-        /// If the position is below the active bin, tokens y will be used.
-        /// If the position is at the current price, tokens x and y will be used in proportion current ratio.
-        /// If it is the first liquidity position to be added, all tokens will be used and it will become the active bin.
+        /// Split a liquidity receipt by moving liquidity claims into a newly minted liquidity receipt.
+        /// Fees earned by a claim are split in proportion to the amount of claim moved.
+        /// Range orders stay with the original liquidity receipt.
         /// 
         /// # Arguments
         /// 
-        /// * `liquidity_receipt` - Liquidity receipt that will be used to track the liquidity claims.
-        /// * `tokens_x` - Tokens x to add as liquidity.
-        /// * `tokens_y` - Tokens y to add as liquidity.
-        /// * `positions` - Positions at which to add liquidity. Format: (tick, amount_x, amount_y).
+        /// * `liquidity_receipt` - Liquidity receipt that contains the liquidity claims.
+        /// * `claims` - Liquidity claims to move. Format: (tick, amount_claim).
         /// 
         /// # Returns
         /// 
         /// * `Bucket` - Updated liquidity receipt.
-        /// * `Bucket` - Tokens x that were not used.
-        /// * `Bucket` - Tokens y that were not used.
+        /// * `Bucket` - New liquidity receipt with the moved liquidity claims.
         /// 
         /// # Panics
         /// 
         /// * If the liquidity receipt is invalid.
-        /// * If the tick of a position is not in the range of [0, 54000].
-        /// * If the tick of a position is not aligned to the bin span.
-        /// * If there are not enough tokens to add liquidity.
-        /// * If the total number of liquidity claims for the liquidity receipt exceeds 200.
+        /// * If the claim amount is not greater than zero.
+        /// * If the claim does not exist.
         /// 
         /// # Events
         /// 
-        /// * `AddLiquidityEvent` - Event emitted when liquidity is added.
-        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// * `MintLiquidityReceiptEvent` - Event emitted when liquidity receipt is minted.
         /// 
-        pub fn add_liquidity_to_receipt(
-            &mut self, 
-            liquidity_receipt: Bucket,
-            mut tokens_x: Bucket, 
-            mut tokens_y: Bucket, 
-            positions: Vec<(u32, Decimal, Decimal)>,
-        ) -> (Bucket, Bucket, Bucket) {
+        pub fn split_liquidity_receipt(&mut self, liquidity_receipt: Bucket, claims: Vec<(u32, Decimal)>) -> (Bucket, Bucket) {
             assert!(
                 liquidity_receipt.resource_address() == self.liquidity_receipt_manager.address(),
                 "Invalid liquidity receipt."
             );
 
-            // Get the current tick
-            let mut current_tick = self.tick_index.current();
-
             // Get liquidity claims
             let liquidity_receipt = liquidity_receipt.as_non_fungible();
             let liquidity_receipt_nft = liquidity_receipt.non_fungible::<LiquidityReceipt>();
-            let mut liquidity_claims = liquidity_receipt_nft.data().liquidity_claims;
-
-            // Amounts to track tokens used
-            let mut amount_x_used = Decimal::zero();
-            let mut amount_y_used = Decimal::zero();
-            let mut added_x: Vec<(u32, Decimal)> = Vec::new();
-            let mut added_y: Vec<(u32, Decimal)> = Vec::new();
-            
-            for (tick, amount_x, amount_y) in positions {
-                let tick: Tick = Tick(tick);
-                assert!(tick.is_valid(self.bin_span), "Invalid tick {:?}.", tick);
-                assert!(amount_x >= Decimal::zero() && amount_y >= Decimal::zero(), "Amounts must be greater than or equal to zero.");
-
-                // Deposit tokens
-                let claim = if current_tick.is_none() {
-                    if amount_x == Decimal::zero() && amount_y == Decimal::zero() {
-                        continue;
-                    }
+            let LiquidityReceipt { mut liquidity_claims, mut fee_checkpoints, .. } = liquidity_receipt_nft.data();
 
-                    let claim = self.add_initial_liquidity(tick, amount_x, amount_y);
-                    current_tick = self.tick_index.current();
-                    amount_x_used += amount_x;
-                    amount_y_used += amount_y;
-                    added_x.push((tick.0, amount_x));
-                    added_y.push((tick.0, amount_y));
+            // Move liquidity claims
+            let mut new_liquidity_claims: HashMap<u32, Decimal> = HashMap::new();
+            let mut new_fee_checkpoints: HashMap<u32, FeeCheckpoint> = HashMap::new();
+            for (tick, claim) in claims {
+                assert!(
+                    claim > Decimal::zero(),
+                    "Claim must be greater than zero."
+                );
 
-                    claim
-                } else if tick > current_tick.unwrap() && amount_x > Decimal::zero() {
-                    let claim = self.add_liquidity_to_bin(tick, amount_x);
-                    amount_x_used += amount_x;
-                    added_x.push((tick.0, amount_x));
+                let available_claim = liquidity_claims.get_mut(&tick).expect("Claim does not exist.");
 
-                    claim
-                } else if tick < current_tick.unwrap() && amount_y > Decimal::zero() {
-                    let claim = self.add_liquidity_to_bin(tick, amount_y);
-                    amount_y_used += amount_y;
-                    added_y.push((tick.0, amount_y));
+                // Checkpoint fees earned before the claim changes
+                self.checkpoint_fees(&mut fee_checkpoints, Tick(tick), *available_claim);
 
-                    claim
-                } else if tick == current_tick.unwrap() && amount_x > Decimal::zero() && amount_y > Decimal::zero() {
-                    let (claim, change_x, change_y) = self.add_active_liquidity(amount_x, amount_y);
-                    amount_x_used += change_x;
-                    amount_y_used += change_y;
-                    added_x.push((tick.0, change_x));
-                    added_y.push((tick.0, change_y));
+                let (claim, checkpoint) = if *available_claim > claim {
+                    // Split fees earned in proportion to the claim moved
+                    let share = claim / *available_claim;
+                    *available_claim -= claim;
+                    let checkpoint = fee_checkpoints.get_mut(&tick).unwrap();
+                    let moved_checkpoint = FeeCheckpoint {
+                        fee_growth: checkpoint.fee_growth,
+                        earned_x: checkpoint.earned_x * share,
+                        earned_y: checkpoint.earned_y * share,
+                    };
+                    checkpoint.earned_x -= moved_checkpoint.earned_x;
+                    checkpoint.earned_y -= moved_checkpoint.earned_y;
 
-                    claim
+                    (claim, moved_checkpoint)
                 } else {
-                    continue;
+                    (liquidity_claims.remove(&tick).unwrap(), fee_checkpoints.remove(&tick).unwrap())
                 };
 
-                // Add to liquidity claim
-                if let Some(position) = liquidity_claims.get_mut(&tick.0) {
-                    *position += claim;
-                } else {
-                    liquidity_claims.insert(tick.0, claim);
-                }
+                // Add to new liquidity claims
+                self.merge_claim(&mut new_liquidity_claims, &mut new_fee_checkpoints, Tick(tick), claim, checkpoint);
             }
 
-            // Deposit tokens
-            self.tokens_x.put(tokens_x.take_advanced(amount_x_used, INCOMING));
-            self.tokens_y.put(tokens_y.take_advanced(amount_y_used, INCOMING));
+            // Update liquidity receipt
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "liquidity_claims", 
+                liquidity_claims
+            );
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "fee_checkpoints", 
+                fee_checkpoints
+            );
+
+            // Mint new liquidity receipt with moved claims
+            let new_liquidity_receipt = self.mint_liquidity_receipt();
+            let new_liquidity_receipt_id = new_liquidity_receipt.as_non_fungible().non_fungible_local_id();
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                &new_liquidity_receipt_id, 
+                "liquidity_claims", 
+                new_liquidity_claims
+            );
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                &new_liquidity_receipt_id, 
+                "fee_checkpoints", 
+                new_fee_checkpoints
+            );
+
+            // Return buckets
+            (liquidity_receipt.into(), new_liquidity_receipt)
+        }
+
+        /// Merge liquidity receipts into one liquidity receipt.
+        /// The liquidity claims and range orders of all liquidity receipts are moved into the first liquidity receipt and the rest are burned.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `liquidity_receipts` - Liquidity receipts to merge.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Merged liquidity receipt.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the liquidity receipts are invalid.
+        /// * If there are no liquidity receipts.
+        /// * If the total number of liquidity claims for the merged liquidity receipt exceeds 200.
+        /// * If range orders on the same tick belong to different range order groups.
+        /// * If the total number of range orders for the merged liquidity receipt exceeds 200.
+        /// 
+        /// # Events
+        /// 
+        /// * `BurnLiquidityReceiptEvent` - Event emitted for each liquidity receipt burned.
+        /// 
+        pub fn merge_liquidity_receipts(&mut self, liquidity_receipts: Bucket) -> Bucket {
+            assert!(
+                liquidity_receipts.resource_address() == self.liquidity_receipt_manager.address(),
+                "Invalid liquidity receipt."
+            );
+            assert!(!liquidity_receipts.is_empty(), "No liquidity receipts to merge.");
+
+            // Separate the liquidity receipt to keep
+            let mut liquidity_receipts = liquidity_receipts.as_non_fungible();
+            let liquidity_receipt_id = liquidity_receipts.non_fungible_local_ids().first().unwrap().clone();
+            let liquidity_receipt = liquidity_receipts.take_non_fungible(&liquidity_receipt_id);
+            let LiquidityReceipt { mut liquidity_claims, mut fee_checkpoints, mut range_orders } = liquidity_receipt.non_fungible::<LiquidityReceipt>().data();
+
+            // Move liquidity claims of the other liquidity receipts
+            for liquidity_receipt_nft in liquidity_receipts.non_fungibles::<LiquidityReceipt>() {
+                let LiquidityReceipt { 
+                    liquidity_claims: other_claims, 
+                    fee_checkpoints: mut other_fee_checkpoints, 
+                    range_orders: other_range_orders,
+                } = liquidity_receipt_nft.data();
+                for (tick, claim) in other_claims {
+                    // Checkpoint fees earned before the claim moves
+                    self.checkpoint_fees(&mut other_fee_checkpoints, Tick(tick), claim);
+                    let checkpoint = other_fee_checkpoints.remove(&tick).unwrap();
+
+                    // Add to liquidity claims
+                    self.merge_claim(&mut liquidity_claims, &mut fee_checkpoints, Tick(tick), claim, checkpoint);
+                }
+
+                // Move range orders of the other liquidity receipt
+                for (tick, other_range_order) in other_range_orders {
+                    let range_order = range_orders.entry(tick).or_insert(RangeOrder { 
+                        group_id: other_range_order.group_id, 
+                        claim: Decimal::zero(),
+                    });
+                    assert!(
+                        range_order.group_id == other_range_order.group_id,
+                        "Cannot merge range orders from different groups on tick {}.", tick
+                    );
+                    range_order.claim += other_range_order.claim;
+                }
+
+                // Emit burn liquidity receipt event
+                Runtime::emit_event(BurnLiquidityReceiptEvent {
+                    liquidity_receipt_id: liquidity_receipt_nft.local_id().clone(),
+                });
+            }
 
             // Assert that there are no more than 200 liquidity claims
             assert!(
@@ -894,34 +1199,37 @@ mod quantaswap {
                 "Too many liquidity claims for this liquidity receipt."
             );
 
+            // Assert that there are no more than 200 range orders
+            assert!(
+                range_orders.len() <= 200,
+                "Too many range orders for this liquidity receipt."
+            );
+
+            // Burn merged liquidity receipts
+            liquidity_receipts.burn();
+
             // Update liquidity receipt
             self.liquidity_receipt_manager.update_non_fungible_data(
-                liquidity_receipt_nft.local_id(), 
+                &liquidity_receipt_id, 
                 "liquidity_claims", 
                 liquidity_claims
             );
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                &liquidity_receipt_id, 
+                "fee_checkpoints", 
+                fee_checkpoints
+            );
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                &liquidity_receipt_id, 
+                "range_orders", 
+                range_orders
+            );
 
-            // Emit add liquidity event
-            Runtime::emit_event(AddLiquidityEvent {
-                liquidity_receipt_id: liquidity_receipt_nft.local_id().clone(),
-                amount_change_x: added_x.iter().fold(dec!(0), |sum, &(_, x)| sum + x),
-                amount_change_y: added_y.iter().fold(dec!(0), |sum, &(_, y)| sum + y),
-                added_x,
-                added_y,
-            });
-
-            // Emit valuation event
-            Runtime::emit_event(ValuationEvent {
-                amount_after_x: self.get_amount_x(),
-                amount_after_y: self.get_amount_y(),
-                price_after: self.get_price().unwrap_or_default(),
-            });
-
-            // Return buckets
-            (liquidity_receipt.into(), tokens_x, tokens_y)
+            // Return liquidity receipt
+            liquidity_receipt.into()
         }
 
-        /// Add tokens as liquidity at the specified positions.
+        /// Add tokens as liquidity at the specified positions to a liquidity receipt.
         /// If the position is above the active bin, tokens x will be used.
         /// If the position is below the active bin, tokens y will be used.
         /// If the position is at the current price, tokens x and y will be used in proportion current ratio.
@@ -929,18 +1237,20 @@ mod quantaswap {
         /// 
         /// # Arguments
         /// 
+        /// * `liquidity_receipt` - Liquidity receipt that will be used to track the liquidity claims.
         /// * `tokens_x` - Tokens x to add as liquidity.
         /// * `tokens_y` - Tokens y to add as liquidity.
         /// * `positions` - Positions at which to add liquidity. Format: (tick, amount_x, amount_y).
         /// 
         /// # Returns
         /// 
-        /// * `Bucket` - Liquidity receipt.
+        /// * `Bucket` - Updated liquidity receipt.
         /// * `Bucket` - Tokens x that were not used.
         /// * `Bucket` - Tokens y that were not used.
         /// 
         /// # Panics
         /// 
+        /// * If the liquidity receipt is invalid.
         /// * If the tick of a position is not in the range of [0, 54000].
         /// * If the tick of a position is not aligned to the bin span.
         /// * If there are not enough tokens to add liquidity.
@@ -948,12 +1258,107 @@ mod quantaswap {
         /// 
         /// # Events
         /// 
-        /// * `MintLiquidityReceiptEvent` - Event emitted when liquidity receipt is minted.
         /// * `AddLiquidityEvent` - Event emitted when liquidity is added.
         /// * `ValuationEvent` - Event emitted when the value of the pool changes.
         /// 
-        pub fn add_liquidity(
-            &mut self,
+        pub fn add_liquidity_to_receipt(
+            &mut self, 
+            liquidity_receipt: Bucket,
+            mut tokens_x: Bucket, 
+            mut tokens_y: Bucket, 
+            positions: Vec<(u32, Decimal, Decimal)>,
+        ) -> (Bucket, Bucket, Bucket) {
+            assert!(
+                liquidity_receipt.resource_address() == self.liquidity_receipt_manager.address(),
+                "Invalid liquidity receipt."
+            );
+
+            // Record price observation
+            self.update_observations();
+
+            // Get liquidity claims
+            let liquidity_receipt = liquidity_receipt.as_non_fungible();
+            let liquidity_receipt_nft = liquidity_receipt.non_fungible::<LiquidityReceipt>();
+            let LiquidityReceipt { mut liquidity_claims, mut fee_checkpoints, .. } = liquidity_receipt_nft.data();
+
+            // Add liquidity at positions
+            let (amount_x_used, amount_y_used, added_x, added_y) = self.add_to_claims(&mut liquidity_claims, &mut fee_checkpoints, positions);
+
+            // Deposit tokens
+            self.tokens_x.put(tokens_x.take_advanced(amount_x_used, INCOMING));
+            self.tokens_y.put(tokens_y.take_advanced(amount_y_used, INCOMING));
+
+            // Assert that there are no more than 200 liquidity claims
+            assert!(
+                liquidity_claims.len() <= 200,
+                "Too many liquidity claims for this liquidity receipt."
+            );
+
+            // Update liquidity receipt
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "liquidity_claims", 
+                liquidity_claims
+            );
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "fee_checkpoints", 
+                fee_checkpoints
+            );
+
+            // Emit add liquidity event
+            Runtime::emit_event(AddLiquidityEvent {
+                liquidity_receipt_id: liquidity_receipt_nft.local_id().clone(),
+                amount_change_x: added_x.iter().fold(dec!(0), |sum, &(_, x)| sum + x),
+                amount_change_y: added_y.iter().fold(dec!(0), |sum, &(_, y)| sum + y),
+                added_x,
+                added_y,
+            });
+
+            // Emit valuation event
+            Runtime::emit_event(ValuationEvent {
+                amount_after_x: self.get_amount_x(),
+                amount_after_y: self.get_amount_y(),
+                price_after: self.get_price().unwrap_or_default(),
+            });
+
+            // Return buckets
+            (liquidity_receipt.into(), tokens_x, tokens_y)
+        }
+
+        /// Add tokens as liquidity at the specified positions.
+        /// If the position is above the active bin, tokens x will be used.
+        /// If the position is below the active bin, tokens y will be used.
+        /// If the position is at the current price, tokens x and y will be used in proportion current ratio.
+        /// If it is the first liquidity position to be added, all tokens will be used and it will become the active bin.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_x` - Tokens x to add as liquidity.
+        /// * `tokens_y` - Tokens y to add as liquidity.
+        /// * `positions` - Positions at which to add liquidity. Format: (tick, amount_x, amount_y).
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Liquidity receipt.
+        /// * `Bucket` - Tokens x that were not used.
+        /// * `Bucket` - Tokens y that were not used.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the tick of a position is not in the range of [0, 54000].
+        /// * If the tick of a position is not aligned to the bin span.
+        /// * If there are not enough tokens to add liquidity.
+        /// * If the total number of liquidity claims for the liquidity receipt exceeds 200.
+        /// 
+        /// # Events
+        /// 
+        /// * `MintLiquidityReceiptEvent` - Event emitted when liquidity receipt is minted.
+        /// * `AddLiquidityEvent` - Event emitted when liquidity is added.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// 
+        pub fn add_liquidity(
+            &mut self,
             tokens_x: Bucket,
             tokens_y: Bucket,
             positions: Vec<(u32, Decimal, Decimal)>,
@@ -965,6 +1370,99 @@ mod quantaswap {
             self.add_liquidity_to_receipt(liquidity_receipt, tokens_x, tokens_y, positions)
         }
 
+        /// Add tokens as liquidity across a range of bins following a distribution shape.
+        /// Bins above the active bin get tokens x, bins below the active bin get tokens y, and the active bin gets both.
+        /// Tokens x and y are each split across their bins using the weights of the shape.
+        /// The active bin only uses tokens in proportion to its current ratio, and the rest are returned.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_x` - Tokens x to add as liquidity.
+        /// * `tokens_y` - Tokens y to add as liquidity.
+        /// * `lower_tick` - Tick of the lowest bin in the range.
+        /// * `upper_tick` - Tick of the highest bin in the range.
+        /// * `shape` - Shape of the distribution.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Liquidity receipt.
+        /// * `Bucket` - Tokens x that were not used.
+        /// * `Bucket` - Tokens y that were not used.
+        /// 
+        /// # Panics
+        /// 
+        /// * If there is no active bin.
+        /// * If `lower_tick` or `upper_tick` is not in the range of [0, 54000].
+        /// * If `lower_tick` or `upper_tick` is not aligned to the bin span.
+        /// * If `lower_tick` is greater than `upper_tick`.
+        /// * If the standard deviation of a normal shape is not greater than zero.
+        /// * If the number of bins in the range exceeds 200.
+        /// 
+        /// # Events
+        /// 
+        /// * `MintLiquidityReceiptEvent` - Event emitted when liquidity receipt is minted.
+        /// * `AddLiquidityEvent` - Event emitted when liquidity is added.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// 
+        pub fn add_liquidity_with_distribution(
+            &mut self,
+            tokens_x: Bucket,
+            tokens_y: Bucket,
+            lower_tick: u32,
+            upper_tick: u32,
+            shape: LiquidityShape,
+        ) -> (Bucket, Bucket, Bucket) {
+            // Get the active tick
+            let active_tick = self.tick_index.current().expect("No active bin.");
+
+            // Check range and shape
+            let lower_tick: Tick = Tick(lower_tick);
+            let upper_tick: Tick = Tick(upper_tick);
+            assert!(lower_tick.is_valid(self.bin_span), "Invalid tick {:?}.", lower_tick);
+            assert!(upper_tick.is_valid(self.bin_span), "Invalid tick {:?}.", upper_tick);
+            assert!(lower_tick <= upper_tick, "Lower tick must not be greater than upper tick.");
+            assert!(
+                (upper_tick.0 - lower_tick.0) / self.bin_span < 200,
+                "Too many liquidity claims for this liquidity receipt."
+            );
+            if let LiquidityShape::Normal(std_dev) = shape {
+                assert!(std_dev > Decimal::zero(), "Standard deviation must be greater than zero.");
+            }
+
+            // Only use the active bin if both tokens are provided
+            let use_active = lower_tick <= active_tick && active_tick <= upper_tick 
+                && !tokens_x.is_empty() && !tokens_y.is_empty();
+
+            // Collect bins on each side of the active bin with their distance in bins
+            let mut bins_x: Vec<(u32, u32)> = Vec::new();
+            let mut bins_y: Vec<(u32, u32)> = Vec::new();
+            let mut tick = lower_tick.0;
+            while tick <= upper_tick.0 {
+                if tick > active_tick.0 || (tick == active_tick.0 && use_active) {
+                    bins_x.push((tick, (tick - active_tick.0) / self.bin_span));
+                }
+                if tick < active_tick.0 || (tick == active_tick.0 && use_active) {
+                    bins_y.push((tick, (active_tick.0 - tick) / self.bin_span));
+                }
+                tick += self.bin_span;
+            }
+
+            // Distribute tokens across bins
+            let mut positions: Vec<(u32, Decimal, Decimal)> = Self::distribute_liquidity(tokens_x.amount(), &bins_x, shape)
+                .into_iter()
+                .map(|(tick, amount_x)| (tick, amount_x, Decimal::zero()))
+                .collect();
+            for (tick, amount_y) in Self::distribute_liquidity(tokens_y.amount(), &bins_y, shape) {
+                match positions.iter_mut().find(|position| position.0 == tick) {
+                    Some(position) => position.2 = amount_y,
+                    None => positions.push((tick, Decimal::zero(), amount_y)),
+                }
+            }
+
+            // Add liquidity and return buckets
+            self.add_liquidity(tokens_x, tokens_y, positions)
+        }
+
         /// Remove liquidity from a liquidity receipt using the specified liquidity claims.
         /// Returns (tokens_x, tokens_y).
         /// 
@@ -996,62 +1494,21 @@ mod quantaswap {
                 "Invalid liquidity receipt."
             );
 
+            // Record price observation
+            self.update_observations();
+
             // Get liquidity claims
             let liquidity_receipt = liquidity_receipt.as_non_fungible();
             let liquidity_receipt_nft = liquidity_receipt.non_fungible::<LiquidityReceipt>();
-            let mut liquidity_claims = liquidity_receipt_nft.data().liquidity_claims;
-
-            // Amounts to track tokens claimed
-            let mut amount_x = Decimal::zero();
-            let mut amount_y = Decimal::zero();
-            let mut removed_x: Vec<(u32, Decimal)> = Vec::new();
-            let mut removed_y: Vec<(u32, Decimal)> = Vec::new();
+            let LiquidityReceipt { mut liquidity_claims, mut fee_checkpoints, .. } = liquidity_receipt_nft.data();
 
-            // Get current tick
-            let mut current_tick = match self.tick_index.current() {
-                Some(tick) => tick,
-                None => return (liquidity_receipt.into(), Bucket::new(self.tokens_x.resource_address()), Bucket::new(self.tokens_y.resource_address())),
-            };
+            // Return early if there is no liquidity
+            if self.tick_index.current().is_none() {
+                return (liquidity_receipt.into(), Bucket::new(self.tokens_x.resource_address()), Bucket::new(self.tokens_y.resource_address()));
+            }
 
             // Remove liquidity
-            for (tick, claim) in claims {
-                assert!(
-                    claim > Decimal::zero(),
-                    "Claim must be greater than zero."
-                );
-
-                let available_claim = liquidity_claims.get_mut(&tick).expect("Claim does not exist.");
-                let claim = if *available_claim > claim {
-                    *available_claim -= claim;
-                    claim
-                } else {
-                    liquidity_claims.remove(&tick).unwrap()
-                };
-                let tick: Tick = Tick(tick);
-
-                // Withdraw tokens
-                if tick > current_tick { // bin above
-                    let change_x = self.remove_liquidity_from_bin(tick, claim);
-                    amount_x += change_x;
-                    removed_x.push((tick.0, -change_x));
-                } else if tick < current_tick { // bin below
-                    let change_y = self.remove_liquidity_from_bin(tick, claim);
-                    amount_y += change_y;
-                    removed_y.push((tick.0, -change_y));
-                } else { // active bin
-                    let (change_x, change_y) = self.remove_active_liquidity(current_tick, claim);
-                    amount_x += change_x;
-                    amount_y += change_y;
-                    removed_x.push((tick.0, -change_x));
-                    removed_y.push((tick.0, -change_y));
-
-                    // Update current tick incase it changed
-                    let check_tick = self.tick_index.current();
-                    if check_tick.is_some() {
-                        current_tick = check_tick.unwrap();
-                    }
-                }
-            }
+            let (amount_x, amount_y, removed_x, removed_y) = self.remove_from_claims(&mut liquidity_claims, &mut fee_checkpoints, claims);
 
             // Update liquidity receipt
             self.liquidity_receipt_manager.update_non_fungible_data(
@@ -1059,6 +1516,11 @@ mod quantaswap {
                 "liquidity_claims", 
                 liquidity_claims
             );
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "fee_checkpoints", 
+                fee_checkpoints
+            );
 
             // Create buckets
             let tokens_x = self.tokens_x.take_advanced(amount_x, OUTGOING);
@@ -1084,7 +1546,7 @@ mod quantaswap {
             (liquidity_receipt.into(), tokens_x, tokens_y)
         }
 
-        /// Remove all liquidity from a liquidity receipt and burn the receipt.
+        /// Remove all liquidity and range orders from a liquidity receipt and burn the receipt.
         /// Returns (tokens_x, tokens_y).
         /// 
         /// # Arguments
@@ -1105,6 +1567,7 @@ mod quantaswap {
         /// # Events
         /// 
         /// * `RemoveLiquidityEvent` - Event emitted when liquidity is removed.
+        /// * `RemoveRangeOrderEvent` - Event emitted for each range order removed.
         /// * `BurnLiquidityReceiptEvent` - Event emitted when a liquidity receipt is burned.
         /// * `ValuationEvent` - Event emitted when the value of the pool changes.
         /// 
@@ -1114,10 +1577,19 @@ mod quantaswap {
         ) -> (Bucket, Bucket) {
             // Get liquidity claims
             let liquidity_receipt_nft = liquidity_receipt.as_non_fungible().non_fungible::<LiquidityReceipt>();
-            let liquidity_claims: Vec<(u32, Decimal)> = liquidity_receipt_nft.data().liquidity_claims.into_iter().collect();
+            let LiquidityReceipt { liquidity_claims, range_orders, .. } = liquidity_receipt_nft.data();
+            let liquidity_claims: Vec<(u32, Decimal)> = liquidity_claims.into_iter().collect();
 
             // Remove liquidity
-            let (liquidity_receipt, token_x, tokens_y) = self.remove_specific_liquidity(liquidity_receipt, liquidity_claims);
+            let (mut liquidity_receipt, mut token_x, mut tokens_y) = self.remove_specific_liquidity(liquidity_receipt, liquidity_claims);
+
+            // Remove range orders
+            for tick in range_orders.into_keys() {
+                let (updated_liquidity_receipt, range_order_x, range_order_y) = self.remove_range_order(liquidity_receipt, tick);
+                liquidity_receipt = updated_liquidity_receipt;
+                token_x.put(range_order_x);
+                tokens_y.put(range_order_y);
+            }
 
             // Burn liquidity receipt
             self.burn_liquidity_receipt(liquidity_receipt);
@@ -1126,50 +1598,483 @@ mod quantaswap {
             (token_x, tokens_y)
         }
 
-        /// Swap tokens.
+        /// Move liquidity of a liquidity receipt by removing claims and adding new positions in one step.
+        /// Tokens removed are netted against tokens added, so only the difference is deposited or withdrawn.
+        /// Claims are removed before the new positions are added.
         /// 
         /// # Arguments
         /// 
-        /// * `tokens` - Tokens to swap.
+        /// * `liquidity_receipt` - Liquidity receipt that contains the liquidity claims.
+        /// * `tokens_x` - Extra tokens x to add as liquidity. May be empty.
+        /// * `tokens_y` - Extra tokens y to add as liquidity. May be empty.
+        /// * `remove_claims` - Liquidity claims to remove. Format: (tick, amount_claim).
+        /// * `new_positions` - Positions at which to add liquidity. Format: (tick, amount_x, amount_y).
         /// 
         /// # Returns
         /// 
-        /// * `Bucket` - Tokens bought.
-        /// * `Bucket` - Tokens leftover.
+        /// * `Bucket` - Updated liquidity receipt.
+        /// * `Bucket` - Tokens x that were not used.
+        /// * `Bucket` - Tokens y that were not used.
         /// 
         /// # Panics
         /// 
-        /// * If the tokens are not tokens x or tokens y.
-        /// * If the swap would result in a breaking state change due to extreme liquidity conditions.
+        /// * If the liquidity receipt is invalid.
+        /// * If a claim amount is not greater than zero.
+        /// * If a claim does not exist.
+        /// * If the tick of a position is not in the range of [0, 54000].
+        /// * If the tick of a position is not aligned to the bin span.
+        /// * If there are not enough tokens removed and provided to add liquidity.
+        /// * If the total number of liquidity claims for the liquidity receipt exceeds 200.
         /// 
         /// # Events
         /// 
-        /// * `SwapEvent` - Event emitted when tokens are swapped.
+        /// * `RebalanceLiquidityEvent` - Event emitted when liquidity is rebalanced.
         /// * `ValuationEvent` - Event emitted when the value of the pool changes.
-        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
-        /// * `LiquidityFeeEvent` - Event emitted when liquidity fee is collected.
         /// 
-        pub fn swap(&mut self, tokens: Bucket) -> (Bucket, Bucket) {
-            let token_address = tokens.resource_address();
-            if token_address == self.tokens_x.resource_address() {
-                self.swap_x(tokens)
-            } else if token_address == self.tokens_y.resource_address() {
-                self.swap_y(tokens)
-            } else {
-                panic!("Invalid token address.")
+        pub fn rebalance_liquidity(
+            &mut self,
+            liquidity_receipt: Bucket,
+            mut tokens_x: Bucket,
+            mut tokens_y: Bucket,
+            remove_claims: Vec<(u32, Decimal)>,
+            new_positions: Vec<(u32, Decimal, Decimal)>,
+        ) -> (Bucket, Bucket, Bucket) {
+            assert!(
+                liquidity_receipt.resource_address() == self.liquidity_receipt_manager.address(),
+                "Invalid liquidity receipt."
+            );
+
+            // Record price observation
+            self.update_observations();
+
+            // Get liquidity claims
+            let liquidity_receipt = liquidity_receipt.as_non_fungible();
+            let liquidity_receipt_nft = liquidity_receipt.non_fungible::<LiquidityReceipt>();
+            let LiquidityReceipt { mut liquidity_claims, mut fee_checkpoints, .. } = liquidity_receipt_nft.data();
+
+            // Remove liquidity and add liquidity at new positions
+            let (amount_x_removed, amount_y_removed, removed_x, removed_y) = self.remove_from_claims(&mut liquidity_claims, &mut fee_checkpoints, remove_claims);
+            let (amount_x_added, amount_y_added, added_x, added_y) = self.add_to_claims(&mut liquidity_claims, &mut fee_checkpoints, new_positions);
+
+            // Net tokens removed against tokens added
+            let amount_change_x = amount_x_added - amount_x_removed;
+            let amount_change_y = amount_y_added - amount_y_removed;
+            if amount_change_x > Decimal::zero() {
+                self.tokens_x.put(tokens_x.take_advanced(amount_change_x, INCOMING));
+            } else if amount_change_x < Decimal::zero() {
+                tokens_x.put(self.tokens_x.take_advanced(-amount_change_x, OUTGOING));
             }
+            if amount_change_y > Decimal::zero() {
+                self.tokens_y.put(tokens_y.take_advanced(amount_change_y, INCOMING));
+            } else if amount_change_y < Decimal::zero() {
+                tokens_y.put(self.tokens_y.take_advanced(-amount_change_y, OUTGOING));
+            }
+
+            // Assert that there are no more than 200 liquidity claims
+            assert!(
+                liquidity_claims.len() <= 200,
+                "Too many liquidity claims for this liquidity receipt."
+            );
+
+            // Update liquidity receipt
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "liquidity_claims", 
+                liquidity_claims
+            );
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "fee_checkpoints", 
+                fee_checkpoints
+            );
+
+            // Emit rebalance liquidity event
+            Runtime::emit_event(RebalanceLiquidityEvent {
+                liquidity_receipt_id: liquidity_receipt_nft.local_id().clone(),
+                amount_change_x,
+                amount_change_y,
+                removed_x,
+                removed_y,
+                added_x,
+                added_y,
+            });
+
+            // Emit valuation event
+            Runtime::emit_event(ValuationEvent {
+                amount_after_x: self.get_amount_x(),
+                amount_after_y: self.get_amount_y(),
+                price_after: self.get_price().unwrap_or_default(),
+            });
+
+            // Return buckets
+            (liquidity_receipt.into(), tokens_x, tokens_y)
         }
 
-        /// Swap tokens x for tokens y.
+        /// Add a range order to a liquidity receipt.
+        /// A range order is liquidity on a single bin that is taken out of the bin once a swap converts it and moves past it.
+        /// The converted tokens are set aside and can be removed with the liquidity receipt.
+        /// Range orders of tokens x must be above the active bin, and range orders of tokens y must be below it.
         /// 
         /// # Arguments
         /// 
-        /// * `tokens_x` - Tokens x to swap.
+        /// * `liquidity_receipt` - Liquidity receipt that will be used to track the range order.
+        /// * `tokens` - Tokens x or y to place in the range order.
+        /// * `tick` - Tick of the bin to place the range order on.
         /// 
         /// # Returns
         /// 
-        /// * `Bucket` - Tokens y bought.
-        /// * `Bucket` - Tokens x leftover.
+        /// * `Bucket` - Updated liquidity receipt.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the liquidity receipt is invalid.
+        /// * If there is no active bin.
+        /// * If the tick is not in the range of [0, 54000].
+        /// * If the tick is not aligned to the bin span.
+        /// * If the amount of tokens is not greater than zero.
+        /// * If `tokens` is not tokens x or tokens y.
+        /// * If the tick is not on the side of the active bin required for `tokens`.
+        /// * If a settled range order on the tick has not been removed.
+        /// * If the total number of range orders for the liquidity receipt exceeds 200.
+        /// 
+        /// # Events
+        /// 
+        /// * `AddRangeOrderEvent` - Event emitted when a range order is added.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// 
+        pub fn add_range_order(&mut self, liquidity_receipt: Bucket, tokens: Bucket, tick: u32) -> Bucket {
+            assert!(
+                liquidity_receipt.resource_address() == self.liquidity_receipt_manager.address(),
+                "Invalid liquidity receipt."
+            );
+
+            // Record price observation
+            self.update_observations();
+
+            // Get the active tick
+            let active_tick = self.tick_index.current().expect("No active bin.");
+
+            // Check tick and tokens
+            let tick: Tick = Tick(tick);
+            assert!(tick.is_valid(self.bin_span), "Invalid tick {:?}.", tick);
+            let amount = tokens.amount();
+            assert!(amount > Decimal::zero(), "Amount must be greater than zero.");
+            let (amount_x, amount_y) = if tokens.resource_address() == self.tokens_x.resource_address() {
+                assert!(tick > active_tick, "Range orders of tokens x must be above the active bin.");
+                (amount, Decimal::zero())
+            } else if tokens.resource_address() == self.tokens_y.resource_address() {
+                assert!(tick < active_tick, "Range orders of tokens y must be below the active bin.");
+                (Decimal::zero(), amount)
+            } else {
+                panic!("Invalid token address.")
+            };
+
+            // Get range orders
+            let liquidity_receipt = liquidity_receipt.as_non_fungible();
+            let liquidity_receipt_nft = liquidity_receipt.non_fungible::<LiquidityReceipt>();
+            let mut range_orders = liquidity_receipt_nft.data().range_orders;
+
+            // Add liquidity to bin
+            let claim = self.add_liquidity_to_bin(tick, amount);
+
+            // Add to the range order group resting in the bin
+            let mut group = match self.range_order_map.get(&tick).map(|group| *group) {
+                Some(group) => group,
+                None => {
+                    self.range_order_group_count += 1;
+                    RangeOrderGroup {
+                        group_id: self.range_order_group_count,
+                        claim: Decimal::zero(),
+                        is_x: amount_x > Decimal::zero(),
+                    }
+                }
+            };
+            group.claim += claim;
+            self.range_order_map.insert(tick, group);
+
+            // Add to range order
+            let range_order = range_orders.entry(tick.0).or_insert(RangeOrder {
+                group_id: group.group_id,
+                claim: Decimal::zero(),
+            });
+            assert!(
+                range_order.group_id == group.group_id,
+                "Settled range order on tick {} must be removed first.", tick.0
+            );
+            range_order.claim += claim;
+
+            // Assert that there are no more than 200 range orders
+            assert!(
+                range_orders.len() <= 200,
+                "Too many range orders for this liquidity receipt."
+            );
+
+            // Deposit tokens
+            if amount_x > Decimal::zero() {
+                self.tokens_x.put(tokens);
+            } else {
+                self.tokens_y.put(tokens);
+            }
+
+            // Update liquidity receipt
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "range_orders", 
+                range_orders
+            );
+
+            // Emit add range order event
+            Runtime::emit_event(AddRangeOrderEvent {
+                liquidity_receipt_id: liquidity_receipt_nft.local_id().clone(),
+                tick: tick.0,
+                amount_change_x: amount_x,
+                amount_change_y: amount_y,
+            });
+
+            // Emit valuation event
+            Runtime::emit_event(ValuationEvent {
+                amount_after_x: self.get_amount_x(),
+                amount_after_y: self.get_amount_y(),
+                price_after: self.get_price().unwrap_or_default(),
+            });
+
+            // Return liquidity receipt
+            liquidity_receipt.into()
+        }
+
+        /// Remove a range order from a liquidity receipt.
+        /// If the range order has been settled, the tokens set aside for it are returned.
+        /// Otherwise the range order is cancelled and its share of the bin is returned.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `liquidity_receipt` - Liquidity receipt that contains the range order.
+        /// * `tick` - Tick of the range order.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Updated liquidity receipt.
+        /// * `Bucket` - Tokens x that were removed.
+        /// * `Bucket` - Tokens y that were removed.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the liquidity receipt is invalid.
+        /// * If the range order does not exist.
+        /// 
+        /// # Events
+        /// 
+        /// * `RemoveRangeOrderEvent` - Event emitted when a range order is removed.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// 
+        pub fn remove_range_order(&mut self, liquidity_receipt: Bucket, tick: u32) -> (Bucket, Bucket, Bucket) {
+            assert!(
+                liquidity_receipt.resource_address() == self.liquidity_receipt_manager.address(),
+                "Invalid liquidity receipt."
+            );
+
+            // Record price observation
+            self.update_observations();
+
+            // Get range order
+            let liquidity_receipt = liquidity_receipt.as_non_fungible();
+            let liquidity_receipt_nft = liquidity_receipt.non_fungible::<LiquidityReceipt>();
+            let mut range_orders = liquidity_receipt_nft.data().range_orders;
+            let range_order = range_orders.remove(&tick).expect("Range order does not exist.");
+            let tick: Tick = Tick(tick);
+
+            // Remove settled or resting range order
+            let settled = self.settled_range_order_map.get(&range_order.group_id).map(|settled| *settled);
+            let (amount_x, amount_y) = match settled {
+                Some(mut settled) => {
+                    // Take share of the tokens set aside
+                    let (amount_x, amount_y) = if range_order.claim == settled.claim {
+                        (settled.amount_x, settled.amount_y)
+                    } else {
+                        let share = range_order.claim / settled.claim;
+                        (settled.amount_x * share, settled.amount_y * share)
+                    };
+
+                    // Update settled range orders
+                    settled.claim -= range_order.claim;
+                    settled.amount_x -= amount_x;
+                    settled.amount_y -= amount_y;
+                    if settled.claim == Decimal::zero() {
+                        self.settled_range_order_map.remove(&range_order.group_id);
+                    } else {
+                        self.settled_range_order_map.insert(range_order.group_id, settled);
+                    }
+
+                    (amount_x, amount_y)
+                },
+                None => {
+                    // Update range order group resting in the bin
+                    let mut group = *self.range_order_map.get(&tick).unwrap();
+                    group.claim -= range_order.claim;
+                    if group.claim == Decimal::zero() {
+                        self.range_order_map.remove(&tick);
+                    } else {
+                        self.range_order_map.insert(tick, group);
+                    }
+
+                    // Remove liquidity from bin
+                    let current_tick = self.tick_index.current().unwrap();
+                    if tick > current_tick { // bin above
+                        (self.remove_liquidity_from_bin(tick, range_order.claim), Decimal::zero())
+                    } else if tick < current_tick { // bin below
+                        (Decimal::zero(), self.remove_liquidity_from_bin(tick, range_order.claim))
+                    } else { // active bin
+                        self.remove_active_liquidity(current_tick, range_order.claim)
+                    }
+                },
+            };
+
+            // Update liquidity receipt
+            self.liquidity_receipt_manager.update_non_fungible_data(
+                liquidity_receipt_nft.local_id(), 
+                "range_orders", 
+                range_orders
+            );
+
+            // Create buckets
+            let (tokens_x, tokens_y) = if settled.is_some() {
+                (self.settled_tokens_x.take_advanced(amount_x, OUTGOING), self.settled_tokens_y.take_advanced(amount_y, OUTGOING))
+            } else {
+                (self.tokens_x.take_advanced(amount_x, OUTGOING), self.tokens_y.take_advanced(amount_y, OUTGOING))
+            };
+
+            // Emit remove range order event
+            Runtime::emit_event(RemoveRangeOrderEvent {
+                liquidity_receipt_id: liquidity_receipt_nft.local_id().clone(),
+                tick: tick.0,
+                amount_change_x: -amount_x,
+                amount_change_y: -amount_y,
+                settled: settled.is_some(),
+            });
+
+            // Emit valuation event
+            Runtime::emit_event(ValuationEvent {
+                amount_after_x: self.get_amount_x(),
+                amount_after_y: self.get_amount_y(),
+                price_after: self.get_price().unwrap_or_default(),
+            });
+
+            // Return buckets
+            (liquidity_receipt.into(), tokens_x, tokens_y)
+        }
+
+        /// Swap tokens.
+        /// If a limit price is given, the swap stops when the price reaches the limit and the unused tokens are returned.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens` - Tokens to swap.
+        /// * `min_amount_out` - Optional minimum amount of tokens bought. If none, any amount is accepted.
+        /// * `limit_price` - Optional limit price, a floor when swapping tokens x and a ceiling when swapping tokens y. If none, swap will not stop until filled.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Tokens bought.
+        /// * `Bucket` - Tokens leftover.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the tokens are not tokens x or tokens y.
+        /// * If `limit_price` is not greater than zero.
+        /// * If the amount of tokens bought is less than `min_amount_out`.
+        /// * If the swap would result in a breaking state change due to extreme liquidity conditions.
+        /// 
+        /// # Events
+        /// 
+        /// * `SwapEvent` - Event emitted when tokens are swapped.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// * `LiquidityFeeEvent` - Event emitted when liquidity fee is collected.
+        /// 
+        pub fn swap(&mut self, tokens: Bucket, min_amount_out: Option<Decimal>, limit_price: Option<Decimal>) -> (Bucket, Bucket) {
+            // Convert limit price to price sqrt
+            let limit_price_sqrt = limit_price.map(|price| {
+                assert!(price > Decimal::zero(), "Limit price must be greater than zero.");
+                calculate_price_sqrt(&price)
+            });
+
+            // Record price observation
+            self.update_observations();
+
+            // Swap tokens
+            let token_address = tokens.resource_address();
+            let (tokens_out, tokens_leftover) = if token_address == self.tokens_x.resource_address() {
+                self.swap_x(tokens, limit_price_sqrt)
+            } else if token_address == self.tokens_y.resource_address() {
+                self.swap_y(tokens, limit_price_sqrt)
+            } else {
+                panic!("Invalid token address.")
+            };
+
+            // Check minimum amount out
+            if let Some(min_amount_out) = min_amount_out {
+                assert!(tokens_out.amount() >= min_amount_out, "Amount out is less than minimum amount out.");
+            }
+
+            (tokens_out, tokens_leftover)
+        }
+
+        /// Swap tokens for an exact amount of the opposite token.
+        /// The input tokens needed are calculated from the amount out, with fees added on top.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_in` - Tokens to swap.
+        /// * `amount_out` - Exact amount of opposite tokens to buy.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Tokens bought.
+        /// * `Bucket` - Tokens leftover.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the tokens are not tokens x or tokens y.
+        /// * If `amount_out` is not greater than zero.
+        /// * If `amount_out` is not a multiple of the divisibility of the tokens bought.
+        /// * If there is not enough liquidity to buy `amount_out`.
+        /// * If there are not enough input tokens to buy `amount_out`.
+        /// 
+        /// # Events
+        /// 
+        /// * `SwapEvent` - Event emitted when tokens are swapped.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// * `LiquidityFeeEvent` - Event emitted when liquidity fee is collected.
+        /// 
+        pub fn swap_exact_output(&mut self, tokens_in: Bucket, amount_out: Decimal) -> (Bucket, Bucket) {
+            assert!(amount_out > Decimal::zero(), "Amount out must be greater than zero.");
+
+            // Record price observation
+            self.update_observations();
+
+            // Swap tokens
+            let token_address = tokens_in.resource_address();
+            if token_address == self.tokens_x.resource_address() {
+                self.swap_exact_output_x(tokens_in, amount_out)
+            } else if token_address == self.tokens_y.resource_address() {
+                self.swap_exact_output_y(tokens_in, amount_out)
+            } else {
+                panic!("Invalid token address.")
+            }
+        }
+
+        /// Swap tokens x for tokens y.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_x` - Tokens x to swap.
+        /// * `limit_price_sqrt` - Optional limit price sqrt at which the swap stops.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Tokens y bought.
+        /// * `Bucket` - Tokens x leftover.
         /// 
         /// # Requires
         /// 
@@ -1182,12 +2087,12 @@ mod quantaswap {
         /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
         /// * `LiquidityFeeEvent` - Event emitted when liquidity fee is collected.
         ///
-        fn swap_x(&mut self, mut tokens_x: Bucket) -> (Bucket, Bucket) {
-            // Get the current tick
-            let current_tick = self.tick_index.current();
-            if current_tick.is_none() {
-                return (Bucket::new(self.tokens_y.resource_address()), tokens_x);
-            }
+        fn swap_x(&mut self, mut tokens_x: Bucket, limit_price_sqrt: Option<Decimal>) -> (Bucket, Bucket) {
+            // Get the active bin
+            let active_bin = match self.get_active_bin() {
+                Some(active_bin) => active_bin,
+                None => return (Bucket::new(self.tokens_y.resource_address()), tokens_x),
+            };
 
             // Get fee percentages
             let (protocol_fee, liquidity_fee) = FEE_CONTROLLER.get_fees(
@@ -1199,78 +2104,9 @@ mod quantaswap {
             let mut liquidity_fee_tokens = tokens_x.take_advanced(tokens_x.amount() * liquidity_fee, INCOMING);
             let amount_x_input = tokens_x.amount();
 
-            // Variables to track token amounts
-            let mut amount_x = amount_x_input;
-            let mut amount_y = Decimal::zero();
-            
-            // Loop through bins
-            let mut tick: Tick = current_tick.unwrap();
-            loop {
-                // Get virtual amounts
-                let (virtual_x, virtual_y) = self.virtual_amounts();
-
-                // Calculate swap
-                let amount_y_swap = calculate_swap(&amount_x, &virtual_x, &virtual_y);
-
-                // Check if swap is not possible, meaning bounds of the active bin have been reached
-                if amount_y_swap >= self.active_y {
-                    // Calculate inverse swap, round up from zero
-                    let amount_x_swap = calculate_swap_inverse(&self.active_y, &virtual_x, &virtual_y);
-                    let amount_x_swap = if amount_x_swap > Decimal::zero() {
-                        amount_x_swap
-                    } else if self.active_y == Decimal::zero() {
-                        Decimal::ZERO
-                    } else {
-                        Decimal(I192::from_digits([1, 0, 0]))
-                    };
-                    let amount_x_swap_with_fee = amount_x_swap / (Decimal::ONE - liquidity_fee);
-
-                    // Update amounts
-                    amount_x -= amount_x_swap;
-                    amount_y += self.active_y;
-                    
-                    // Check if there is another bin
-                    let next_tick: Option<Tick> = self.tick_index.move_down();
-                    if next_tick.is_some() {
-                        // Save active bin
-                        self.bin_map.insert(tick, 
-                            Bin {
-                                amount: self.active_x + amount_x_swap_with_fee, 
-                                total_claim: self.active_total_claim
-                            }
-                        );
-
-                        // Get next bin
-                        tick = next_tick.unwrap();
-                        let bin = self.bin_map.get(&tick).unwrap();
-
-                        // Update active bin state
-                        self.active_x = Decimal::zero();
-                        self.active_y = bin.amount;
-                        self.active_total_claim = bin.total_claim;
-                        self.lower_limit = tick.into();
-                        self.upper_limit = tick.tick_upper(self.bin_span).into();
-                    } else {
-                        // Update active bin state
-                        self.active_x += amount_x_swap_with_fee;
-                        self.active_y = Decimal::zero();
-
-                        break;
-                    }
-                } else {
-                    let amount_x_swap_with_fee = amount_x / (Decimal::ONE - liquidity_fee);
-
-                    // Update amounts
-                    amount_x = Decimal::zero();
-                    amount_y += amount_y_swap;
-
-                    // Update active bin state
-                    self.active_x += amount_x_swap_with_fee;
-                    self.active_y -= amount_y_swap;
-
-                    break;
-                }
-            }
+            // Walk swap through bins
+            let walk = self.walk_swap_x(active_bin, amount_x_input, liquidity_fee, limit_price_sqrt);
+            let (amount_x, amount_y) = (amount_x_input - walk.amount_in, walk.amount_out);
 
             // Create withdraw and deposit tokens
             let tokens_y = self.tokens_y.take_advanced(amount_y, OUTGOING);
@@ -1292,6 +2128,9 @@ mod quantaswap {
             FEE_VAULTS.deposit(protocol_fee_tokens);
             self.tokens_x.put(liquidity_fee_tokens);
 
+            // Apply swap to the pool once the tokens swapped are deposited, as settled range orders are taken from them
+            self.apply_swap_walk(walk, true);
+
             // Emit fee events
             Runtime::emit_event(ProtocolFeeEvent {
                 token_address: self.tokens_x.resource_address(),
@@ -1328,6 +2167,7 @@ mod quantaswap {
         /// # Arguments
         /// 
         /// * `tokens_y` - Tokens y to swap.
+        /// * `limit_price_sqrt` - Optional limit price sqrt at which the swap stops.
         /// 
         /// # Returns
         /// 
@@ -1345,12 +2185,12 @@ mod quantaswap {
         /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
         /// * `LiquidityFeeEvent` - Event emitted when liquidity fee is collected.
         /// 
-        fn swap_y(&mut self, mut tokens_y: Bucket) -> (Bucket, Bucket) {
-            // Get the current tick
-            let current_tick = self.tick_index.current();
-            if current_tick.is_none() {
-                return (Bucket::new(self.tokens_x.resource_address()), tokens_y);
-            }
+        fn swap_y(&mut self, mut tokens_y: Bucket, limit_price_sqrt: Option<Decimal>) -> (Bucket, Bucket) {
+            // Get the active bin
+            let active_bin = match self.get_active_bin() {
+                Some(active_bin) => active_bin,
+                None => return (Bucket::new(self.tokens_x.resource_address()), tokens_y),
+            };
 
             // Get fee percentages
             let (protocol_fee, liquidity_fee) = FEE_CONTROLLER.get_fees(
@@ -1362,85 +2202,16 @@ mod quantaswap {
             let mut liquidity_fee_tokens = tokens_y.take_advanced(tokens_y.amount() * liquidity_fee, INCOMING);
             let amount_y_input = tokens_y.amount();
 
-            // Variables to track token amounts
-            let mut amount_y = amount_y_input;
-            let mut amount_x = Decimal::zero();
-            
-            // Loop through liquidity pots
-            let mut tick = current_tick.unwrap();
-            loop {
-                // Get virtual amounts
-                let (virtual_x, virtual_y) = self.virtual_amounts();
+            // Walk swap through bins
+            let walk = self.walk_swap_y(active_bin, amount_y_input, liquidity_fee, limit_price_sqrt);
+            let (amount_y, amount_x) = (amount_y_input - walk.amount_in, walk.amount_out);
 
-                // Calculate swap
-                let amount_x_swap = calculate_swap(&amount_y, &virtual_y, &virtual_x);
-
-                // Check if swap is not possible, meaning bounds of the active bin have been reached
-                if amount_x_swap >= self.active_x {
-                    // Calculate inverse swap, round up from zero
-                    let amount_y_swap = calculate_swap_inverse(&self.active_x, &virtual_y, &virtual_x);
-                    let amount_y_swap = if amount_y_swap > Decimal::zero() {
-                        amount_y_swap
-                    } else if self.active_x == Decimal::zero() {
-                        Decimal::ZERO
-                    } else {
-                        Decimal(I192::from_digits([1, 0, 0]))
-                    };
-                    let amount_y_swap_with_fee = amount_y_swap / (Decimal::ONE - liquidity_fee);
-
-                    // Update amounts
-                    amount_x += self.active_x;
-                    amount_y -= amount_y_swap;
-                    
-                    // Check if there is another bin
-                    let next_tick: Option<Tick> = self.tick_index.move_up();
-                    if next_tick.is_some() {
-                        // Save active bin
-                        self.bin_map.insert(tick, 
-                            Bin {
-                                amount: self.active_y + amount_y_swap_with_fee,
-                                total_claim: self.active_total_claim
-                            }
-                        );
-
-                        // Get next bin
-                        tick = next_tick.unwrap();
-                        let bin = self.bin_map.get(&tick).unwrap();
-
-                        // Update active bin state
-                        self.active_x = bin.amount;
-                        self.active_y = Decimal::zero();
-                        self.active_total_claim = bin.total_claim;
-                        self.lower_limit = tick.into();
-                        self.upper_limit = tick.tick_upper(self.bin_span).into();
-                    } else {
-                        // Update active bin state
-                        self.active_x = Decimal::zero();
-                        self.active_y += amount_y_swap_with_fee;
-
-                        break;
-                    }
-                } else {
-                    let amount_y_swap_with_fee = amount_y / (Decimal::ONE - liquidity_fee);
-
-                    // Update amounts
-                    amount_y = Decimal::zero();
-                    amount_x += amount_x_swap;
-
-                    // Update active bin state
-                    self.active_x -= amount_x_swap;
-                    self.active_y += amount_y_swap_with_fee;
-
-                    break;
-                }
-            }
-
-            // Create withdraw and deposit tokens
-            let tokens_x = self.tokens_x.take_advanced(amount_x, OUTGOING);
-            let amount_x_swapped = tokens_x.amount();
-            let tokens_y_swapped = tokens_y.take_advanced(tokens_y.amount() - amount_y, INCOMING);
-            let amount_y_swapped = tokens_y_swapped.amount();
-            self.tokens_y.put(tokens_y_swapped);
+            // Create withdraw and deposit tokens
+            let tokens_x = self.tokens_x.take_advanced(amount_x, OUTGOING);
+            let amount_x_swapped = tokens_x.amount();
+            let tokens_y_swapped = tokens_y.take_advanced(tokens_y.amount() - amount_y, INCOMING);
+            let amount_y_swapped = tokens_y_swapped.amount();
+            self.tokens_y.put(tokens_y_swapped);
 
             // Replace unused component fee
             if amount_y != Decimal::zero() {
@@ -1455,6 +2226,9 @@ mod quantaswap {
             FEE_VAULTS.deposit(protocol_fee_tokens);
             self.tokens_y.put(liquidity_fee_tokens);
 
+            // Apply swap to the pool once the tokens swapped are deposited, as settled range orders are taken from them
+            self.apply_swap_walk(walk, false);
+
             // Emit fee events
             Runtime::emit_event(ProtocolFeeEvent {
                 token_address: self.tokens_y.resource_address(),
@@ -1486,6 +2260,1217 @@ mod quantaswap {
             (tokens_x, tokens_y)
         }
 
+        /// Swap tokens x for an exact amount of tokens y.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_x` - Tokens x to swap.
+        /// * `amount_y_out` - Exact amount of tokens y to buy.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Tokens y bought.
+        /// * `Bucket` - Tokens x leftover.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tokens_x` - Tokens x are of the correct type.
+        /// * `amount_y_out` - Amount is greater than zero.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `amount_y_out` is not a multiple of the divisibility of tokens y.
+        /// * If there is not enough liquidity to buy `amount_y_out`.
+        /// * If there are not enough tokens x to buy `amount_y_out`.
+        /// 
+        /// # Events
+        /// 
+        /// * `SwapEvent` - Event emitted when tokens are swapped.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// * `LiquidityFeeEvent` - Event emitted when liquidity fee is collected.
+        ///
+        fn swap_exact_output_x(&mut self, mut tokens_x: Bucket, amount_y_out: Decimal) -> (Bucket, Bucket) {
+            // Check amount out can be withdrawn at the divisibility of tokens y
+            let divisibility_y = ResourceManager::from(self.tokens_y.resource_address()).resource_type().divisibility().unwrap();
+            assert!(
+                amount_y_out.checked_round(divisibility_y, RoundingMode::ToZero).unwrap() == amount_y_out,
+                "Amount out must be a multiple of the divisibility of the tokens bought."
+            );
+
+            // Get the active bin
+            let active_bin = self.get_active_bin().expect("Not enough liquidity.");
+
+            // Get fee percentages
+            let (protocol_fee, liquidity_fee) = FEE_CONTROLLER.get_fees(
+                Runtime::package_address(), 
+                vec![self.tokens_x.resource_address(), self.tokens_y.resource_address()]);
+
+            // Walk swap through bins
+            let walk = self.walk_swap_exact_output_x(active_bin, amount_y_out, liquidity_fee);
+            let amount_x = walk.amount_in;
+
+            // Calculate fees on top of tokens x swapped, rounding each amount taken up to the divisibility of tokens x
+            let divisibility_x = ResourceManager::from(self.tokens_x.resource_address()).resource_type().divisibility().unwrap();
+            let round_up = |amount: Decimal| amount.checked_round(divisibility_x, RoundingMode::ToPositiveInfinity).unwrap();
+            let amount_x_total = amount_x / (Decimal::ONE - liquidity_fee) / (Decimal::ONE - protocol_fee);
+            let amount_protocol_fee = round_up(amount_x_total * protocol_fee);
+            let amount_liquidity_fee = round_up(amount_x_total - amount_x_total * protocol_fee - amount_x);
+            let amount_x_swapped = round_up(amount_x);
+            assert!(
+                tokens_x.amount() >= amount_protocol_fee + amount_liquidity_fee + amount_x_swapped, 
+                "Not enough tokens to buy amount out."
+            );
+            let protocol_fee_tokens = tokens_x.take(amount_protocol_fee);
+            let liquidity_fee_tokens = tokens_x.take(amount_liquidity_fee);
+
+            // Create withdraw and deposit tokens
+            let tokens_y = self.tokens_y.take(amount_y_out);
+            let tokens_x_swapped = tokens_x.take(amount_x_swapped);
+            self.tokens_x.put(tokens_x_swapped);
+
+            // Deposit fees
+            FEE_VAULTS.deposit(protocol_fee_tokens);
+            self.tokens_x.put(liquidity_fee_tokens);
+
+            // Apply swap to the pool once the tokens swapped are deposited, as settled range orders are taken from them
+            self.apply_swap_walk(walk, true);
+
+            // Emit fee events
+            Runtime::emit_event(ProtocolFeeEvent {
+                token_address: self.tokens_x.resource_address(),
+                amount: amount_protocol_fee,
+            });
+            Runtime::emit_event(LiquidityFeeEvent {
+                token_address: self.tokens_x.resource_address(),
+                amount: amount_liquidity_fee,
+            });
+
+            // Get price after swap
+            let price_after = self.get_price().unwrap_or_default();
+
+            // Emit swap event
+            Runtime::emit_event(SwapEvent {
+                amount_change_x: amount_x_swapped + amount_liquidity_fee,
+                amount_change_y: -amount_y_out,
+                price_after,
+            });
+
+            // Emit valuation event
+            Runtime::emit_event(ValuationEvent {
+                amount_after_x: self.get_amount_x(),
+                amount_after_y: self.get_amount_y(),
+                price_after,
+            });
+
+            // Return tokens
+            (tokens_y, tokens_x)
+        }
+
+        /// Swap tokens y for an exact amount of tokens x.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tokens_y` - Tokens y to swap.
+        /// * `amount_x_out` - Exact amount of tokens x to buy.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bucket` - Tokens x bought.
+        /// * `Bucket` - Tokens y leftover.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tokens_y` - Tokens y are of the correct type.
+        /// * `amount_x_out` - Amount is greater than zero.
+        /// 
+        /// # Panics
+        /// 
+        /// * If `amount_x_out` is not a multiple of the divisibility of tokens x.
+        /// * If there is not enough liquidity to buy `amount_x_out`.
+        /// * If there are not enough tokens y to buy `amount_x_out`.
+        /// 
+        /// # Events
+        /// 
+        /// * `SwapEvent` - Event emitted when tokens are swapped.
+        /// * `ValuationEvent` - Event emitted when the value of the pool changes.
+        /// * `ProtocolFeeEvent` - Event emitted when protocol fee is collected.
+        /// * `LiquidityFeeEvent` - Event emitted when liquidity fee is collected.
+        ///
+        fn swap_exact_output_y(&mut self, mut tokens_y: Bucket, amount_x_out: Decimal) -> (Bucket, Bucket) {
+            // Check amount out can be withdrawn at the divisibility of tokens x
+            let divisibility_x = ResourceManager::from(self.tokens_x.resource_address()).resource_type().divisibility().unwrap();
+            assert!(
+                amount_x_out.checked_round(divisibility_x, RoundingMode::ToZero).unwrap() == amount_x_out,
+                "Amount out must be a multiple of the divisibility of the tokens bought."
+            );
+
+            // Get the active bin
+            let active_bin = self.get_active_bin().expect("Not enough liquidity.");
+
+            // Get fee percentages
+            let (protocol_fee, liquidity_fee) = FEE_CONTROLLER.get_fees(
+                Runtime::package_address(), 
+                vec![self.tokens_x.resource_address(), self.tokens_y.resource_address()]);
+
+            // Walk swap through bins
+            let walk = self.walk_swap_exact_output_y(active_bin, amount_x_out, liquidity_fee);
+            let amount_y = walk.amount_in;
+
+            // Calculate fees on top of tokens y swapped, rounding each amount taken up to the divisibility of tokens y
+            let divisibility_y = ResourceManager::from(self.tokens_y.resource_address()).resource_type().divisibility().unwrap();
+            let round_up = |amount: Decimal| amount.checked_round(divisibility_y, RoundingMode::ToPositiveInfinity).unwrap();
+            let amount_y_total = amount_y / (Decimal::ONE - liquidity_fee) / (Decimal::ONE - protocol_fee);
+            let amount_protocol_fee = round_up(amount_y_total * protocol_fee);
+            let amount_liquidity_fee = round_up(amount_y_total - amount_y_total * protocol_fee - amount_y);
+            let amount_y_swapped = round_up(amount_y);
+            assert!(
+                tokens_y.amount() >= amount_protocol_fee + amount_liquidity_fee + amount_y_swapped, 
+                "Not enough tokens to buy amount out."
+            );
+            let protocol_fee_tokens = tokens_y.take(amount_protocol_fee);
+            let liquidity_fee_tokens = tokens_y.take(amount_liquidity_fee);
+
+            // Create withdraw and deposit tokens
+            let tokens_x = self.tokens_x.take(amount_x_out);
+            let tokens_y_swapped = tokens_y.take(amount_y_swapped);
+            self.tokens_y.put(tokens_y_swapped);
+
+            // Deposit fees
+            FEE_VAULTS.deposit(protocol_fee_tokens);
+            self.tokens_y.put(liquidity_fee_tokens);
+
+            // Apply swap to the pool once the tokens swapped are deposited, as settled range orders are taken from them
+            self.apply_swap_walk(walk, false);
+
+            // Emit fee events
+            Runtime::emit_event(ProtocolFeeEvent {
+                token_address: self.tokens_y.resource_address(),
+                amount: amount_protocol_fee,
+            });
+            Runtime::emit_event(LiquidityFeeEvent {
+                token_address: self.tokens_y.resource_address(),
+                amount: amount_liquidity_fee,
+            });
+
+            // Get price after swap
+            let price_after = self.get_price().unwrap_or_default();
+
+            // Emit swap event
+            Runtime::emit_event(SwapEvent {
+                amount_change_x: -amount_x_out,
+                amount_change_y: amount_y_swapped + amount_liquidity_fee,
+                price_after,
+            });
+
+            // Emit valuation event
+            Runtime::emit_event(ValuationEvent {
+                amount_after_x: self.get_amount_x(),
+                amount_after_y: self.get_amount_y(),
+                price_after,
+            });
+
+            // Return tokens
+            (tokens_x, tokens_y)
+        }
+
+        /// Helper method to quote a swap of tokens x for tokens y.
+        /// Walks the swap through the bins in the same way as `swap_x` without changing any state.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_x_total` - Amount of tokens x to swap.
+        /// * `limit_price_sqrt` - Optional limit price sqrt at which the swap stops.
+        /// 
+        /// # Returns
+        /// 
+        /// * `SwapQuote` - Expected outcome of the swap.
+        /// 
+        fn quote_swap_x(&self, amount_x_total: Decimal, limit_price_sqrt: Option<Decimal>) -> SwapQuote {
+            // Get the active bin
+            let active_bin = match self.get_active_bin() {
+                Some(active_bin) => active_bin,
+                None => return SwapQuote {
+                    amount_out: Decimal::zero(),
+                    price_after: None,
+                    bins_crossed: 0,
+                    protocol_fee: Decimal::zero(),
+                    liquidity_fee: Decimal::zero(),
+                    amount_remaining: amount_x_total,
+                },
+            };
+
+            // Get fee percentages
+            let (protocol_fee, liquidity_fee) = FEE_CONTROLLER.get_fees(
+                Runtime::package_address(), 
+                vec![self.tokens_x.resource_address(), self.tokens_y.resource_address()]);
+
+            // Separate fee amounts
+            let mut amount_protocol_fee = amount_x_total * protocol_fee;
+            let mut amount_liquidity_fee = (amount_x_total - amount_protocol_fee) * liquidity_fee;
+            let amount_x_input = amount_x_total - amount_protocol_fee - amount_liquidity_fee;
+
+            // Walk swap through bins
+            let walk = self.walk_swap_x(active_bin, amount_x_input, liquidity_fee, limit_price_sqrt);
+            let amount_x = amount_x_input - walk.amount_in;
+
+            // Replace unused fees
+            let mut amount_remaining = amount_x;
+            if amount_x != Decimal::zero() {
+                let unused = amount_x / amount_x_input;
+                amount_remaining += (amount_protocol_fee + amount_liquidity_fee) * unused;
+                amount_protocol_fee -= amount_protocol_fee * unused;
+                amount_liquidity_fee -= amount_liquidity_fee * unused;
+            }
+
+            SwapQuote {
+                amount_out: walk.amount_out,
+                price_after: Some(walk.active_bin.price()),
+                bins_crossed: walk.bins_crossed.len() as u32,
+                protocol_fee: amount_protocol_fee,
+                liquidity_fee: amount_liquidity_fee,
+                amount_remaining,
+            }
+        }
+
+        /// Helper method to quote a swap of tokens y for tokens x.
+        /// Walks the swap through the bins in the same way as `swap_y` without changing any state.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount_y_total` - Amount of tokens y to swap.
+        /// * `limit_price_sqrt` - Optional limit price sqrt at which the swap stops.
+        /// 
+        /// # Returns
+        /// 
+        /// * `SwapQuote` - Expected outcome of the swap.
+        /// 
+        fn quote_swap_y(&self, amount_y_total: Decimal, limit_price_sqrt: Option<Decimal>) -> SwapQuote {
+            // Get the active bin
+            let active_bin = match self.get_active_bin() {
+                Some(active_bin) => active_bin,
+                None => return SwapQuote {
+                    amount_out: Decimal::zero(),
+                    price_after: None,
+                    bins_crossed: 0,
+                    protocol_fee: Decimal::zero(),
+                    liquidity_fee: Decimal::zero(),
+                    amount_remaining: amount_y_total,
+                },
+            };
+
+            // Get fee percentages
+            let (protocol_fee, liquidity_fee) = FEE_CONTROLLER.get_fees(
+                Runtime::package_address(), 
+                vec![self.tokens_x.resource_address(), self.tokens_y.resource_address()]);
+
+            // Separate fee amounts
+            let mut amount_protocol_fee = amount_y_total * protocol_fee;
+            let mut amount_liquidity_fee = (amount_y_total - amount_protocol_fee) * liquidity_fee;
+            let amount_y_input = amount_y_total - amount_protocol_fee - amount_liquidity_fee;
+
+            // Walk swap through bins
+            let walk = self.walk_swap_y(active_bin, amount_y_input, liquidity_fee, limit_price_sqrt);
+            let amount_y = amount_y_input - walk.amount_in;
+
+            // Replace unused fees
+            let mut amount_remaining = amount_y;
+            if amount_y != Decimal::zero() {
+                let unused = amount_y / amount_y_input;
+                amount_remaining += (amount_protocol_fee + amount_liquidity_fee) * unused;
+                amount_protocol_fee -= amount_protocol_fee * unused;
+                amount_liquidity_fee -= amount_liquidity_fee * unused;
+            }
+
+            SwapQuote {
+                amount_out: walk.amount_out,
+                price_after: Some(walk.active_bin.price()),
+                bins_crossed: walk.bins_crossed.len() as u32,
+                protocol_fee: amount_protocol_fee,
+                liquidity_fee: amount_liquidity_fee,
+                amount_remaining,
+            }
+        }
+
+        /// Helper method to copy the state of the active bin.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Option<ActiveBin>` - Copy of the active bin state. None if no active tick.
+        /// 
+        fn get_active_bin(&self) -> Option<ActiveBin> {
+            let tick = self.tick_index.current()?;
+            Some(ActiveBin {
+                tick,
+                amount_x: self.active_x,
+                amount_y: self.active_y,
+                total_claim: self.active_total_claim,
+                lower_limit: self.lower_limit,
+                upper_limit: self.upper_limit,
+            })
+        }
+
+        /// Helper method to copy a dormant bin as the active bin state it would have once a swap reaches it.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tick` - Tick of the dormant bin.
+        /// 
+        /// # Returns
+        /// 
+        /// * `ActiveBin` - Copy of the bin as the active bin.
+        /// 
+        /// # Requires
+        /// 
+        /// * `tick` - Tick of an existing bin other than the active bin.
+        /// 
+        fn copy_dormant_bin(&self, tick: Tick) -> ActiveBin {
+            // Bins above the active bin hold tokens x, bins below hold tokens y
+            let bin = self.bin_map.get(&tick).unwrap();
+            let (amount_x, amount_y) = if tick > self.tick_index.current().unwrap() {
+                (bin.amount, Decimal::zero())
+            } else {
+                (Decimal::zero(), bin.amount)
+            };
+
+            ActiveBin {
+                tick,
+                amount_x,
+                amount_y,
+                total_claim: bin.total_claim,
+                lower_limit: tick.into(),
+                upper_limit: tick.tick_upper(self.bin_span).into(),
+            }
+        }
+
+        /// Helper method to walk a swap of tokens x for tokens y down through the bins without changing any state.
+        /// Used by `swap_x` to execute the swap and by `quote_swap_x` to quote it.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `active_bin` - Copy of the active bin state.
+        /// * `amount_x_input` - Amount of tokens x to swap after fees are taken.
+        /// * `liquidity_fee` - Liquidity fee percentage.
+        /// * `limit_price_sqrt` - Optional limit price sqrt at which the swap stops.
+        /// 
+        /// # Returns
+        /// 
+        /// * `SwapWalk` - Outcome of the swap.
+        /// 
+        fn walk_swap_x(&self, mut active_bin: ActiveBin, amount_x_input: Decimal, liquidity_fee: Decimal, limit_price_sqrt: Option<Decimal>) -> SwapWalk {
+            // Variables to track token amounts
+            let mut amount_x = amount_x_input;
+            let mut amount_y = Decimal::zero();
+            let mut bins_crossed: Vec<(Tick, Bin, Decimal)> = Vec::new();
+
+            // Loop through bins
+            loop {
+                // Get virtual amounts
+                let (virtual_x, virtual_y) = active_bin.virtual_amounts();
+
+                // Limit swap amount if the limit price is within the active bin
+                let amount_x_bin = match limit_price_sqrt {
+                    Some(limit_price_sqrt) if limit_price_sqrt >= active_bin.lower_limit => {
+                        let amount_x_limit = calculate_swap_x_to_price(&limit_price_sqrt, &virtual_x, &virtual_y);
+                        amount_x_limit.max(Decimal::zero()).min(amount_x)
+                    },
+                    _ => amount_x,
+                };
+
+                // Calculate swap
+                let amount_y_swap = calculate_swap(&amount_x_bin, &virtual_x, &virtual_y);
+
+                // Check if swap is not possible, meaning bounds of the active bin have been reached
+                if amount_y_swap >= active_bin.amount_y {
+                    // Calculate inverse swap, round up from zero
+                    let amount_x_swap = calculate_swap_inverse_bin(&active_bin.amount_y, &virtual_x, &virtual_y);
+                    let amount_x_swap_with_fee = amount_x_swap / (Decimal::ONE - liquidity_fee);
+                    let fee = amount_x_swap_with_fee - amount_x_swap;
+
+                    // Update amounts
+                    amount_x -= amount_x_swap;
+                    amount_y += active_bin.amount_y;
+
+                    // Update active bin state
+                    active_bin.amount_x += amount_x_swap_with_fee;
+                    active_bin.amount_y = Decimal::zero();
+
+                    // Check if there is another bin above the limit price
+                    let next_tick: Option<Tick> = self.tick_index.next_down(active_bin.tick)
+                        .filter(|next_tick| limit_price_sqrt.map_or(true, |limit| Decimal::from(next_tick.tick_upper(self.bin_span)) > limit));
+                    match next_tick {
+                        Some(next_tick) => {
+                            // Move past active bin to next bin
+                            bins_crossed.push((active_bin.tick, Bin { amount: active_bin.amount_x, total_claim: active_bin.total_claim }, fee));
+                            active_bin = self.copy_dormant_bin(next_tick);
+                        },
+                        None => return SwapWalk {
+                            amount_in: amount_x_input - amount_x,
+                            amount_out: amount_y,
+                            bins_crossed,
+                            active_fee: fee,
+                            active_bin,
+                        },
+                    }
+                } else {
+                    let amount_x_swap_with_fee = amount_x_bin / (Decimal::ONE - liquidity_fee);
+
+                    // Update amounts
+                    amount_x -= amount_x_bin;
+                    amount_y += amount_y_swap;
+
+                    // Update active bin state
+                    active_bin.amount_x += amount_x_swap_with_fee;
+                    active_bin.amount_y -= amount_y_swap;
+
+                    return SwapWalk {
+                        amount_in: amount_x_input - amount_x,
+                        amount_out: amount_y,
+                        bins_crossed,
+                        active_fee: amount_x_swap_with_fee - amount_x_bin,
+                        active_bin,
+                    };
+                }
+            }
+        }
+
+        /// Helper method to walk a swap of tokens y for tokens x up through the bins without changing any state.
+        /// Used by `swap_y` to execute the swap and by `quote_swap_y` to quote it.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `active_bin` - Copy of the active bin state.
+        /// * `amount_y_input` - Amount of tokens y to swap after fees are taken.
+        /// * `liquidity_fee` - Liquidity fee percentage.
+        /// * `limit_price_sqrt` - Optional limit price sqrt at which the swap stops.
+        /// 
+        /// # Returns
+        /// 
+        /// * `SwapWalk` - Outcome of the swap.
+        /// 
+        fn walk_swap_y(&self, mut active_bin: ActiveBin, amount_y_input: Decimal, liquidity_fee: Decimal, limit_price_sqrt: Option<Decimal>) -> SwapWalk {
+            // Variables to track token amounts
+            let mut amount_y = amount_y_input;
+            let mut amount_x = Decimal::zero();
+            let mut bins_crossed: Vec<(Tick, Bin, Decimal)> = Vec::new();
+
+            // Loop through bins
+            loop {
+                // Get virtual amounts
+                let (virtual_x, virtual_y) = active_bin.virtual_amounts();
+
+                // Limit swap amount if the limit price is within the active bin
+                let amount_y_bin = match limit_price_sqrt {
+                    Some(limit_price_sqrt) if limit_price_sqrt <= active_bin.upper_limit => {
+                        let amount_y_limit = calculate_swap_y_to_price(&limit_price_sqrt, &virtual_x, &virtual_y);
+                        amount_y_limit.max(Decimal::zero()).min(amount_y)
+                    },
+                    _ => amount_y,
+                };
+
+                // Calculate swap
+                let amount_x_swap = calculate_swap(&amount_y_bin, &virtual_y, &virtual_x);
+
+                // Check if swap is not possible, meaning bounds of the active bin have been reached
+                if amount_x_swap >= active_bin.amount_x {
+                    // Calculate inverse swap, round up from zero
+                    let amount_y_swap = calculate_swap_inverse_bin(&active_bin.amount_x, &virtual_y, &virtual_x);
+                    let amount_y_swap_with_fee = amount_y_swap / (Decimal::ONE - liquidity_fee);
+                    let fee = amount_y_swap_with_fee - amount_y_swap;
+
+                    // Update amounts
+                    amount_x += active_bin.amount_x;
+                    amount_y -= amount_y_swap;
+
+                    // Update active bin state
+                    active_bin.amount_x = Decimal::zero();
+                    active_bin.amount_y += amount_y_swap_with_fee;
+
+                    // Check if there is another bin below the limit price
+                    let next_tick: Option<Tick> = self.tick_index.next_up(active_bin.tick)
+                        .filter(|next_tick| limit_price_sqrt.map_or(true, |limit| Decimal::from(*next_tick) < limit));
+                    match next_tick {
+                        Some(next_tick) => {
+                            // Move past active bin to next bin
+                            bins_crossed.push((active_bin.tick, Bin { amount: active_bin.amount_y, total_claim: active_bin.total_claim }, fee));
+                            active_bin = self.copy_dormant_bin(next_tick);
+                        },
+                        None => return SwapWalk {
+                            amount_in: amount_y_input - amount_y,
+                            amount_out: amount_x,
+                            bins_crossed,
+                            active_fee: fee,
+                            active_bin,
+                        },
+                    }
+                } else {
+                    let amount_y_swap_with_fee = amount_y_bin / (Decimal::ONE - liquidity_fee);
+
+                    // Update amounts
+                    amount_y -= amount_y_bin;
+                    amount_x += amount_x_swap;
+
+                    // Update active bin state
+                    active_bin.amount_x -= amount_x_swap;
+                    active_bin.amount_y += amount_y_swap_with_fee;
+
+                    return SwapWalk {
+                        amount_in: amount_y_input - amount_y,
+                        amount_out: amount_x,
+                        bins_crossed,
+                        active_fee: amount_y_swap_with_fee - amount_y_bin,
+                        active_bin,
+                    };
+                }
+            }
+        }
+
+        /// Helper method to walk a swap of tokens x for an exact amount of tokens y down through the bins without changing any state.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `active_bin` - Copy of the active bin state.
+        /// * `amount_y_out` - Exact amount of tokens y to buy.
+        /// * `liquidity_fee` - Liquidity fee percentage.
+        /// 
+        /// # Returns
+        /// 
+        /// * `SwapWalk` - Outcome of the swap.
+        /// 
+        /// # Panics
+        /// 
+        /// * If there is not enough liquidity to buy `amount_y_out`.
+        /// 
+        fn walk_swap_exact_output_x(&self, mut active_bin: ActiveBin, amount_y_out: Decimal, liquidity_fee: Decimal) -> SwapWalk {
+            // Variables to track token amounts
+            let mut amount_x = Decimal::zero();
+            let mut amount_y = amount_y_out;
+            let mut bins_crossed: Vec<(Tick, Bin, Decimal)> = Vec::new();
+
+            // Loop through bins
+            loop {
+                // Get virtual amounts
+                let (virtual_x, virtual_y) = active_bin.virtual_amounts();
+
+                // Check if the remaining amount can be bought from the active bin
+                if amount_y <= active_bin.amount_y {
+                    // Calculate inverse swap, round up
+                    let amount_x_swap = calculate_swap_inverse(&amount_y, &virtual_x, &virtual_y) + Decimal(I192::from_digits([1, 0, 0]));
+                    let amount_x_swap_with_fee = amount_x_swap / (Decimal::ONE - liquidity_fee);
+
+                    // Update amounts
+                    amount_x += amount_x_swap;
+
+                    // Update active bin state
+                    active_bin.amount_x += amount_x_swap_with_fee;
+                    active_bin.amount_y -= amount_y;
+
+                    return SwapWalk {
+                        amount_in: amount_x,
+                        amount_out: amount_y_out,
+                        bins_crossed,
+                        active_fee: amount_x_swap_with_fee - amount_x_swap,
+                        active_bin,
+                    };
+                }
+
+                // Calculate inverse swap, round up from zero
+                let amount_x_swap = calculate_swap_inverse_bin(&active_bin.amount_y, &virtual_x, &virtual_y);
+                let amount_x_swap_with_fee = amount_x_swap / (Decimal::ONE - liquidity_fee);
+
+                // Update amounts
+                amount_x += amount_x_swap;
+                amount_y -= active_bin.amount_y;
+
+                // Update active bin state
+                active_bin.amount_x += amount_x_swap_with_fee;
+                active_bin.amount_y = Decimal::zero();
+
+                // Move past active bin to next bin, panic if there is none
+                let next_tick: Option<Tick> = self.tick_index.next_down(active_bin.tick);
+                assert!(next_tick.is_some(), "Not enough liquidity.");
+                bins_crossed.push((active_bin.tick, Bin { amount: active_bin.amount_x, total_claim: active_bin.total_claim }, amount_x_swap_with_fee - amount_x_swap));
+                active_bin = self.copy_dormant_bin(next_tick.unwrap());
+            }
+        }
+
+        /// Helper method to walk a swap of tokens y for an exact amount of tokens x up through the bins without changing any state.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `active_bin` - Copy of the active bin state.
+        /// * `amount_x_out` - Exact amount of tokens x to buy.
+        /// * `liquidity_fee` - Liquidity fee percentage.
+        /// 
+        /// # Returns
+        /// 
+        /// * `SwapWalk` - Outcome of the swap.
+        /// 
+        /// # Panics
+        /// 
+        /// * If there is not enough liquidity to buy `amount_x_out`.
+        /// 
+        fn walk_swap_exact_output_y(&self, mut active_bin: ActiveBin, amount_x_out: Decimal, liquidity_fee: Decimal) -> SwapWalk {
+            // Variables to track token amounts
+            let mut amount_y = Decimal::zero();
+            let mut amount_x = amount_x_out;
+            let mut bins_crossed: Vec<(Tick, Bin, Decimal)> = Vec::new();
+
+            // Loop through bins
+            loop {
+                // Get virtual amounts
+                let (virtual_x, virtual_y) = active_bin.virtual_amounts();
+
+                // Check if the remaining amount can be bought from the active bin
+                if amount_x <= active_bin.amount_x {
+                    // Calculate inverse swap, round up
+                    let amount_y_swap = calculate_swap_inverse(&amount_x, &virtual_y, &virtual_x) + Decimal(I192::from_digits([1, 0, 0]));
+                    let amount_y_swap_with_fee = amount_y_swap / (Decimal::ONE - liquidity_fee);
+
+                    // Update amounts
+                    amount_y += amount_y_swap;
+
+                    // Update active bin state
+                    active_bin.amount_x -= amount_x;
+                    active_bin.amount_y += amount_y_swap_with_fee;
+
+                    return SwapWalk {
+                        amount_in: amount_y,
+                        amount_out: amount_x_out,
+                        bins_crossed,
+                        active_fee: amount_y_swap_with_fee - amount_y_swap,
+                        active_bin,
+                    };
+                }
+
+                // Calculate inverse swap, round up from zero
+                let amount_y_swap = calculate_swap_inverse_bin(&active_bin.amount_x, &virtual_y, &virtual_x);
+                let amount_y_swap_with_fee = amount_y_swap / (Decimal::ONE - liquidity_fee);
+
+                // Update amounts
+                amount_y += amount_y_swap;
+                amount_x -= active_bin.amount_x;
+
+                // Update active bin state
+                active_bin.amount_x = Decimal::zero();
+                active_bin.amount_y += amount_y_swap_with_fee;
+
+                // Move past active bin to next bin, panic if there is none
+                let next_tick: Option<Tick> = self.tick_index.next_up(active_bin.tick);
+                assert!(next_tick.is_some(), "Not enough liquidity.");
+                bins_crossed.push((active_bin.tick, Bin { amount: active_bin.amount_y, total_claim: active_bin.total_claim }, amount_y_swap_with_fee - amount_y_swap));
+                active_bin = self.copy_dormant_bin(next_tick.unwrap());
+            }
+        }
+
+        /// Helper method to apply a swap walked through the bins to the pool.
+        /// Moves past the bins crossed, settling range orders resting in them, and updates the active bin.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `walk` - Outcome of the swap.
+        /// * `is_x` - Whether tokens x were swapped for tokens y, moving the active bin down.
+        /// 
+        /// # Events
+        /// 
+        /// * `SettleRangeOrdersEvent` - Event emitted when range orders are settled.
+        /// 
+        fn apply_swap_walk(&mut self, walk: SwapWalk, is_x: bool) {
+            // Move past bins crossed
+            for (tick, bin, fee) in walk.bins_crossed {
+                // Add liquidity fee paid into the bin
+                if is_x {
+                    self.add_fee_growth(tick, bin.total_claim, fee, Decimal::zero());
+                    self.tick_index.move_down();
+                } else {
+                    self.add_fee_growth(tick, bin.total_claim, Decimal::zero(), fee);
+                    self.tick_index.move_up();
+                }
+
+                // Save bin, settling range orders resting in it
+                let bin = self.settle_range_orders(tick, bin, !is_x);
+                self.bin_map.insert(tick, bin);
+            }
+
+            // Add liquidity fee paid into the active bin
+            let active_bin = walk.active_bin;
+            if is_x {
+                self.add_fee_growth(active_bin.tick, active_bin.total_claim, walk.active_fee, Decimal::zero());
+            } else {
+                self.add_fee_growth(active_bin.tick, active_bin.total_claim, Decimal::zero(), walk.active_fee);
+            }
+
+            // Update active bin state
+            self.active_x = active_bin.amount_x;
+            self.active_y = active_bin.amount_y;
+            self.active_total_claim = active_bin.total_claim;
+            self.lower_limit = active_bin.lower_limit;
+            self.upper_limit = active_bin.upper_limit;
+        }
+
+        /// Helper method to split an amount of tokens across bins using the weights of a distribution shape.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `amount` - Amount of tokens to split.
+        /// * `bins` - Bins to split the tokens across. Format: (tick, distance in bins from the active bin).
+        /// * `shape` - Shape of the distribution.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Vec<(u32, Decimal)>` - Amount of tokens for each bin. Format: (tick, amount).
+        /// 
+        fn distribute_liquidity(amount: Decimal, bins: &[(u32, u32)], shape: LiquidityShape) -> Vec<(u32, Decimal)> {
+            // Calculate weights
+            let max_distance = bins.iter().map(|&(_, distance)| distance).max().unwrap_or(0);
+            let weights: Vec<Decimal> = bins.iter().map(|&(_, distance)| shape.weight(distance, max_distance)).collect();
+            let total_weight = weights.iter().fold(Decimal::zero(), |sum, &weight| sum + weight);
+            if total_weight == Decimal::zero() {
+                return Vec::new();
+            }
+
+            // Split amount by weight
+            bins.iter()
+                .zip(weights)
+                .map(|(&(tick, _), weight)| (tick, amount * weight / total_weight))
+                .collect()
+        }
+
+        /// Helper method to add liquidity at positions to a set of liquidity claims.
+        /// Tokens are not moved, the amounts used are returned for the caller to deposit.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `liquidity_claims` - Liquidity claims of a liquidity receipt.
+        /// * `fee_checkpoints` - Fee checkpoints of a liquidity receipt.
+        /// * `positions` - Positions at which to add liquidity. Format: (tick, amount_x, amount_y).
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Amount of tokens x used.
+        /// * `Decimal` - Amount of tokens y used.
+        /// * `Vec<(u32, Decimal)>` - Tokens x added to each bin in the format (tick, amount_x).
+        /// * `Vec<(u32, Decimal)>` - Tokens y added to each bin in the format (tick, amount_y).
+        /// 
+        /// # Panics
+        /// 
+        /// * If the tick of a position is not valid.
+        /// * If an amount of a position is less than zero.
+        /// 
+        fn add_to_claims(
+            &mut self,
+            liquidity_claims: &mut HashMap<u32, Decimal>,
+            fee_checkpoints: &mut HashMap<u32, FeeCheckpoint>,
+            positions: Vec<(u32, Decimal, Decimal)>,
+        ) -> (Decimal, Decimal, Vec<(u32, Decimal)>, Vec<(u32, Decimal)>) {
+            // Get the current tick
+            let mut current_tick = self.tick_index.current();
+
+            // Amounts to track tokens used
+            let mut amount_x_used = Decimal::zero();
+            let mut amount_y_used = Decimal::zero();
+            let mut added_x: Vec<(u32, Decimal)> = Vec::new();
+            let mut added_y: Vec<(u32, Decimal)> = Vec::new();
+            
+            for (tick, amount_x, amount_y) in positions {
+                let tick: Tick = Tick(tick);
+                assert!(tick.is_valid(self.bin_span), "Invalid tick {:?}.", tick);
+                assert!(amount_x >= Decimal::zero() && amount_y >= Decimal::zero(), "Amounts must be greater than or equal to zero.");
+
+                // Add liquidity to bin
+                let claim = if current_tick.is_none() {
+                    if amount_x == Decimal::zero() && amount_y == Decimal::zero() {
+                        continue;
+                    }
+
+                    let claim = self.add_initial_liquidity(tick, amount_x, amount_y);
+                    current_tick = self.tick_index.current();
+                    amount_x_used += amount_x;
+                    amount_y_used += amount_y;
+                    added_x.push((tick.0, amount_x));
+                    added_y.push((tick.0, amount_y));
+
+                    claim
+                } else if tick > current_tick.unwrap() && amount_x > Decimal::zero() {
+                    let claim = self.add_liquidity_to_bin(tick, amount_x);
+                    amount_x_used += amount_x;
+                    added_x.push((tick.0, amount_x));
+
+                    claim
+                } else if tick < current_tick.unwrap() && amount_y > Decimal::zero() {
+                    let claim = self.add_liquidity_to_bin(tick, amount_y);
+                    amount_y_used += amount_y;
+                    added_y.push((tick.0, amount_y));
+
+                    claim
+                } else if tick == current_tick.unwrap() && amount_x > Decimal::zero() && amount_y > Decimal::zero() {
+                    let (claim, change_x, change_y) = self.add_active_liquidity(amount_x, amount_y);
+                    amount_x_used += change_x;
+                    amount_y_used += change_y;
+                    added_x.push((tick.0, change_x));
+                    added_y.push((tick.0, change_y));
+
+                    claim
+                } else {
+                    continue;
+                };
+
+                // Checkpoint fees earned before the claim changes
+                let claim_before = liquidity_claims.get(&tick.0).copied().unwrap_or_default();
+                self.checkpoint_fees(fee_checkpoints, tick, claim_before);
+
+                // Add to liquidity claim
+                if let Some(position) = liquidity_claims.get_mut(&tick.0) {
+                    *position += claim;
+                } else {
+                    liquidity_claims.insert(tick.0, claim);
+                }
+            }
+
+            // Return amounts
+            (amount_x_used, amount_y_used, added_x, added_y)
+        }
+
+        /// Helper method to remove liquidity claims from a set of liquidity claims.
+        /// Tokens are not moved, the amounts removed are returned for the caller to withdraw.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `liquidity_claims` - Liquidity claims of a liquidity receipt.
+        /// * `fee_checkpoints` - Fee checkpoints of a liquidity receipt.
+        /// * `claims` - Liquidity claims to remove. Format: (tick, amount_claim).
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Amount of tokens x removed.
+        /// * `Decimal` - Amount of tokens y removed.
+        /// * `Vec<(u32, Decimal)>` - Tokens x removed from each bin in the format (tick, -amount_x).
+        /// * `Vec<(u32, Decimal)>` - Tokens y removed from each bin in the format (tick, -amount_y).
+        /// 
+        /// # Panics
+        /// 
+        /// * If the claim amount is not greater than zero.
+        /// * If the claim does not exist.
+        /// 
+        fn remove_from_claims(
+            &mut self,
+            liquidity_claims: &mut HashMap<u32, Decimal>,
+            fee_checkpoints: &mut HashMap<u32, FeeCheckpoint>,
+            claims: Vec<(u32, Decimal)>,
+        ) -> (Decimal, Decimal, Vec<(u32, Decimal)>, Vec<(u32, Decimal)>) {
+            // Amounts to track tokens claimed
+            let mut amount_x = Decimal::zero();
+            let mut amount_y = Decimal::zero();
+            let mut removed_x: Vec<(u32, Decimal)> = Vec::new();
+            let mut removed_y: Vec<(u32, Decimal)> = Vec::new();
+
+            // Get current tick
+            let mut current_tick = match self.tick_index.current() {
+                Some(tick) => tick,
+                None => return (Decimal::zero(), Decimal::zero(), removed_x, removed_y),
+            };
+
+            // Remove liquidity
+            for (tick, claim) in claims {
+                assert!(
+                    claim > Decimal::zero(),
+                    "Claim must be greater than zero."
+                );
+
+                let available_claim = liquidity_claims.get_mut(&tick).expect("Claim does not exist.");
+
+                // Checkpoint fees earned before the claim changes
+                self.checkpoint_fees(fee_checkpoints, Tick(tick), *available_claim);
+
+                let claim = if *available_claim > claim {
+                    *available_claim -= claim;
+                    claim
+                } else {
+                    fee_checkpoints.remove(&tick);
+                    liquidity_claims.remove(&tick).unwrap()
+                };
+                let tick: Tick = Tick(tick);
+
+                // Withdraw tokens
+                if tick > current_tick { // bin above
+                    let change_x = self.remove_liquidity_from_bin(tick, claim);
+                    amount_x += change_x;
+                    removed_x.push((tick.0, -change_x));
+                } else if tick < current_tick { // bin below
+                    let change_y = self.remove_liquidity_from_bin(tick, claim);
+                    amount_y += change_y;
+                    removed_y.push((tick.0, -change_y));
+                } else { // active bin
+                    let (change_x, change_y) = self.remove_active_liquidity(current_tick, claim);
+                    amount_x += change_x;
+                    amount_y += change_y;
+                    removed_x.push((tick.0, -change_x));
+                    removed_y.push((tick.0, -change_y));
+
+                    // Update current tick incase it changed
+                    let check_tick = self.tick_index.current();
+                    if check_tick.is_some() {
+                        current_tick = check_tick.unwrap();
+                    }
+                }
+            }
+
+            // Return amounts
+            (amount_x, amount_y, removed_x, removed_y)
+        }
+
+        /// Helper method to get the fee growth of a bin.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tick` - Tick of the bin.
+        /// 
+        /// # Returns
+        /// 
+        /// * `FeeGrowth` - Cumulative liquidity fees per unit of claim on the bin.
+        /// 
+        fn get_fee_growth(&self, tick: Tick) -> FeeGrowth {
+            self.fee_growth_map.get(&tick).map(|fee_growth| *fee_growth).unwrap_or_default()
+        }
+
+        /// Helper method to add liquidity fees paid into a bin to its fee growth.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tick` - Tick of the bin.
+        /// * `total_claim` - Total claim of the bin when the fees were paid.
+        /// * `fee_x` - Liquidity fees in tokens x.
+        /// * `fee_y` - Liquidity fees in tokens y.
+        /// 
+        fn add_fee_growth(&mut self, tick: Tick, total_claim: Decimal, fee_x: Decimal, fee_y: Decimal) {
+            if total_claim == Decimal::zero() || (fee_x == Decimal::zero() && fee_y == Decimal::zero()) {
+                return;
+            }
+
+            let mut fee_growth = self.get_fee_growth(tick);
+            fee_growth.x += fee_x / total_claim;
+            fee_growth.y += fee_y / total_claim;
+            self.fee_growth_map.insert(tick, fee_growth);
+        }
+
+        /// Helper method to move fees earned by a liquidity claim since its last checkpoint into the checkpoint.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `fee_checkpoints` - Fee checkpoints of a liquidity receipt.
+        /// * `tick` - Tick of the liquidity claim.
+        /// * `claim` - Amount of the liquidity claim since its last checkpoint.
+        /// 
+        fn checkpoint_fees(&self, fee_checkpoints: &mut HashMap<u32, FeeCheckpoint>, tick: Tick, claim: Decimal) {
+            let fee_growth = self.get_fee_growth(tick);
+            let checkpoint = fee_checkpoints.entry(tick.0).or_default();
+            checkpoint.earned_x += claim * (fee_growth.x - checkpoint.fee_growth.x);
+            checkpoint.earned_y += claim * (fee_growth.y - checkpoint.fee_growth.y);
+            checkpoint.fee_growth = fee_growth;
+        }
+
+        /// Helper method to merge a liquidity claim and its fee checkpoint into a set of liquidity claims.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `liquidity_claims` - Liquidity claims of a liquidity receipt.
+        /// * `fee_checkpoints` - Fee checkpoints of a liquidity receipt.
+        /// * `tick` - Tick of the liquidity claim.
+        /// * `claim` - Amount of the liquidity claim.
+        /// * `checkpoint` - Fee checkpoint of the liquidity claim.
+        /// 
+        /// # Requires
+        /// 
+        /// * `checkpoint` - Checkpoint is up to date with the fee growth of the bin.
+        /// 
+        fn merge_claim(
+            &self,
+            liquidity_claims: &mut HashMap<u32, Decimal>,
+            fee_checkpoints: &mut HashMap<u32, FeeCheckpoint>,
+            tick: Tick,
+            claim: Decimal,
+            checkpoint: FeeCheckpoint,
+        ) {
+            // Checkpoint fees earned by the existing claim
+            let claim_before = liquidity_claims.get(&tick.0).copied().unwrap_or_default();
+            self.checkpoint_fees(fee_checkpoints, tick, claim_before);
+
+            // Add claim and fees earned
+            *liquidity_claims.entry(tick.0).or_default() += claim;
+            let merged_checkpoint = fee_checkpoints.get_mut(&tick.0).unwrap();
+            merged_checkpoint.earned_x += checkpoint.earned_x;
+            merged_checkpoint.earned_y += checkpoint.earned_y;
+        }
+
+        /// Helper method to settle the range orders resting in a bin that a swap has moved past.
+        /// Range orders are only settled when the swap fully converted them, that is range orders of tokens x when moving up
+        /// and range orders of tokens y when moving down.
+        /// The share of the bin owned by the range orders is taken out and moved to the vault for settled range orders.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `tick` - Tick of the bin.
+        /// * `bin` - Bin after the swap moved past it.
+        /// * `moved_up` - Whether the swap moved up past the bin.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Bin` - Bin without the range orders.
+        /// 
+        /// # Requires
+        /// 
+        /// * The tick index has moved past `tick`.
+        /// 
+        /// # Events
+        /// 
+        /// * `SettleRangeOrdersEvent` - Event emitted when range orders are settled.
+        /// 
+        fn settle_range_orders(&mut self, tick: Tick, mut bin: Bin, moved_up: bool) -> Bin {
+            // Get range order group resting in the bin, if converted by the swap
+            let group = match self.range_order_map.get(&tick).map(|group| *group) {
+                Some(group) if group.is_x == moved_up => group,
+                _ => return bin,
+            };
+            self.range_order_map.remove(&tick);
+
+            // Take share of the range orders out of the bin
+            let amount = if group.claim == bin.total_claim {
+                bin.amount
+            } else {
+                group.claim / bin.total_claim * bin.amount
+            };
+            bin.amount -= amount;
+            bin.total_claim -= group.claim;
+
+            // Move converted tokens to the vault for settled range orders
+            let (amount_x, amount_y) = if group.is_x {
+                let tokens_y = self.tokens_y.take_advanced(amount, OUTGOING);
+                let amount_y = tokens_y.amount();
+                self.settled_tokens_y.put(tokens_y);
+                (Decimal::zero(), amount_y)
+            } else {
+                let tokens_x = self.tokens_x.take_advanced(amount, OUTGOING);
+                let amount_x = tokens_x.amount();
+                self.settled_tokens_x.put(tokens_x);
+                (amount_x, Decimal::zero())
+            };
+
+            // Remove from price index if the bin is empty
+            if bin.total_claim == Decimal::zero() {
+                self.tick_index.remove(tick);
+            }
+
+            // Set aside tokens for the range orders
+            self.settled_range_order_map.insert(group.group_id, SettledRangeOrders {
+                claim: group.claim,
+                amount_x,
+                amount_y,
+            });
+
+            // Emit settle range orders event
+            Runtime::emit_event(SettleRangeOrdersEvent {
+                tick: tick.0,
+                amount_x,
+                amount_y,
+            });
+
+            // Return bin
+            bin
+        }
+
+        /// Helper method to record a price observation on the first change of the price or active bin each minute.
+        /// Called before every method that can change the price, which is taken as the price held since the latest observation.
+        /// 
+        fn update_observations(&mut self) {
+            // Get price held since the latest observation
+            let price = match self.get_price() {
+                Some(price) => price,
+                None => return,
+            };
+            let now = Clock::current_time_rounded_to_minutes().seconds_since_unix_epoch;
+
+            // Record first observation
+            if self.observation_count == 0 {
+                self.observations.insert(0, Observation {
+                    timestamp: now,
+                    price_cumulative: Decimal::zero(),
+                });
+                self.observation_count = 1;
+                return;
+            }
+
+            // Skip if there is already an observation for this minute
+            let latest = *self.observations.get(&self.observation_index).unwrap();
+            if latest.timestamp >= now {
+                return;
+            }
+
+            // Record observation, overwriting the oldest once the ring buffer is full
+            self.observation_index = (self.observation_index + 1) % MAX_OBSERVATIONS;
+            self.observations.insert(self.observation_index, Observation {
+                timestamp: now,
+                price_cumulative: latest.price_cumulative + price * Decimal::from(now - latest.timestamp),
+            });
+            self.observation_count = (self.observation_count + 1).min(MAX_OBSERVATIONS);
+        }
+
+        /// Helper method to get the cumulative price at a time.
+        /// Times after the latest observation use the current price, and times between observations are interpolated.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `timestamp` - Time in seconds since the unix epoch.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Decimal` - Cumulative price at the time.
+        /// 
+        /// # Panics
+        /// 
+        /// * If the time is before the oldest price observation.
+        /// 
+        /// # Requires
+        /// 
+        /// * There is at least one price observation.
+        /// 
+        fn price_cumulative_at(&self, timestamp: i64) -> Decimal {
+            // Extend latest observation with the current price
+            let latest = *self.observations.get(&self.observation_index).unwrap();
+            if timestamp >= latest.timestamp {
+                let price = self.get_price().unwrap_or_default();
+                return latest.price_cumulative + price * Decimal::from(timestamp - latest.timestamp);
+            }
+
+            // Check time is not before the oldest observation
+            let oldest_index = if self.observation_count < MAX_OBSERVATIONS {
+                0
+            } else {
+                (self.observation_index + 1) % MAX_OBSERVATIONS
+            };
+            let get_observation = |position: u32| *self.observations.get(&((oldest_index + position) % MAX_OBSERVATIONS)).unwrap();
+            let oldest = get_observation(0);
+            assert!(timestamp >= oldest.timestamp, "Observation is older than the oldest observation.");
+
+            // Binary search for the observations around the time
+            let mut low = 0;
+            let mut high = self.observation_count - 1;
+            while high - low > 1 {
+                let mid = (low + high) / 2;
+                if get_observation(mid).timestamp <= timestamp {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            let before = get_observation(low);
+            let after = get_observation(high);
+
+            // Interpolate between observations
+            before.price_cumulative + (after.price_cumulative - before.price_cumulative) 
+                * Decimal::from(timestamp - before.timestamp) 
+                / Decimal::from(after.timestamp - before.timestamp)
+        }
+
         /// Helper method to add initial liquidity.
         /// 
         /// # Arguments
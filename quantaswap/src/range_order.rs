@@ -0,0 +1,34 @@
+use scrypto::prelude::*;
+
+/// Range order on a single bin stored in a liquidity receipt.
+/// Once a swap moves past the bin, the order is taken out of the bin and set aside in the converted tokens.
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct RangeOrder {
+    /// Id of the range order group the order belongs to.
+    pub group_id: u64,
+    /// Amount of claim on the bin.
+    pub claim: Decimal,
+}
+
+/// Group of range orders resting in a bin. All range orders in a group are settled together.
+#[derive(ScryptoSbor, Clone, Copy)]
+pub struct RangeOrderGroup {
+    /// Id of the range order group.
+    pub group_id: u64,
+    /// Total claim of the range orders in the group.
+    pub claim: Decimal,
+    /// Whether the range orders are of tokens x. Range orders of tokens x settle when a swap moves up past the bin,
+    /// and range orders of tokens y when a swap moves down past it.
+    pub is_x: bool,
+}
+
+/// Tokens set aside for a settled group of range orders that have not been removed yet.
+#[derive(ScryptoSbor, Clone, Copy)]
+pub struct SettledRangeOrders {
+    /// Total claim of the range orders that have not been removed.
+    pub claim: Decimal,
+    /// Amount of tokens x set aside.
+    pub amount_x: Decimal,
+    /// Amount of tokens y set aside.
+    pub amount_y: Decimal,
+}
@@ -40,6 +40,30 @@ pub fn calculate_swap_inverse(output_b: &Decimal, reserve_a: &I512, reserve_b: &
     Decimal(I192::try_from(input_a).unwrap())
 }
 
+/// Calculate the amount of tokens to swap to buy all of the output token in a bin, rounding up from zero 
+/// so that a bin holding any output tokens is never emptied for free.
+/// 
+/// # Arguments
+/// 
+/// * `output_b` - The amount of output tokens in the bin in `Decimal`.
+/// * `reserve_a` - The reserve of the input token in `I512` with base `10^36`.
+/// * `reserve_b` - The reserve of the output token in `I512` with base `10^36`
+/// 
+/// # Returns
+/// 
+/// * `Decimal` - The amount of tokens to swap.
+/// 
+pub fn calculate_swap_inverse_bin(output_b: &Decimal, reserve_a: &I512, reserve_b: &I512) -> Decimal {
+    let input_a = calculate_swap_inverse(output_b, reserve_a, reserve_b);
+    if input_a > Decimal::zero() {
+        input_a
+    } else if *output_b == Decimal::zero() {
+        Decimal::ZERO
+    } else {
+        Decimal(I192::from_digits([1, 0, 0]))
+    }
+}
+
 /// Calculate the amount of virtual amounts of tokens x and y given the real amounts of tokens x and y, 
 /// such that real tokens x and y are exhausted at the upper and lower limits respectively.
 /// 
@@ -100,3 +124,62 @@ pub fn calculate_price(virtual_x: &I512, virtual_y: &I512) -> Decimal {
     let price = virtual_y * _10_E18 / virtual_x;
     Decimal(I192::try_from(price).unwrap())
 }
+
+/// Calculate the price sqrt given the price.
+/// 
+/// # Arguments
+/// 
+/// * `price` - Price in `Decimal`.
+/// 
+/// # Returns
+/// 
+/// * `Decimal` - Price sqrt in `I192` with base `10^18`.
+/// 
+/// # Require
+/// 
+/// * `price` must be greater than 0.
+/// 
+pub fn calculate_price_sqrt(price: &Decimal) -> Decimal {
+    let price_sqrt = (I512::from(price.0) * _10_E18).sqrt();
+    Decimal(I192::try_from(price_sqrt).unwrap())
+}
+
+/// Calculate the amount of tokens x to swap to move the price down to a target price sqrt.
+/// 
+/// # Arguments
+/// 
+/// * `price_sqrt` - Target price sqrt in `Decimal`.
+/// * `virtual_x` - Virtual amount of tokens x in `I512` with base `10^36`.
+/// * `virtual_y` - Virtual amount of tokens y in `I512` with base `10^36`.
+/// 
+/// # Returns
+/// 
+/// * `Decimal` - The amount of tokens x to swap. Negative if the price is already below the target.
+/// 
+pub fn calculate_swap_x_to_price(price_sqrt: &Decimal, virtual_x: &I512, virtual_y: &I512) -> Decimal {
+    let liq = (*virtual_x * *virtual_y).sqrt(); // base e36
+    let target_x = liq * _10_E18 / I512::from(price_sqrt.0); // base e36
+    let input_x = (target_x - virtual_x) / _10_E18;
+
+    Decimal(I192::try_from(input_x).unwrap())
+}
+
+/// Calculate the amount of tokens y to swap to move the price up to a target price sqrt.
+/// 
+/// # Arguments
+/// 
+/// * `price_sqrt` - Target price sqrt in `Decimal`.
+/// * `virtual_x` - Virtual amount of tokens x in `I512` with base `10^36`.
+/// * `virtual_y` - Virtual amount of tokens y in `I512` with base `10^36`.
+/// 
+/// # Returns
+/// 
+/// * `Decimal` - The amount of tokens y to swap. Negative if the price is already above the target.
+/// 
+pub fn calculate_swap_y_to_price(price_sqrt: &Decimal, virtual_x: &I512, virtual_y: &I512) -> Decimal {
+    let liq = (*virtual_x * *virtual_y).sqrt(); // base e36
+    let target_y = liq * I512::from(price_sqrt.0) / _10_E18; // base e36
+    let input_y = (target_y - virtual_y) / _10_E18;
+
+    Decimal(I192::try_from(input_y).unwrap())
+}
@@ -0,0 +1,19 @@
+use scrypto::prelude::*;
+
+/// Expected outcome of a swap if executed against the current state of the pool.
+/// Amounts are not rounded to the divisibility of the tokens.
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct SwapQuote {
+    /// Amount of tokens bought.
+    pub amount_out: Decimal,
+    /// Price after the swap. None if the pool has no active bin.
+    pub price_after: Option<Decimal>,
+    /// Number of bins crossed before the swap ends.
+    pub bins_crossed: u32,
+    /// Amount of input tokens taken as protocol fee.
+    pub protocol_fee: Decimal,
+    /// Amount of input tokens taken as liquidity fee.
+    pub liquidity_fee: Decimal,
+    /// Amount of input tokens returned unused, including any unused fees.
+    pub amount_remaining: Decimal,
+}
@@ -0,0 +1,61 @@
+use scrypto::prelude::*;
+
+use crate::bin::*;
+use crate::swap_math::*;
+use crate::tick::*;
+
+/// State of the active bin copied from the pool, so a swap can be walked through the bins without changing any state.
+#[derive(Clone, Copy, Debug)]
+pub struct ActiveBin {
+    /// Tick of the active bin.
+    pub tick: Tick,
+    /// Amount of tokens x in the active bin.
+    pub amount_x: Decimal,
+    /// Amount of tokens y in the active bin.
+    pub amount_y: Decimal,
+    /// Total claim of liquidity positions in the active bin.
+    pub total_claim: Decimal,
+    /// Lower price sqrt limit of the active bin.
+    pub lower_limit: Decimal,
+    /// Upper price sqrt limit of the active bin.
+    pub upper_limit: Decimal,
+}
+
+impl ActiveBin {
+    /// Get the virtual amounts of the active bin.
+    /// 
+    /// # Returns
+    /// 
+    /// * `I512` - Virtual amount of tokens x in `I512` with base `10^36`.
+    /// * `I512` - Virtual amount of tokens y in `I512` with base `10^36`.
+    /// 
+    pub fn virtual_amounts(&self) -> (I512, I512) {
+        calculate_virtual_amounts(&self.amount_x, &self.amount_y, &self.upper_limit, &self.lower_limit)
+    }
+
+    /// Get the price of the active bin.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Decimal` - Price of the active bin.
+    /// 
+    pub fn price(&self) -> Decimal {
+        let (virtual_x, virtual_y) = self.virtual_amounts();
+        calculate_price(&virtual_x, &virtual_y)
+    }
+}
+
+/// Outcome of a swap walked through the bins. Executed by swaps and reported by quotes.
+#[derive(Clone)]
+pub struct SwapWalk {
+    /// Amount of input tokens swapped, not including liquidity fees.
+    pub amount_in: Decimal,
+    /// Amount of tokens bought.
+    pub amount_out: Decimal,
+    /// Bins the swap moved past in order. Format: (tick, bin after the swap, liquidity fee paid into the bin).
+    pub bins_crossed: Vec<(Tick, Bin, Decimal)>,
+    /// Liquidity fee paid into the active bin after the swap.
+    pub active_fee: Decimal,
+    /// Active bin after the swap.
+    pub active_bin: ActiveBin,
+}
@@ -24,6 +24,10 @@ pub fn assert_within_error_margin(actual: Decimal, expected: Decimal, margin_per
     );
 }
 
+pub fn set_time(round: u64, seconds: i64, vars: &mut Vars) {
+    vars.test_runner.advance_to_round_at_timestamp(Round::of(round), seconds * 1000).expect_commit_success();
+}
+
 pub fn get_balance(resource: ResourceAddress, vars: &mut Vars) -> Decimal {
     vars.test_runner.get_component_balance(vars.account_component, resource)
 }
@@ -3,6 +3,22 @@ use transaction::{builder::ManifestBuilder, model::TransactionManifestV1};
 use radix_engine::transaction::TransactionReceipt;
 
 use crate::common::vars::*;
+pub use ::quantaswap::swap_quote::SwapQuote;
+use ::quantaswap::events::MintLiquidityReceiptEvent;
+
+/// Liquidity shape.
+///
+/// * `Uniform` - Every bin gets the same weight.
+/// * `Normal(Decimal)` - Weight follows a normal curve centered on the active bin, with the standard deviation in bins.
+/// * `BidAsk` - Weight increases linearly with distance from the active bin.
+/// * `Curve` - Weight decreases linearly with distance from the active bin.
+#[derive(ManifestSbor, Clone, Copy)]
+pub enum LiquidityShape {
+    Uniform,
+    Normal(Decimal),
+    BidAsk,
+    Curve,
+}
 
 pub fn build_manifest(
     quantaswap_package: PackageAddress,
@@ -298,6 +314,78 @@ pub fn get_liquidity_claims(liquidity_receipt_id: NonFungibleLocalId, vars: &mut
     receipt.expect_commit_success().output::<HashMap<u32, Decimal>>(1)
 }
 
+pub fn get_earned_fees(liquidity_receipt_id: NonFungibleLocalId, vars: &mut Vars) -> HashMap<u32, (Decimal, Decimal)> {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.quantaswap_component,
+        "get_earned_fees",
+        manifest_args!(liquidity_receipt_id),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nGET EARNED FEES\n");
+    // println!("{:?}", receipt);
+    receipt.expect_commit_success().output::<HashMap<u32, (Decimal, Decimal)>>(1)
+}
+
+pub fn get_range_orders(liquidity_receipt_id: NonFungibleLocalId, vars: &mut Vars) -> Vec<(u32, Decimal, Decimal, bool)> {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.quantaswap_component,
+        "get_range_orders",
+        manifest_args!(liquidity_receipt_id),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nGET RANGE ORDERS\n");
+    // println!("{:?}", receipt);
+    receipt.expect_commit_success().output::<Vec<(u32, Decimal, Decimal, bool)>>(1)
+}
+
+pub fn get_twap(seconds_ago: u32, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.quantaswap_component,
+        "get_twap",
+        manifest_args!(seconds_ago),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nGET TWAP\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn observe(seconds_agos: Vec<u32>, vars: &mut Vars) -> Vec<Decimal> {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.quantaswap_component,
+        "observe",
+        manifest_args!(seconds_agos),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nOBSERVE\n");
+    // println!("{:?}", receipt);
+    receipt.expect_commit_success().output::<Vec<Decimal>>(1)
+}
+
 pub fn get_redemption_value(liquidity_receipt_id: NonFungibleLocalId, vars: &mut Vars) -> (Decimal, Decimal) {
     let manifest = ManifestBuilder::new()
     .call_method(
@@ -334,6 +422,36 @@ pub fn get_redemption_bin_values(liquidity_receipt_id: NonFungibleLocalId, vars:
     receipt.expect_commit_success().output::<Vec<(u32, Decimal, Decimal)>>(1)
 }
 
+pub fn quote_swap_receipt(token: ResourceAddress, amount: Decimal, vars: &mut Vars) -> TransactionReceipt {
+    quote_swap_with_limit_receipt(token, amount, None, vars)
+}
+
+pub fn quote_swap_with_limit_receipt(token: ResourceAddress, amount: Decimal, limit_price: Option<Decimal>, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+    .call_method(
+        vars.quantaswap_component,
+        "quote_swap",
+        manifest_args!(token, amount, limit_price),
+    )
+    .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nQUOTE SWAP\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn quote_swap(token: ResourceAddress, amount: Decimal, vars: &mut Vars) -> SwapQuote {
+    quote_swap_receipt(token, amount, vars).expect_commit_success().output::<SwapQuote>(1)
+}
+
+pub fn quote_swap_with_limit(token: ResourceAddress, amount: Decimal, limit_price: Option<Decimal>, vars: &mut Vars) -> SwapQuote {
+    quote_swap_with_limit_receipt(token, amount, limit_price, vars).expect_commit_success().output::<SwapQuote>(1)
+}
+
 pub fn mint_liquidity_receipt(vars: &mut Vars) -> NonFungibleLocalId {
     let manifest = ManifestBuilder::new()
         .call_method(
@@ -387,6 +505,130 @@ pub fn burn_liquidity_receipt(liquidity_receipt_id: NonFungibleLocalId, vars: &m
     receipt
 }
 
+pub fn split_liquidity_receipt(liquidity_receipt_id: NonFungibleLocalId, claims: Vec<(u32, Decimal)>, vars: &mut Vars) -> TransactionReceipt {
+    let ids = BTreeSet::from([liquidity_receipt_id]);
+
+    let manifest = ManifestBuilder::new()
+        .withdraw_non_fungibles_from_account(vars.account_component, vars.liquidity_receipt, ids.clone())
+        .take_non_fungibles_from_worktop(vars.liquidity_receipt, ids, "liquidity_receipt")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.quantaswap_component,
+                "split_liquidity_receipt",
+                manifest_args!(lookup.bucket("liquidity_receipt"), claims),
+            )
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nSPLIT LIQUIDITY RECEIPT\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn merge_liquidity_receipts(liquidity_receipt_ids: Vec<NonFungibleLocalId>, vars: &mut Vars) -> TransactionReceipt {
+    let ids: BTreeSet<NonFungibleLocalId> = liquidity_receipt_ids.into_iter().collect();
+
+    let manifest = ManifestBuilder::new()
+        .withdraw_non_fungibles_from_account(vars.account_component, vars.liquidity_receipt, ids.clone())
+        .take_non_fungibles_from_worktop(vars.liquidity_receipt, ids, "liquidity_receipts")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.quantaswap_component,
+                "merge_liquidity_receipts",
+                manifest_args!(lookup.bucket("liquidity_receipts")),
+            )
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nMERGE LIQUIDITY RECEIPTS\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn add_range_order(liquidity_receipt_id: NonFungibleLocalId, token: ResourceAddress, amount: Decimal, tick: u32, vars: &mut Vars) -> TransactionReceipt {
+    let ids = BTreeSet::from([liquidity_receipt_id]);
+
+    let manifest = ManifestBuilder::new()
+        .withdraw_non_fungibles_from_account(vars.account_component, vars.liquidity_receipt, ids.clone())
+        .withdraw_from_account(vars.account_component, token, amount)
+        .take_non_fungibles_from_worktop(vars.liquidity_receipt, ids, "liquidity_receipt")
+        .take_from_worktop(token, amount, "tokens")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.quantaswap_component,
+                "add_range_order",
+                manifest_args!(lookup.bucket("liquidity_receipt"), lookup.bucket("tokens"), tick),
+            )
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nADD RANGE ORDER\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn remove_range_order(liquidity_receipt_id: NonFungibleLocalId, tick: u32, vars: &mut Vars) -> TransactionReceipt {
+    let ids = BTreeSet::from([liquidity_receipt_id]);
+
+    let manifest = ManifestBuilder::new()
+        .withdraw_non_fungibles_from_account(vars.account_component, vars.liquidity_receipt, ids.clone())
+        .take_non_fungibles_from_worktop(vars.liquidity_receipt, ids, "liquidity_receipt")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.quantaswap_component,
+                "remove_range_order",
+                manifest_args!(lookup.bucket("liquidity_receipt"), tick),
+            )
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nREMOVE RANGE ORDER\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn get_minted_liquidity_receipt_id(receipt: &TransactionReceipt, vars: &mut Vars) -> NonFungibleLocalId {
+    let (_, event_data) = receipt.expect_commit_success().application_events.clone()
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<MintLiquidityReceiptEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    scrypto_decode::<MintLiquidityReceiptEvent>(&event_data).unwrap().liquidity_receipt_id
+}
+
 pub fn add_liquidity_to_receipt(
     liquidity_receipt_id: NonFungibleLocalId, 
     amount_x: Decimal, 
@@ -519,7 +761,83 @@ pub fn remove_liquidity(
     receipt
 }
 
+pub fn rebalance_liquidity(
+    liquidity_receipt_id: NonFungibleLocalId, 
+    amount_x: Decimal, 
+    amount_y: Decimal, 
+    remove_claims: Vec<(u32, Decimal)>, 
+    new_positions: Vec<(u32, Decimal, Decimal)>, 
+    vars: &mut Vars) -> TransactionReceipt {
+    let ids = BTreeSet::from([liquidity_receipt_id]);
+
+    let manifest = ManifestBuilder::new()
+        .withdraw_non_fungibles_from_account(vars.account_component, vars.liquidity_receipt, ids.clone())
+        .withdraw_from_account(vars.account_component, vars.token_x, amount_x)
+        .withdraw_from_account(vars.account_component, vars.token_y, amount_y)
+        .take_non_fungibles_from_worktop(vars.liquidity_receipt, ids, "liquidity_receipt")
+        .take_from_worktop(vars.token_x, amount_x, "tokens_x")
+        .take_from_worktop(vars.token_y, amount_y, "tokens_y")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.quantaswap_component,
+                "rebalance_liquidity",
+                manifest_args!(lookup.bucket("liquidity_receipt"), lookup.bucket("tokens_x"), lookup.bucket("tokens_y"), remove_claims, new_positions),
+            )
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nREBALANCE LIQUIDITY\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
+pub fn add_liquidity_with_distribution(
+    amount_x: Decimal, 
+    amount_y: Decimal, 
+    lower_tick: u32,
+    upper_tick: u32,
+    shape: LiquidityShape,
+    vars: &mut Vars) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(vars.account_component, vars.token_x, amount_x)
+        .withdraw_from_account(vars.account_component, vars.token_y, amount_y)
+        .take_from_worktop(vars.token_x, amount_x, "tokens_x")
+        .take_from_worktop(vars.token_y, amount_y, "tokens_y")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.quantaswap_component,
+                "add_liquidity_with_distribution",
+                manifest_args!(lookup.bucket("tokens_x"), lookup.bucket("tokens_y"), lower_tick, upper_tick, shape),
+            )
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nADD LIQUIDITY WITH DISTRIBUTION\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
 pub fn swap(token: ResourceAddress, amount: Decimal, vars: &mut Vars) -> TransactionReceipt {
+    swap_with_limits(token, amount, None, None, vars)
+}
+
+pub fn swap_with_limits(token: ResourceAddress, amount: Decimal, min_amount_out: Option<Decimal>, limit_price: Option<Decimal>, vars: &mut Vars) -> TransactionReceipt {
     let manifest = ManifestBuilder::new()
         .withdraw_from_account(vars.account_component, token, amount)
         .take_from_worktop(token, amount, "tokens")
@@ -527,7 +845,7 @@ pub fn swap(token: ResourceAddress, amount: Decimal, vars: &mut Vars) -> Transac
             builder.call_method(
                 vars.quantaswap_component,
                 "swap",
-                manifest_args!(lookup.bucket("tokens"))
+                manifest_args!(lookup.bucket("tokens"), min_amount_out, limit_price)
             )
         })
         .call_method(
@@ -545,6 +863,32 @@ pub fn swap(token: ResourceAddress, amount: Decimal, vars: &mut Vars) -> Transac
     receipt
 }
 
+pub fn swap_exact_output(token: ResourceAddress, amount: Decimal, amount_out: Decimal, vars: &mut Vars) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(vars.account_component, token, amount)
+        .take_from_worktop(token, amount, "tokens")
+        .with_name_lookup(|builder, lookup| {
+            builder.call_method(
+                vars.quantaswap_component,
+                "swap_exact_output",
+                manifest_args!(lookup.bucket("tokens"), amount_out)
+            )
+        })
+        .call_method(
+            vars.account_component,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+
+    let receipt = vars.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&vars.public_key)],
+    );
+    // println!("\nSWAP EXACT OUTPUT\n");
+    // println!("{:?}", receipt);
+    receipt
+}
+
 pub fn assert_amount_sums(vars: &mut Vars) {
     let bins_above = get_bins_above(None, None, None, vars);
     let bins_below = get_bins_below(None, None, None, vars);
@@ -7,6 +7,10 @@ use super::fee_vaults;
 use super::quantaswap;
 
 pub fn setup() -> Vars {
+    setup_with_divisibility(18, 18)
+}
+
+pub fn setup_with_divisibility(divisibility_x: u8, divisibility_y: u8) -> Vars {
     // Setup the environment
     let mut test_runner = TestRunnerBuilder::new().without_trace().build();
 
@@ -25,8 +29,6 @@ pub fn setup() -> Vars {
     // Create tokens
     let amount_x = Decimal(I192::from(2).pow(152)).checked_floor().unwrap();
     let amount_y = Decimal(I192::from(2).pow(152)).checked_floor().unwrap();
-    let divisibility_x = 18;
-    let divisibility_y = 18;
     let floop_token = test_runner.create_freely_mintable_and_burnable_fungible_resource(OwnerRole::None, Some(dec!(1000)), DIVISIBILITY_MAXIMUM, admin_account_component);
     let token_x = test_runner.create_fungible_resource(amount_x, divisibility_x, account_component);
     let token_y = test_runner.create_fungible_resource(amount_y, divisibility_y, account_component);
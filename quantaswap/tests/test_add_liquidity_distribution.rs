@@ -0,0 +1,222 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+fn bin_amount(tick: u32, vars: &mut Vars) -> Decimal {
+    get_bins_above(None, None, None, vars).into_iter()
+        .chain(get_bins_below(None, None, None, vars))
+        .find(|&(bin_tick, _)| bin_tick == tick)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_uniform() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    let span = vars.bin_span;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    add_liquidity_with_distribution(dec!(9), dec!(9), tick - 2 * span, tick + 2 * span, LiquidityShape::Uniform, &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(bin_amount(tick + span, &mut vars), dec!(3));
+    assert_eq!(bin_amount(tick + 2 * span, &mut vars), dec!(3));
+    assert_eq!(bin_amount(tick - span, &mut vars), dec!(3));
+    assert_eq!(bin_amount(tick - 2 * span, &mut vars), dec!(3));
+    assert_eq!(get_active_amounts(&mut vars), Some((dec!(8), dec!(8))));
+    assert_eq!(get_balance(vars.token_x, &mut vars), balance_x - dec!(9));
+    assert_eq!(get_balance(vars.token_y, &mut vars), balance_y - dec!(9));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_bid_ask() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    let span = vars.bin_span;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    add_liquidity_with_distribution(dec!(9), dec!(0), tick, tick + 3 * span, LiquidityShape::BidAsk, &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(bin_amount(tick + span, &mut vars), dec!(2));
+    assert_eq!(bin_amount(tick + 2 * span, &mut vars), dec!(3));
+    assert_eq!(bin_amount(tick + 3 * span, &mut vars), dec!(4));
+    assert_eq!(get_active_amounts(&mut vars), Some((dec!(5), dec!(5))));
+    assert_eq!(get_balance(vars.token_y, &mut vars), balance_y);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_curve() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    let span = vars.bin_span;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    add_liquidity_with_distribution(dec!(0), dec!(6), tick - 3 * span, tick, LiquidityShape::Curve, &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(bin_amount(tick - span, &mut vars), dec!(3));
+    assert_eq!(bin_amount(tick - 2 * span, &mut vars), dec!(2));
+    assert_eq!(bin_amount(tick - 3 * span, &mut vars), dec!(1));
+    assert_eq!(get_active_amounts(&mut vars), Some((dec!(5), dec!(5))));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_normal() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    let span = vars.bin_span;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    add_liquidity_with_distribution(dec!(10), dec!(10), tick - 2 * span, tick + 2 * span, LiquidityShape::Normal(dec!(1)), &mut vars)
+        .expect_commit_success();
+
+    let amount_near_x = bin_amount(tick + span, &mut vars);
+    let amount_far_x = bin_amount(tick + 2 * span, &mut vars);
+    let amount_near_y = bin_amount(tick - span, &mut vars);
+    let amount_far_y = bin_amount(tick - 2 * span, &mut vars);
+
+    assert!(amount_near_x > amount_far_x);
+    assert_within_error_margin(amount_near_x / amount_far_x, dec!("4.48168907033806482"), dec!("0.0001"));
+    assert_within_error_margin(amount_near_y / amount_far_y, dec!("4.48168907033806482"), dec!("0.0001"));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_normal_tiny_std_dev() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    let span = vars.bin_span;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    add_liquidity_with_distribution(dec!(10), dec!(10), tick - 2 * span, tick + 2 * span, LiquidityShape::Normal(dec!("0.000000000000000001")), &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(get_bins_above(None, None, None, &mut vars).len(), 1);
+    assert_eq!(get_bins_below(None, None, None, &mut vars).len(), 1);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_range_outside_active() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    let span = vars.bin_span;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    add_liquidity_with_distribution(dec!(4), dec!(4), tick + 2 * span, tick + 3 * span, LiquidityShape::Uniform, &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(bin_amount(tick + span, &mut vars), dec!(0));
+    assert_eq!(bin_amount(tick + 2 * span, &mut vars), dec!(2));
+    assert_eq!(bin_amount(tick + 3 * span, &mut vars), dec!(2));
+    assert_eq!(get_balance(vars.token_y, &mut vars), balance_y);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_no_active_bin_fail() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity_with_distribution(dec!(1), dec!(1), tick, tick, LiquidityShape::Uniform, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("No active bin.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_invalid_tick_fail() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    add_liquidity_with_distribution(dec!(1), dec!(1), tick + 1, tick + vars.bin_span, LiquidityShape::Uniform, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Invalid tick")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_inverted_range_fail() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    add_liquidity_with_distribution(dec!(1), dec!(1), tick + vars.bin_span, tick, LiquidityShape::Uniform, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Lower tick must not be greater than upper tick.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_too_many_bins_fail() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    add_liquidity_with_distribution(dec!(1), dec!(1), tick - 100 * vars.bin_span, tick + 100 * vars.bin_span, LiquidityShape::Uniform, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Too many liquidity claims for this liquidity receipt.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_add_liquidity_with_distribution_zero_std_dev_fail() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    add_liquidity_with_distribution(dec!(1), dec!(1), tick, tick, LiquidityShape::Normal(dec!(0)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Standard deviation must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -0,0 +1,157 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_earned_fees_no_swaps() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    assert_eq!(
+        get_earned_fees(id, &mut vars),
+        HashMap::from([(tick, (dec!(0), dec!(0)))])
+    );
+}
+
+#[test]
+fn test_earned_fees_swap_x() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = quote_swap(vars.token_x, dec!(1), &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+
+    let (fees_x, fees_y) = get_earned_fees(id, &mut vars)[&tick];
+    assert!(quote.liquidity_fee > Decimal::zero());
+    assert_within_error_margin(fees_x, quote.liquidity_fee, dec!("0.0001"));
+    assert_eq!(fees_y, dec!(0));
+}
+
+#[test]
+fn test_earned_fees_swap_y() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = quote_swap(vars.token_y, dec!(1), &mut vars);
+    swap(vars.token_y, dec!(1), &mut vars).expect_commit_success();
+
+    let (fees_x, fees_y) = get_earned_fees(id, &mut vars)[&tick];
+    assert!(quote.liquidity_fee > Decimal::zero());
+    assert_eq!(fees_x, dec!(0));
+    assert_within_error_margin(fees_y, quote.liquidity_fee, dec!("0.0001"));
+}
+
+#[test]
+fn test_earned_fees_crossing_bins() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_below = tick - vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(10), vec![(tick, dec!(5), dec!(5)), (tick_below, dec!(0), dec!(5))], &mut vars)
+        .expect_commit_success();
+
+    let quote = quote_swap(vars.token_x, dec!(8), &mut vars);
+    swap(vars.token_x, dec!(8), &mut vars).expect_commit_success();
+
+    let earned_fees = get_earned_fees(id, &mut vars);
+    let (fees_x, _) = earned_fees[&tick];
+    let (fees_x_below, _) = earned_fees[&tick_below];
+    assert_eq!(quote.bins_crossed, 1);
+    assert!(fees_x > Decimal::zero());
+    assert!(fees_x_below > Decimal::zero());
+    assert_within_error_margin(fees_x + fees_x_below, quote.liquidity_fee, dec!("0.0001"));
+}
+
+#[test]
+fn test_earned_fees_shared_between_receipts() {
+    let mut vars = setup();
+
+    let id_a = mint_liquidity_receipt(&mut vars);
+    let id_b = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id_a.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    add_liquidity_to_receipt(id_b.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = quote_swap(vars.token_x, dec!(1), &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+
+    let (fees_x_a, _) = get_earned_fees(id_a, &mut vars)[&tick];
+    let (fees_x_b, _) = get_earned_fees(id_b, &mut vars)[&tick];
+    assert_eq!(fees_x_a, fees_x_b);
+    assert_within_error_margin(fees_x_a + fees_x_b, quote.liquidity_fee, dec!("0.0001"));
+}
+
+#[test]
+fn test_earned_fees_added_after_swap() {
+    let mut vars = setup();
+
+    let id_a = mint_liquidity_receipt(&mut vars);
+    let id_b = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id_a.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let (fees_x_a, _) = get_earned_fees(id_a.clone(), &mut vars)[&tick];
+
+    add_liquidity_to_receipt(id_b.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    assert_eq!(get_earned_fees(id_b.clone(), &mut vars)[&tick], (dec!(0), dec!(0)));
+    assert_eq!(get_earned_fees(id_a.clone(), &mut vars)[&tick].0, fees_x_a);
+
+    swap(vars.token_y, dec!(1), &mut vars).expect_commit_success();
+
+    assert!(get_earned_fees(id_b, &mut vars)[&tick].1 > Decimal::zero());
+    assert_eq!(get_earned_fees(id_a, &mut vars)[&tick].0, fees_x_a);
+}
+
+#[test]
+fn test_earned_fees_partial_remove() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let earned_fees = get_earned_fees(id.clone(), &mut vars);
+
+    let claim = get_liquidity_claims(id.clone(), &mut vars)[&tick];
+    remove_specific_liquidity(id.clone(), vec![(tick, claim / 2)], &mut vars).expect_commit_success();
+
+    assert_eq!(get_earned_fees(id, &mut vars), earned_fees);
+}
+
+#[test]
+fn test_earned_fees_full_remove() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+
+    let claim = get_liquidity_claims(id.clone(), &mut vars)[&tick_above];
+    remove_specific_liquidity(id.clone(), vec![(tick_above, claim)], &mut vars).expect_commit_success();
+
+    let earned_fees = get_earned_fees(id, &mut vars);
+    assert_eq!(earned_fees.len(), 1);
+    assert!(earned_fees[&tick].0 > Decimal::zero());
+}
@@ -487,6 +487,88 @@ fn test_remove_liquidity_event_many() {
     assert_eq!(event.removed_y, vec![(tick2, -amount_y2), (tick0, -amount_y0)]);
 }
 
+#[test]
+fn test_rebalance_liquidity_event() {
+    let mut vars: Vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+
+    let tick0 = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(1), dec!(1), vec![(tick0, dec!(1), dec!(1))], &mut vars).expect_commit_success();
+
+    let tick1 = tick0 + vars.bin_span;
+    let tick2 = tick1 + vars.bin_span;
+    let amount_x1 = dec!(4);
+    let amount_x2 = dec!(3);
+    add_liquidity_to_receipt(id.clone(), amount_x1, dec!(0), vec![(tick1, amount_x1, dec!(0))], &mut vars).expect_commit_success();
+
+    let events = rebalance_liquidity(id.clone(), dec!(0), dec!(0), vec![(tick1, amount_x1)], vec![(tick2, amount_x2, dec!(0))], &mut vars)
+        .expect_commit_success().application_events.clone();
+
+    let (event_type_identifier, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<RebalanceLiquidityEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<RebalanceLiquidityEvent>(&event_data).unwrap();
+
+    assert_eq!(
+        event_type_identifier,
+        EventTypeIdentifier(
+            Emitter::Method(*vars.quantaswap_component.as_node_id(), ObjectModuleId::Main),
+            event_type_identifier.1.clone(),
+        )
+    );
+
+    assert_eq!(event.liquidity_receipt_id, id);
+    assert_eq!(event.amount_change_x, amount_x2 - amount_x1);
+    assert_eq!(event.amount_change_y, dec!(0));
+    assert_eq!(event.removed_x, vec![(tick1, -amount_x1)]);
+    assert_eq!(event.removed_y, vec![]);
+    assert_eq!(event.added_x, vec![(tick2, amount_x2)]);
+    assert_eq!(event.added_y, vec![]);
+}
+
+#[test]
+fn test_settle_range_orders_event() {
+    let mut vars: Vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick0 = Tick::ONE.0;
+    let tick1 = tick0 + vars.bin_span;
+    let tick2 = tick1 + vars.bin_span;
+    add_liquidity(dec!(2), dec!(1), vec![(tick0, dec!(1), dec!(1)), (tick2, dec!(1), dec!(0))], &mut vars).expect_commit_success();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    add_range_order(id.clone(), vars.token_x, dec!(1), tick1, &mut vars).expect_commit_success();
+
+    let events = swap(vars.token_y, dec!(3), &mut vars)
+        .expect_commit_success().application_events.clone();
+
+    let (event_type_identifier, event_data) = events
+        .into_iter()
+        .find(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<SettleRangeOrdersEvent>(event_type_identifier)
+        }).expect("Event not found");
+
+    let event = scrypto_decode::<SettleRangeOrdersEvent>(&event_data).unwrap();
+
+    assert_eq!(
+        event_type_identifier,
+        EventTypeIdentifier(
+            Emitter::Method(*vars.quantaswap_component.as_node_id(), ObjectModuleId::Main),
+            event_type_identifier.1.clone(),
+        )
+    );
+
+    let (_, _, amount_y, _) = get_range_orders(id, &mut vars)[0];
+    assert_eq!(event.tick, tick1);
+    assert_eq!(event.amount_x, dec!(0));
+    assert_eq!(event.amount_y, amount_y);
+}
+
 #[test]
 fn test_swap_event_x() {
     let mut vars = setup();
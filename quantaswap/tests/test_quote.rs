@@ -0,0 +1,259 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+fn tick_price(tick: u32) -> Decimal {
+    let price_sqrt: Decimal = Tick(tick).into();
+    price_sqrt * price_sqrt
+}
+
+fn assert_quote_matches_swap(token: ResourceAddress, amount: Decimal, vars: &mut Vars) -> SwapQuote {
+    assert_quote_matches_swap_with_limit(token, amount, None, vars)
+}
+
+fn assert_quote_matches_swap_with_limit(token: ResourceAddress, amount: Decimal, limit_price: Option<Decimal>, vars: &mut Vars) -> SwapQuote {
+    let (token_in, token_out) = if token == vars.token_x {
+        (vars.token_x, vars.token_y)
+    } else {
+        (vars.token_y, vars.token_x)
+    };
+
+    let quote = quote_swap_with_limit(token, amount, limit_price, vars);
+    let balance_in = get_balance(token_in, vars);
+    let balance_out = get_balance(token_out, vars);
+
+    swap_with_limits(token, amount, None, limit_price, vars).expect_commit_success();
+
+    assert_eq!(quote.amount_out, get_balance(token_out, vars) - balance_out);
+    assert_eq!(quote.amount_remaining, amount + get_balance(token_in, vars) - balance_in);
+    assert_eq!(quote.price_after, get_price(vars));
+
+    quote
+}
+
+#[test]
+fn test_quote_swap_no_liquidity_x() {
+    let mut vars = setup();
+
+    let quote = quote_swap(vars.token_x, dec!(1), &mut vars);
+
+    assert_eq!(quote.amount_out, dec!(0));
+    assert_eq!(quote.price_after, None);
+    assert_eq!(quote.bins_crossed, 0);
+    assert_eq!(quote.protocol_fee, dec!(0));
+    assert_eq!(quote.liquidity_fee, dec!(0));
+    assert_eq!(quote.amount_remaining, dec!(1));
+}
+
+#[test]
+fn test_quote_swap_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = assert_quote_matches_swap(vars.token_x, dec!(1), &mut vars);
+
+    assert_eq!(quote.bins_crossed, 0);
+    assert_eq!(quote.amount_remaining, dec!(0));
+}
+
+#[test]
+fn test_quote_swap_y() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = assert_quote_matches_swap(vars.token_y, dec!(1), &mut vars);
+
+    assert_eq!(quote.bins_crossed, 0);
+    assert_eq!(quote.amount_remaining, dec!(0));
+}
+
+#[test]
+fn test_quote_swap_does_not_change_state() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let price = get_price(&mut vars);
+    let active_amounts = get_active_amounts(&mut vars);
+
+    quote_swap(vars.token_x, dec!(1), &mut vars);
+
+    assert_eq!(get_price(&mut vars), price);
+    assert_eq!(get_active_amounts(&mut vars), active_amounts);
+}
+
+#[test]
+fn test_quote_swap_cross_many_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick0 = Tick::ONE.0;
+    let tick1 = tick0 - vars.bin_span;
+    let tick2 = tick1 - vars.bin_span;
+    add_liquidity(
+        dec!(0), 
+        dec!(3), 
+        vec![(tick0, dec!(0), dec!(1)), (tick1, dec!(0), dec!(1)), (tick2, dec!(0), dec!(1))], 
+        &mut vars
+    ).expect_commit_success();
+
+    let quote = assert_quote_matches_swap(vars.token_x, dec!("2.5"), &mut vars);
+
+    assert_eq!(quote.bins_crossed, 2);
+    assert_eq!(get_active_tick(&mut vars), Some(tick2));
+}
+
+#[test]
+fn test_quote_swap_cross_no_next_y() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!("0.1"), dec!(5), vec![(tick, dec!("0.1"), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = assert_quote_matches_swap(vars.token_y, dec!(1), &mut vars);
+
+    assert_eq!(quote.bins_crossed, 0);
+    assert!(quote.amount_remaining > dec!(0));
+}
+
+#[test]
+fn test_quote_swap_fees_x() {
+    let mut vars = setup();
+    let protocol_fee = fee_controller::get_protocol_fee_default(&mut vars);
+    let liquidity_fee = fee_controller::get_liquidity_fee_default(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let amount = dec!(1);
+    let quote = assert_quote_matches_swap(vars.token_x, amount, &mut vars);
+
+    assert_eq!(quote.protocol_fee, amount * protocol_fee);
+    assert_eq!(quote.liquidity_fee, (amount - amount * protocol_fee) * liquidity_fee);
+}
+
+#[test]
+fn test_quote_swap_fees_part_y() {
+    let mut vars = setup();
+    let protocol_fee = fee_controller::get_protocol_fee_default(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!("0.1"), dec!(5), vec![(tick, dec!("0.1"), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = assert_quote_matches_swap(vars.token_y, dec!(1), &mut vars);
+
+    assert!(quote.amount_remaining > dec!(0));
+    assert!(quote.protocol_fee < dec!(1) * protocol_fee);
+}
+
+#[test]
+fn test_quote_swap_limit_price_x() {
+    let mut vars = setup();
+
+    let tick0 = Tick::ONE.0;
+    let tick1 = tick0 - vars.bin_span;
+    let tick2 = tick1 - vars.bin_span;
+    add_liquidity(
+        dec!(0), 
+        dec!(3), 
+        vec![(tick0, dec!(0), dec!(1)), (tick1, dec!(0), dec!(1)), (tick2, dec!(0), dec!(1))], 
+        &mut vars
+    ).expect_commit_success();
+
+    let limit_price = tick_price(tick1 + vars.bin_span / 2);
+    let quote = assert_quote_matches_swap_with_limit(vars.token_x, dec!(3), Some(limit_price), &mut vars);
+
+    assert_eq!(quote.bins_crossed, 1);
+    assert!(quote.amount_remaining > dec!(0));
+    assert!(quote.price_after.unwrap() >= limit_price);
+    assert_eq!(get_active_tick(&mut vars), Some(tick1));
+}
+
+#[test]
+fn test_quote_swap_limit_price_y() {
+    let mut vars = setup();
+
+    let tick0 = Tick::ONE.0;
+    let tick1 = tick0 + vars.bin_span;
+    let tick2 = tick1 + vars.bin_span;
+    add_liquidity(
+        dec!(3), 
+        dec!(0), 
+        vec![(tick0, dec!(1), dec!(0)), (tick1, dec!(1), dec!(0)), (tick2, dec!(1), dec!(0))], 
+        &mut vars
+    ).expect_commit_success();
+
+    let limit_price = tick_price(tick1 + vars.bin_span / 2);
+    let quote = assert_quote_matches_swap_with_limit(vars.token_y, dec!(5), Some(limit_price), &mut vars);
+
+    assert_eq!(quote.bins_crossed, 1);
+    assert!(quote.amount_remaining > dec!(0));
+    assert!(quote.price_after.unwrap() <= limit_price);
+    assert_eq!(get_active_tick(&mut vars), Some(tick1));
+}
+
+#[test]
+fn test_quote_swap_limit_price_invalid() {
+    let mut vars = setup();
+
+    quote_swap_with_limit_receipt(vars.token_x, dec!(1), Some(dec!(0)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Limit price must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_quote_swap_zero_amount_invalid() {
+    let mut vars = setup();
+
+    quote_swap_receipt(vars.token_x, dec!(0), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Amount must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_quote_swap_invalid_token() {
+    let mut vars = setup();
+
+    let token = vars.test_runner.create_fungible_resource(dec!(1000), DIVISIBILITY_MAXIMUM, vars.account_component);
+
+    quote_swap_receipt(token, dec!(1), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Invalid token address.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -0,0 +1,269 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+/// Active bin with 5 tokens x and 5 tokens y, and a range order of 5 tokens x on the bin above.
+/// Returns the id of the range order liquidity receipt and the tick of the range order.
+fn setup_range_order(vars: &mut Vars) -> (NonFungibleLocalId, u32) {
+    fee_controller::set_protocol_fee_default_zero(vars);
+    fee_controller::set_liquidity_fee_default_zero(vars);
+
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let tick_above_2 = tick_above + vars.bin_span;
+    add_liquidity(dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above_2, dec!(5), dec!(0))], vars).expect_commit_success();
+
+    let id = mint_liquidity_receipt(vars);
+    add_range_order(id.clone(), vars.token_x, dec!(5), tick_above, vars).expect_commit_success();
+
+    (id, tick_above)
+}
+
+#[test]
+fn test_add_range_order() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+
+    assert_eq!(
+        get_range_orders(id, &mut vars),
+        vec![(tick, dec!(5), dec!(0), false)]
+    );
+    assert_eq!(
+        get_bins_above(None, None, None, &mut vars)[1],
+        (tick, dec!(5))
+    );
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_add_range_order_wrong_side_fail() {
+    let mut vars = setup();
+    let (id, _) = setup_range_order(&mut vars);
+
+    let tick_below = Tick::ONE.0 - vars.bin_span;
+    add_range_order(id, vars.token_x, dec!(1), tick_below, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Range orders of tokens x must be above the active bin.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_cancel_range_order() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+    let balance_x = get_balance(vars.token_x, &mut vars);
+
+    remove_range_order(id.clone(), tick, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_x, balance_x + dec!(5), &mut vars);
+    assert_eq!(get_range_orders(id, &mut vars), vec![]);
+    assert!(get_bins_above(None, None, None, &mut vars).iter().all(|&(bin_tick, _)| bin_tick != tick));
+}
+
+#[test]
+fn test_range_order_not_settled_when_partially_crossed() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+
+    swap(vars.token_y, dec!(7), &mut vars).expect_commit_success();
+
+    assert_eq!(get_active_tick(&mut vars), Some(tick));
+    let range_orders = get_range_orders(id, &mut vars);
+    let (_, amount_x, amount_y, settled) = range_orders[0];
+    assert!(!settled);
+    assert!(amount_x > Decimal::zero() && amount_x < dec!(5));
+    assert!(amount_y > Decimal::zero());
+}
+
+#[test]
+fn test_range_order_not_settled_when_partially_crossed_and_reversed() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+
+    swap(vars.token_y, dec!(7), &mut vars).expect_commit_success();
+    assert_eq!(get_active_tick(&mut vars), Some(tick));
+
+    // Price moving back down past the bin converts the range order back to tokens x
+    swap(vars.token_x, dec!(5), &mut vars).expect_commit_success();
+    assert!(get_active_tick(&mut vars).unwrap() < tick);
+
+    let (_, amount_x, amount_y, settled) = get_range_orders(id.clone(), &mut vars)[0];
+    assert!(!settled);
+    assert_within_error_margin(amount_x, dec!(5), dec!("0.0001"));
+    assert_eq!(amount_y, dec!(0));
+    assert!(get_bins_above(None, None, None, &mut vars).iter().any(|&(bin_tick, _)| bin_tick == tick));
+
+    // Range order settles once the price fully moves up past the bin
+    swap(vars.token_y, dec!(20), &mut vars).expect_commit_success();
+    assert!(get_active_tick(&mut vars).unwrap() > tick);
+
+    let (_, amount_x, amount_y, settled) = get_range_orders(id, &mut vars)[0];
+    assert!(settled);
+    assert_eq!(amount_x, dec!(0));
+    assert!(amount_y > dec!(5));
+    assert_amount_sums(&mut vars);
+}
+
+/// Active bin and the bin above the range order with only tokens x, so the pool holds no tokens y.
+fn setup_range_order_only_x(vars: &mut Vars) -> (NonFungibleLocalId, u32) {
+    fee_controller::set_protocol_fee_default_zero(vars);
+    fee_controller::set_liquidity_fee_default_zero(vars);
+
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let tick_above_2 = tick_above + vars.bin_span;
+    add_liquidity(dec!(10), dec!(0), vec![(tick, dec!(5), dec!(0)), (tick_above_2, dec!(5), dec!(0))], vars).expect_commit_success();
+
+    let id = mint_liquidity_receipt(vars);
+    add_range_order(id.clone(), vars.token_x, dec!(5), tick_above, vars).expect_commit_success();
+    assert_eq!(get_amount_y(vars), dec!(0));
+
+    (id, tick_above)
+}
+
+#[test]
+fn test_range_order_settles_when_pool_has_no_tokens_y() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order_only_x(&mut vars);
+
+    swap(vars.token_y, dec!(12), &mut vars).expect_commit_success();
+    assert!(get_active_tick(&mut vars).unwrap() > tick);
+
+    let (_, amount_x, amount_y, settled) = get_range_orders(id, &mut vars)[0];
+    assert!(settled);
+    assert_eq!(amount_x, dec!(0));
+    assert!(amount_y > dec!(0));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_range_order_settles_on_swap_exact_output_when_pool_has_no_tokens_y() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order_only_x(&mut vars);
+
+    swap_exact_output(vars.token_y, dec!(20), dec!(12), &mut vars).expect_commit_success();
+    assert!(get_active_tick(&mut vars).unwrap() > tick);
+
+    let (_, amount_x, amount_y, settled) = get_range_orders(id, &mut vars)[0];
+    assert!(settled);
+    assert_eq!(amount_x, dec!(0));
+    assert!(amount_y > dec!(0));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_range_order_settled_tokens_not_in_amounts() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+
+    swap(vars.token_y, dec!(12), &mut vars).expect_commit_success();
+    let amount_x = get_amount_x(&mut vars);
+    let amount_y = get_amount_y(&mut vars);
+    let (_, _, settled_y, settled) = get_range_orders(id.clone(), &mut vars)[0];
+    assert!(settled);
+
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    remove_range_order(id, tick, &mut vars).expect_commit_success();
+
+    assert_balance(vars.token_y, balance_y + settled_y, &mut vars);
+    assert_eq!(get_amount_x(&mut vars), amount_x);
+    assert_eq!(get_amount_y(&mut vars), amount_y);
+}
+
+#[test]
+fn test_range_order_settles_when_crossed() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+
+    swap(vars.token_y, dec!(12), &mut vars).expect_commit_success();
+    assert!(get_active_tick(&mut vars).unwrap() > tick);
+
+    let range_orders = get_range_orders(id.clone(), &mut vars);
+    let (_, amount_x, amount_y, settled) = range_orders[0];
+    assert!(settled);
+    assert_eq!(amount_x, dec!(0));
+    assert!(amount_y > dec!(5));
+    assert!(get_bins_below(None, None, None, &mut vars).iter().all(|&(bin_tick, _)| bin_tick != tick));
+
+    // Price reversing does not convert the settled range order back
+    swap(vars.token_x, dec!(12), &mut vars).expect_commit_success();
+    assert!(get_active_tick(&mut vars).unwrap() < tick);
+    assert_eq!(get_range_orders(id.clone(), &mut vars), range_orders);
+
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    remove_range_order(id.clone(), tick, &mut vars).expect_commit_success();
+    assert_balance(vars.token_y, balance_y + amount_y, &mut vars);
+    assert_eq!(get_range_orders(id, &mut vars), vec![]);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_range_order_settles_share_of_bin() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+    let liquidity_id = mint_liquidity_receipt(&mut vars);
+    add_liquidity_to_receipt(liquidity_id.clone(), dec!(5), dec!(0), vec![(tick, dec!(5), dec!(0))], &mut vars).expect_commit_success();
+
+    swap(vars.token_y, dec!(20), &mut vars).expect_commit_success();
+
+    let (_, _, amount_y, settled) = get_range_orders(id, &mut vars)[0];
+    let (liquidity_x, liquidity_y) = get_redemption_value(liquidity_id.clone(), &mut vars);
+    assert!(settled);
+    assert_eq!(liquidity_x, dec!(0));
+    assert_within_error_margin(amount_y, liquidity_y, dec!("0.0001"));
+    assert_eq!(get_liquidity_claims(liquidity_id, &mut vars), HashMap::from([(tick, dec!(5))]));
+}
+
+#[test]
+fn test_add_range_order_on_settled_tick_fail() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+
+    swap(vars.token_y, dec!(12), &mut vars).expect_commit_success();
+
+    add_range_order(id, vars.token_y, dec!(1), tick, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("must be removed first.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_remove_liquidity_removes_range_orders() {
+    let mut vars = setup();
+    let (id, tick) = setup_range_order(&mut vars);
+
+    swap(vars.token_y, dec!(12), &mut vars).expect_commit_success();
+    let (_, _, amount_y, _) = get_range_orders(id.clone(), &mut vars)[0];
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    burn_liquidity_receipt(id.clone(), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Cannot burn liquidity receipt with range orders.")
+            },
+            _ => false,
+        }
+    });
+
+    remove_liquidity(id, &mut vars).expect_commit_success();
+    assert_balance(vars.token_y, balance_y + amount_y, &mut vars);
+    assert!(get_bins_below(None, None, None, &mut vars).iter().all(|&(bin_tick, _)| bin_tick != tick));
+}
@@ -0,0 +1,155 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_rebalance_liquidity_move_bin() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let tick_new = tick + 2 * vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    rebalance_liquidity(id.clone(), dec!(0), dec!(0), vec![(tick_above, dec!(5))], vec![(tick_new, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(
+        get_liquidity_claims(id, &mut vars),
+        HashMap::from([(tick, dec!(5)), (tick_new, dec!(5))])
+    );
+    assert_balance(vars.token_x, balance_x, &mut vars);
+    assert_balance(vars.token_y, balance_y, &mut vars);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_rebalance_liquidity_extra_tokens() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let tick_new = tick + 2 * vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    rebalance_liquidity(id.clone(), dec!(3), dec!(0), vec![(tick_above, dec!(5))], vec![(tick_new, dec!(7), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(get_liquidity_claims(id, &mut vars)[&tick_new], dec!(7));
+    assert_balance(vars.token_x, balance_x - dec!(2), &mut vars);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_rebalance_liquidity_returns_excess() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let tick_new = tick + 2 * vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    rebalance_liquidity(id.clone(), dec!(0), dec!(0), vec![(tick_above, dec!(5))], vec![(tick_new, dec!(3), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(get_liquidity_claims(id, &mut vars)[&tick_new], dec!(3));
+    assert_balance(vars.token_x, balance_x + dec!(2), &mut vars);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_rebalance_liquidity_across_active_bin() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let tick_below = tick - vars.bin_span;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(0), vec![(tick_above, dec!(5), dec!(0))], &mut vars).expect_commit_success();
+
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    rebalance_liquidity(id.clone(), dec!(0), dec!(4), vec![(tick_above, dec!(5))], vec![(tick_below, dec!(0), dec!(4))], &mut vars)
+        .expect_commit_success();
+
+    assert_eq!(
+        get_liquidity_claims(id, &mut vars),
+        HashMap::from([(tick_below, dec!(4))])
+    );
+    assert_balance(vars.token_x, balance_x + dec!(5), &mut vars);
+    assert_balance(vars.token_y, balance_y - dec!(4), &mut vars);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_rebalance_liquidity_not_enough_tokens_fail() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let tick_new = tick + 2 * vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    rebalance_liquidity(id, dec!(1), dec!(0), vec![(tick_above, dec!(5))], vec![(tick_new, dec!(7), dec!(0))], &mut vars)
+        .expect_commit_failure();
+}
+
+#[test]
+fn test_rebalance_liquidity_claim_does_not_exist_fail() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    rebalance_liquidity(id, dec!(0), dec!(0), vec![(tick + vars.bin_span, dec!(1))], vec![], &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Claim does not exist.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_rebalance_liquidity_too_many_claims_fail() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let positions: Vec<(u32, Decimal, Decimal)> = (1..=200).map(|i| (tick + i * vars.bin_span, dec!("0.01"), dec!(0))).collect();
+    rebalance_liquidity(id, dec!(2), dec!(0), vec![], positions, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Too many liquidity claims for this liquidity receipt.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -0,0 +1,189 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+use radix_engine::transaction::TransactionReceipt;
+
+use ::quantaswap::events::BurnLiquidityReceiptEvent;
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+fn get_burned_liquidity_receipt_ids(receipt: &TransactionReceipt, vars: &mut Vars) -> Vec<NonFungibleLocalId> {
+    receipt.expect_commit_success().application_events.clone()
+        .into_iter()
+        .filter(|(event_type_identifier, _)| {
+            vars.test_runner.is_event_name_equal::<BurnLiquidityReceiptEvent>(event_type_identifier)
+        })
+        .map(|(_, event_data)| scrypto_decode::<BurnLiquidityReceiptEvent>(&event_data).unwrap().liquidity_receipt_id)
+        .collect()
+}
+
+#[test]
+fn test_split_liquidity_receipt_partial() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+    let redemption_value = get_redemption_value(id.clone(), &mut vars);
+
+    let receipt = split_liquidity_receipt(id.clone(), vec![(tick_above, dec!(2))], &mut vars);
+    let new_id = get_minted_liquidity_receipt_id(&receipt, &mut vars);
+
+    assert_eq!(
+        get_liquidity_claims(id.clone(), &mut vars),
+        HashMap::from([(tick, dec!(5)), (tick_above, dec!(3))])
+    );
+    assert_eq!(
+        get_liquidity_claims(new_id.clone(), &mut vars),
+        HashMap::from([(tick_above, dec!(2))])
+    );
+
+    let (amount_x, amount_y) = get_redemption_value(id, &mut vars);
+    let (new_amount_x, new_amount_y) = get_redemption_value(new_id, &mut vars);
+    assert_eq!(amount_x + new_amount_x, redemption_value.0);
+    assert_eq!(amount_y + new_amount_y, redemption_value.1);
+}
+
+#[test]
+fn test_split_liquidity_receipt_full_claim() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    add_liquidity_to_receipt(id.clone(), dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+
+    let receipt = split_liquidity_receipt(id.clone(), vec![(tick_above, dec!(5))], &mut vars);
+    let new_id = get_minted_liquidity_receipt_id(&receipt, &mut vars);
+
+    assert_eq!(
+        get_liquidity_claims(id.clone(), &mut vars),
+        HashMap::from([(tick, dec!(5))])
+    );
+    assert_eq!(
+        get_liquidity_claims(new_id.clone(), &mut vars),
+        HashMap::from([(tick_above, dec!(5))])
+    );
+
+    remove_liquidity(new_id, &mut vars).expect_commit_success();
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_split_liquidity_receipt_splits_fees() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let (fees_x, _) = get_earned_fees(id.clone(), &mut vars)[&tick];
+
+    let receipt = split_liquidity_receipt(id.clone(), vec![(tick, dec!("2.5"))], &mut vars);
+    let new_id = get_minted_liquidity_receipt_id(&receipt, &mut vars);
+
+    let (fees_x_kept, _) = get_earned_fees(id, &mut vars)[&tick];
+    let (fees_x_moved, _) = get_earned_fees(new_id, &mut vars)[&tick];
+    assert!(fees_x > Decimal::zero());
+    assert_eq!(fees_x_kept + fees_x_moved, fees_x);
+    assert_within_error_margin(fees_x_moved, fees_x / 2, dec!("0.0001"));
+}
+
+#[test]
+fn test_split_liquidity_receipt_claim_does_not_exist_fail() {
+    let mut vars = setup();
+
+    let id = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    split_liquidity_receipt(id, vec![(tick + vars.bin_span, dec!(1))], &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Claim does not exist.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_merge_liquidity_receipts() {
+    let mut vars = setup();
+
+    let id_a = mint_liquidity_receipt(&mut vars);
+    let id_b = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    add_liquidity_to_receipt(id_a.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    add_liquidity_to_receipt(id_b.clone(), dec!(8), dec!(3), vec![(tick, dec!(3), dec!(3)), (tick_above, dec!(5), dec!(0))], &mut vars)
+        .expect_commit_success();
+    let balance_receipts = get_balance(vars.liquidity_receipt, &mut vars);
+
+    let receipt = merge_liquidity_receipts(vec![id_a.clone(), id_b.clone()], &mut vars);
+    let burned_ids = get_burned_liquidity_receipt_ids(&receipt, &mut vars);
+    let merged_id = if burned_ids.contains(&id_a) { id_b } else { id_a };
+
+    assert_eq!(burned_ids.len(), 1);
+    assert_eq!(
+        get_liquidity_claims(merged_id, &mut vars),
+        HashMap::from([(tick, dec!(8)), (tick_above, dec!(5))])
+    );
+    assert_balance(vars.liquidity_receipt, balance_receipts - dec!(1), &mut vars);
+}
+
+#[test]
+fn test_merge_liquidity_receipts_adds_fees() {
+    let mut vars = setup();
+
+    let id_a = mint_liquidity_receipt(&mut vars);
+    let id_b = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id_a.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    add_liquidity_to_receipt(id_b.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+    swap(vars.token_y, dec!(1), &mut vars).expect_commit_success();
+    let (_, fees_y_a) = get_earned_fees(id_a.clone(), &mut vars)[&tick];
+    let (_, fees_y_b) = get_earned_fees(id_b.clone(), &mut vars)[&tick];
+
+    let receipt = merge_liquidity_receipts(vec![id_a.clone(), id_b.clone()], &mut vars);
+    let burned_ids = get_burned_liquidity_receipt_ids(&receipt, &mut vars);
+    let merged_id = if burned_ids.contains(&id_a) { id_b } else { id_a };
+
+    let (_, fees_y) = get_earned_fees(merged_id, &mut vars)[&tick];
+    assert!(fees_y > Decimal::zero());
+    assert_eq!(fees_y, fees_y_a + fees_y_b);
+}
+
+#[test]
+fn test_merge_liquidity_receipts_too_many_claims_fail() {
+    let mut vars = setup();
+
+    let id_a = mint_liquidity_receipt(&mut vars);
+    let id_b = mint_liquidity_receipt(&mut vars);
+    let tick = Tick::ONE.0;
+    add_liquidity_to_receipt(id_a.clone(), dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let positions_a: Vec<(u32, Decimal, Decimal)> = (1..=100).map(|i| (tick + i * vars.bin_span, dec!("0.01"), dec!(0))).collect();
+    let positions_b: Vec<(u32, Decimal, Decimal)> = (101..=200).map(|i| (tick + i * vars.bin_span, dec!("0.01"), dec!(0))).collect();
+    add_liquidity_to_receipt(id_a.clone(), dec!(1), dec!(0), positions_a, &mut vars).expect_commit_success();
+    add_liquidity_to_receipt(id_b.clone(), dec!(1), dec!(0), positions_b, &mut vars).expect_commit_success();
+
+    merge_liquidity_receipts(vec![id_a, id_b], &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Too many liquidity claims for this liquidity receipt.")
+            },
+            _ => false,
+        }
+    });
+}
@@ -0,0 +1,247 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+#[test]
+fn test_swap_exact_output_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let price = get_price(&mut vars).unwrap();
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    let amount_out = dec!(1);
+    swap_exact_output(vars.token_x, dec!(2), amount_out, &mut vars).expect_commit_success();
+
+    let new_price = get_price(&mut vars).unwrap();
+    let change_balance_x = get_balance(vars.token_x, &mut vars) - balance_x;
+    let change_balance_y = get_balance(vars.token_y, &mut vars) - balance_y;
+    let price_mid = (price * new_price).checked_sqrt().unwrap();
+
+    assert!(new_price < price);
+    assert_eq!(change_balance_y, amount_out);
+    assert_within_error_margin(-change_balance_x, amount_out / price_mid, dec!("0.01"));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_exact_output_y() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let price = get_price(&mut vars).unwrap();
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    let amount_out = dec!(1);
+    swap_exact_output(vars.token_y, dec!(2), amount_out, &mut vars).expect_commit_success();
+
+    let new_price = get_price(&mut vars).unwrap();
+    let change_balance_x = get_balance(vars.token_x, &mut vars) - balance_x;
+    let change_balance_y = get_balance(vars.token_y, &mut vars) - balance_y;
+    let price_mid = (price * new_price).checked_sqrt().unwrap();
+
+    assert!(new_price > price);
+    assert_eq!(change_balance_x, amount_out);
+    assert_within_error_margin(-change_balance_y, amount_out * price_mid, dec!("0.01"));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_exact_output_matches_swap_x() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = quote_swap(vars.token_x, dec!(1), &mut vars);
+    let balance_x = get_balance(vars.token_x, &mut vars);
+
+    swap_exact_output(vars.token_x, dec!(2), quote.amount_out, &mut vars).expect_commit_success();
+
+    let change_balance_x = get_balance(vars.token_x, &mut vars) - balance_x;
+    assert_within_error_margin(-change_balance_x, dec!(1), dec!("0.0001"));
+}
+
+#[test]
+fn test_swap_exact_output_cross_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick0 = Tick::ONE.0;
+    let tick1 = tick0 - vars.bin_span;
+    add_liquidity(dec!(0), dec!(2), vec![(tick0, dec!(0), dec!(1)), (tick1, dec!(0), dec!(1))], &mut vars).expect_commit_success();
+
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    swap_exact_output(vars.token_x, dec!(3), dec!("1.5"), &mut vars).expect_commit_success();
+
+    assert_eq!(get_balance(vars.token_y, &mut vars) - balance_y, dec!("1.5"));
+    assert_eq!(get_active_tick(&mut vars), Some(tick1));
+    assert_eq!(get_active_amounts(&mut vars).unwrap().1, dec!("0.5"));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_exact_output_cross_y() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick0 = Tick::ONE.0;
+    let tick1 = tick0 + vars.bin_span;
+    add_liquidity(dec!(2), dec!(0), vec![(tick0, dec!(1), dec!(0)), (tick1, dec!(1), dec!(0))], &mut vars).expect_commit_success();
+
+    let balance_x = get_balance(vars.token_x, &mut vars);
+
+    swap_exact_output(vars.token_y, dec!(3), dec!("1.5"), &mut vars).expect_commit_success();
+
+    assert_eq!(get_balance(vars.token_x, &mut vars) - balance_x, dec!("1.5"));
+    assert_eq!(get_active_tick(&mut vars), Some(tick1));
+    assert_eq!(get_active_amounts(&mut vars).unwrap().0, dec!("0.5"));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_exact_output_fees_x() {
+    let mut vars = setup();
+    let protocol_fee = fee_controller::get_protocol_fee_default(&mut vars);
+    let liquidity_fee = fee_controller::get_liquidity_fee_default(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let price = get_price(&mut vars).unwrap();
+    let balance_x = get_balance(vars.token_x, &mut vars);
+
+    let amount_out = dec!(1);
+    swap_exact_output(vars.token_x, dec!(2), amount_out, &mut vars).expect_commit_success();
+
+    let new_price = get_price(&mut vars).unwrap();
+    let change_balance_x = get_balance(vars.token_x, &mut vars) - balance_x;
+    let price_mid = (price * new_price).checked_sqrt().unwrap();
+    let expected_x = amount_out / price_mid / (dec!(1) - liquidity_fee) / (dec!(1) - protocol_fee);
+
+    assert_within_error_margin(-change_balance_x, expected_x, dec!("0.01"));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_exact_output_not_enough_liquidity_x() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    swap_exact_output(vars.token_x, dec!(100), dec!(6), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Not enough liquidity.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_swap_exact_output_no_liquidity_y() {
+    let mut vars = setup();
+
+    swap_exact_output(vars.token_y, dec!(1), dec!(1), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Not enough liquidity.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_swap_exact_output_not_enough_tokens_y() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    swap_exact_output(vars.token_y, dec!("0.5"), dec!(1), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Not enough tokens to buy amount out.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_swap_exact_output_zero_amount_invalid() {
+    let mut vars = setup();
+
+    swap_exact_output(vars.token_x, dec!(1), dec!(0), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Amount out must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_swap_exact_output_divisibility_invalid_x() {
+    let mut vars = setup_with_divisibility(18, 2);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    swap_exact_output(vars.token_x, dec!(2), dec!("0.001"), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Amount out must be a multiple of the divisibility of the tokens bought.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_swap_exact_output_rounded_input_y() {
+    let mut vars = setup_with_divisibility(2, 2);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    swap_exact_output(vars.token_y, dec!(2), dec!(1), &mut vars).expect_commit_success();
+
+    let change_balance_x = get_balance(vars.token_x, &mut vars) - balance_x;
+    let change_balance_y = get_balance(vars.token_y, &mut vars) - balance_y;
+
+    assert_eq!(change_balance_x, dec!(1));
+    assert!(change_balance_y < dec!(-1));
+    assert_eq!(change_balance_y.checked_round(2, RoundingMode::ToZero).unwrap(), change_balance_y);
+    assert_amount_sums(&mut vars);
+}
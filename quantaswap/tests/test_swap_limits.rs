@@ -0,0 +1,186 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+fn tick_price(tick: u32) -> Decimal {
+    let price_sqrt: Decimal = Tick(tick).into();
+    price_sqrt * price_sqrt
+}
+
+#[test]
+fn test_swap_min_amount_out() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = quote_swap(vars.token_x, dec!(1), &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    swap_with_limits(vars.token_x, dec!(1), Some(quote.amount_out), None, &mut vars).expect_commit_success();
+
+    assert_eq!(get_balance(vars.token_y, &mut vars) - balance_y, quote.amount_out);
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_min_amount_out_fail() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let quote = quote_swap(vars.token_y, dec!(1), &mut vars);
+
+    swap_with_limits(vars.token_y, dec!(1), Some(quote.amount_out + dec!("0.000001")), None, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Amount out is less than minimum amount out.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_swap_limit_price_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let price = get_price(&mut vars).unwrap();
+    let limit_price = tick_price(tick + 5);
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    assert!(limit_price < price);
+
+    swap_with_limits(vars.token_x, dec!(5), None, Some(limit_price), &mut vars).expect_commit_success();
+
+    let new_price = get_price(&mut vars).unwrap();
+    let change_balance_x = get_balance(vars.token_x, &mut vars) - balance_x;
+
+    assert!(new_price >= limit_price);
+    assert_within_error_margin(new_price, limit_price, dec!("0.0001"));
+    assert!(-change_balance_x < dec!(5));
+    assert_eq!(get_active_tick(&mut vars), Some(tick));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_limit_price_y() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let price = get_price(&mut vars).unwrap();
+    let limit_price = tick_price(tick + 15);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+    assert!(limit_price > price);
+
+    swap_with_limits(vars.token_y, dec!(5), None, Some(limit_price), &mut vars).expect_commit_success();
+
+    let new_price = get_price(&mut vars).unwrap();
+    let change_balance_y = get_balance(vars.token_y, &mut vars) - balance_y;
+
+    assert!(new_price <= limit_price);
+    assert_within_error_margin(new_price, limit_price, dec!("0.0001"));
+    assert!(-change_balance_y < dec!(5));
+    assert_eq!(get_active_tick(&mut vars), Some(tick));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_limit_price_refunds_fees() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    let price = get_price(&mut vars).unwrap();
+    let balance_x = get_balance(vars.token_x, &mut vars);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    swap_with_limits(vars.token_x, dec!(1), None, Some(price * dec!(2)), &mut vars).expect_commit_success();
+
+    assert_eq!(get_price(&mut vars).unwrap(), price);
+    assert_eq!(get_balance(vars.token_x, &mut vars), balance_x);
+    assert_eq!(get_balance(vars.token_y, &mut vars), balance_y);
+}
+
+#[test]
+fn test_swap_limit_price_stops_before_next_bin_x() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    let tick_below = tick - 2 * vars.bin_span;
+    add_liquidity(dec!(5), dec!(10), vec![(tick, dec!(5), dec!(5)), (tick_below, dec!(0), dec!(5))], &mut vars).expect_commit_success();
+
+    let limit_price = tick_price(tick - vars.bin_span / 2);
+    let balance_y = get_balance(vars.token_y, &mut vars);
+
+    swap_with_limits(vars.token_x, dec!(100), None, Some(limit_price), &mut vars).expect_commit_success();
+
+    let change_balance_y = get_balance(vars.token_y, &mut vars) - balance_y;
+
+    assert_eq!(get_active_tick(&mut vars), Some(tick));
+    assert_within_error_margin(change_balance_y, dec!(5), dec!("0.0001"));
+    assert!(change_balance_y <= dec!(5));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_limit_price_stops_before_next_bin_y() {
+    let mut vars = setup();
+    fee_controller::set_protocol_fee_default_zero(&mut vars);
+    fee_controller::set_liquidity_fee_default_zero(&mut vars);
+
+    let tick = Tick::ONE.0;
+    let tick_above = tick + 2 * vars.bin_span;
+    add_liquidity(dec!(10), dec!(5), vec![(tick, dec!(5), dec!(5)), (tick_above, dec!(5), dec!(0))], &mut vars).expect_commit_success();
+
+    let limit_price = tick_price(tick + vars.bin_span + vars.bin_span / 2);
+    let balance_x = get_balance(vars.token_x, &mut vars);
+
+    swap_with_limits(vars.token_y, dec!(100), None, Some(limit_price), &mut vars).expect_commit_success();
+
+    let change_balance_x = get_balance(vars.token_x, &mut vars) - balance_x;
+
+    assert_eq!(get_active_tick(&mut vars), Some(tick));
+    assert_within_error_margin(change_balance_x, dec!(5), dec!("0.0001"));
+    assert!(change_balance_x <= dec!(5));
+    assert_amount_sums(&mut vars);
+}
+
+#[test]
+fn test_swap_limit_price_zero_fail() {
+    let mut vars = setup();
+
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(5), dec!(5), vec![(tick, dec!(5), dec!(5))], &mut vars).expect_commit_success();
+
+    swap_with_limits(vars.token_x, dec!(1), None, Some(dec!(0)), &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Limit price must be greater than zero.")
+            },
+            _ => false,
+        }
+    });
+}
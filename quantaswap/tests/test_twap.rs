@@ -0,0 +1,154 @@
+#![allow(dead_code)]
+use scrypto::prelude::*;
+use radix_engine::errors::RuntimeError::ApplicationError;
+use radix_engine::errors::ApplicationError::PanicMessage;
+
+use ::quantaswap::tick::Tick;
+
+mod common;
+pub use crate::common::vars::*;
+pub use crate::common::setup::*;
+pub use crate::common::misc::*;
+pub use crate::common::quantaswap::*;
+pub use crate::common::fee_controller;
+
+// Start time in seconds, aligned to the minute
+const START: i64 = 1_699_999_980;
+
+fn setup_twap(vars: &mut Vars) {
+    let tick = Tick::ONE.0;
+    add_liquidity(dec!(100), dec!(100), vec![(tick, dec!(100), dec!(100))], vars).expect_commit_success();
+}
+
+#[test]
+fn test_twap_constant_price() {
+    let mut vars = setup();
+    setup_twap(&mut vars);
+
+    set_time(1, START, &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let price = get_price(&mut vars).unwrap();
+
+    set_time(2, START + 600, &mut vars);
+    let twap = get_twap(300, &mut vars).expect_commit_success().output::<Decimal>(1);
+
+    assert_eq!(twap, price);
+}
+
+#[test]
+fn test_twap_time_weighted() {
+    let mut vars = setup();
+    setup_twap(&mut vars);
+
+    set_time(1, START, &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let price_1 = get_price(&mut vars).unwrap();
+
+    set_time(2, START + 600, &mut vars);
+    swap(vars.token_y, dec!(20), &mut vars).expect_commit_success();
+    let price_2 = get_price(&mut vars).unwrap();
+
+    set_time(3, START + 1800, &mut vars);
+    let twap = get_twap(1800, &mut vars).expect_commit_success().output::<Decimal>(1);
+
+    assert!(price_2 > price_1);
+    assert_within_error_margin(twap, (price_1 * 600 + price_2 * 1200) / 1800, dec!("0.0001"));
+}
+
+#[test]
+fn test_twap_ignores_swaps_in_same_minute() {
+    let mut vars = setup();
+    setup_twap(&mut vars);
+
+    set_time(1, START, &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let price = get_price(&mut vars).unwrap();
+
+    set_time(2, START + 600, &mut vars);
+    swap(vars.token_y, dec!(50), &mut vars).expect_commit_success();
+    swap(vars.token_y, dec!(50), &mut vars).expect_commit_success();
+    let twap = get_twap(600, &mut vars).expect_commit_success().output::<Decimal>(1);
+
+    assert!(get_price(&mut vars).unwrap() > price);
+    assert_eq!(twap, price);
+}
+
+#[test]
+fn test_observe_interpolates_between_observations() {
+    let mut vars = setup();
+    setup_twap(&mut vars);
+
+    set_time(1, START, &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let price_1 = get_price(&mut vars).unwrap();
+
+    set_time(2, START + 600, &mut vars);
+    swap(vars.token_y, dec!(20), &mut vars).expect_commit_success();
+
+    set_time(3, START + 1200, &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+
+    let price_cumulatives = observe(vec![1200, 900, 600], &mut vars);
+    assert_eq!(price_cumulatives[0], dec!(0));
+    assert_within_error_margin(price_cumulatives[1], price_1 * 300, dec!("0.0001"));
+    assert_within_error_margin(price_cumulatives[2], price_1 * 600, dec!("0.0001"));
+}
+
+#[test]
+fn test_twap_records_observation_on_liquidity_change() {
+    let mut vars = setup();
+    let tick = Tick::ONE.0;
+    let tick_above = tick + vars.bin_span;
+    let id = mint_liquidity_receipt(&mut vars);
+    add_liquidity_to_receipt(id.clone(), dec!(200), dec!(100), vec![(tick, dec!(100), dec!(100)), (tick_above, dec!(100), dec!(0))], &mut vars).expect_commit_success();
+
+    set_time(1, START, &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+    let price_1 = get_price(&mut vars).unwrap();
+
+    // Removing the active bin moves the price to the bin above
+    set_time(2, START + 600, &mut vars);
+    let claim = get_liquidity_claims(id.clone(), &mut vars)[&tick];
+    remove_specific_liquidity(id, vec![(tick, claim)], &mut vars).expect_commit_success();
+    let price_2 = get_price(&mut vars).unwrap();
+
+    set_time(3, START + 1800, &mut vars);
+    let twap = get_twap(1800, &mut vars).expect_commit_success().output::<Decimal>(1);
+
+    assert!(price_2 > price_1);
+    assert_within_error_margin(twap, (price_1 * 600 + price_2 * 1200) / 1800, dec!("0.0001"));
+}
+
+#[test]
+fn test_twap_no_observations_fail() {
+    let mut vars = setup();
+    setup_twap(&mut vars);
+
+    get_twap(60, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("No price observations.")
+            },
+            _ => false,
+        }
+    });
+}
+
+#[test]
+fn test_twap_older_than_observations_fail() {
+    let mut vars = setup();
+    setup_twap(&mut vars);
+
+    set_time(1, START, &mut vars);
+    swap(vars.token_x, dec!(1), &mut vars).expect_commit_success();
+
+    set_time(2, START + 600, &mut vars);
+    get_twap(1200, &mut vars).expect_specific_failure(|err| {
+        match err {
+            ApplicationError(PanicMessage(msg)) => {
+                msg.contains("Observation is older than the oldest observation.")
+            },
+            _ => false,
+        }
+    });
+}
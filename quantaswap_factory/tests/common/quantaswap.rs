@@ -448,7 +448,7 @@ pub fn swap(token: ResourceAddress, amount: Decimal, vars: &mut Vars) -> Transac
             builder.call_method(
                 vars.quantaswap_component,
                 "swap",
-                manifest_args!(lookup.bucket("tokens"))
+                manifest_args!(lookup.bucket("tokens"), None::<Decimal>, None::<Decimal>)
             )
         })
         .call_method(